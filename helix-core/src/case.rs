@@ -0,0 +1,117 @@
+//! Locale-sensitive case mapping.
+//!
+//! Rust's `char::to_uppercase`/`to_lowercase` implement the
+//! locale-independent Unicode mappings, which are wrong for Turkish and
+//! Azeri: those languages pair dotted `i`/`İ` and dotless `ı`/`I`, so e.g.
+//! uppercasing `i` must yield `İ`, not `I`.
+
+use serde::{Deserialize, Serialize};
+
+/// Which mapping [`change_case`] applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaseMode {
+    Upper,
+    Lower,
+    /// Swap the case of cased characters and leave the rest alone.
+    Switch,
+}
+
+/// The locale used to pick case mappings, usually from `editor.locale`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Locale {
+    /// Detect the locale from `LC_ALL`, `LC_CTYPE` or `LANG`.
+    #[default]
+    Auto,
+    /// The locale-independent Unicode mappings.
+    Root,
+    /// Turkish and Azeri dotted/dotless-i handling.
+    Turkish,
+}
+
+impl Locale {
+    /// Resolves `Auto` against the process environment.
+    fn resolve(self) -> Self {
+        if self != Locale::Auto {
+            return self;
+        }
+        let lang = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .find_map(|var| std::env::var(var).ok().filter(|value| !value.is_empty()));
+        match lang {
+            Some(lang) if lang.starts_with("tr") || lang.starts_with("az") => Locale::Turkish,
+            _ => Locale::Root,
+        }
+    }
+}
+
+/// Changes the case of `text` according to `mode`, using the case mappings
+/// of `locale`.
+pub fn change_case(text: &str, mode: CaseMode, locale: Locale) -> String {
+    let locale = locale.resolve();
+    let mut res = String::with_capacity(text.len());
+    for c in text.chars() {
+        match mode {
+            CaseMode::Upper => push_upper(c, locale, &mut res),
+            CaseMode::Lower => push_lower(c, locale, &mut res),
+            CaseMode::Switch => {
+                if c.is_uppercase() {
+                    push_lower(c, locale, &mut res)
+                } else if c.is_lowercase() {
+                    push_upper(c, locale, &mut res)
+                } else {
+                    res.push(c)
+                }
+            }
+        }
+    }
+    res
+}
+
+fn push_upper(c: char, locale: Locale, buf: &mut String) {
+    match (locale, c) {
+        (Locale::Turkish, 'i') => buf.push('İ'),
+        (Locale::Turkish, 'ı') => buf.push('I'),
+        _ => buf.extend(c.to_uppercase()),
+    }
+}
+
+fn push_lower(c: char, locale: Locale, buf: &mut String) {
+    match (locale, c) {
+        (Locale::Turkish, 'I') => buf.push('ı'),
+        // the default mapping produces `i` plus a combining dot above
+        (Locale::Turkish, 'İ') => buf.push('i'),
+        _ => buf.extend(c.to_lowercase()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn turkish_dotted_and_dotless_i() {
+        assert_eq!(
+            change_case("istanbul", CaseMode::Upper, Locale::Turkish),
+            "İSTANBUL"
+        );
+        assert_eq!(
+            change_case("DİYARBAKIR", CaseMode::Lower, Locale::Turkish),
+            "diyarbakır"
+        );
+        assert_eq!(change_case("Iı", CaseMode::Switch, Locale::Turkish), "ıI");
+        assert_eq!(change_case("İi", CaseMode::Switch, Locale::Turkish), "iİ");
+    }
+
+    #[test]
+    fn root_locale_keeps_unicode_mappings() {
+        assert_eq!(
+            change_case("istanbul", CaseMode::Upper, Locale::Root),
+            "ISTANBUL"
+        );
+        assert_eq!(
+            change_case("Straße", CaseMode::Upper, Locale::Root),
+            "STRASSE"
+        );
+    }
+}
@@ -4,7 +4,8 @@
 use smallvec::SmallVec;
 
 use crate::{
-    syntax::BlockCommentToken, Change, Range, Rope, RopeSlice, Selection, Tendril, Transaction,
+    syntax::BlockCommentToken, Change, Range, Rope, RopeSlice, Selection, Syntax, Tendril,
+    Transaction,
 };
 use helix_stdx::rope::RopeSliceExt;
 use std::borrow::Cow;
@@ -314,6 +315,46 @@ pub fn toggle_block_comments(
     transaction
 }
 
+/// Whether the node kind names a comment or string. Grammars don't agree on
+/// exact names (`comment`, `line_comment`, `string_literal`, ...), so this
+/// matches on substrings.
+fn is_comment_or_string_kind(kind: &str) -> bool {
+    kind.contains("comment") || kind.contains("string")
+}
+
+/// Returns `true` if the character before `pos` lies inside a comment or
+/// string node of the syntax tree.
+///
+/// The character *before* the cursor is inspected because in insert mode the
+/// cursor sits one past the text being continued; checking the cursor itself
+/// would miss typing at the end of a comment.
+pub fn cursor_in_comment_or_string(syntax: &Syntax, text: RopeSlice, pos: usize) -> bool {
+    cursor_context_matches(syntax, text, pos, is_comment_or_string_kind)
+}
+
+/// Returns `true` if the character before `pos` lies inside a string node
+/// (comments don't count). See [`cursor_in_comment_or_string`] for how the
+/// position is interpreted.
+pub fn cursor_in_string(syntax: &Syntax, text: RopeSlice, pos: usize) -> bool {
+    cursor_context_matches(syntax, text, pos, |kind| kind.contains("string"))
+}
+
+/// Whether the node before `pos` or one of its ancestors has a kind matching
+/// `pred`.
+fn cursor_context_matches(
+    syntax: &Syntax,
+    text: RopeSlice,
+    pos: usize,
+    pred: impl Fn(&str) -> bool,
+) -> bool {
+    let byte = text.char_to_byte(pos.min(text.len_chars())).saturating_sub(1);
+    let root = syntax.tree_for_byte_range(byte, byte).root_node();
+    let Some(node) = root.descendant_for_byte_range(byte, byte) else {
+        return false;
+    };
+    std::iter::successors(Some(node), |node| node.parent()).any(|node| pred(node.kind()))
+}
+
 pub fn split_lines_of_selection(text: RopeSlice, selection: &Selection) -> Selection {
     let mut ranges = SmallVec::new();
     for range in selection.ranges() {
@@ -1,7 +1,7 @@
 use std::ops::DerefMut;
 
 use nucleo::pattern::{Atom, AtomKind, CaseMatching, Normalization};
-use nucleo::Config;
+use nucleo::{Config, Utf32Str};
 use parking_lot::Mutex;
 
 pub struct LazyMutex<T> {
@@ -47,3 +47,42 @@ pub fn fuzzy_match<T: AsRef<str>>(
     );
     pattern.match_list(items, &mut matcher)
 }
+
+/// Scores a single candidate against `pattern` with the same Smith-Waterman
+/// scoring [`fuzzy_match`] uses: consecutive matches, matches on word
+/// boundaries (after `_`, `-`, `.`, `/` or a case change), matches at the
+/// start of the candidate and exact-case matches all score higher. Returns
+/// `None` when the pattern does not match at all.
+pub fn fuzzy_score(pattern: &str, candidate: &str, path: bool) -> Option<u16> {
+    let mut matcher = MATCHER.lock();
+    matcher.config = Config::DEFAULT;
+    if path {
+        matcher.config.set_match_paths();
+    }
+    let pattern = Atom::new(
+        pattern,
+        CaseMatching::Smart,
+        Normalization::Smart,
+        AtomKind::Fuzzy,
+        false,
+    );
+    let mut buf = Vec::new();
+    pattern.score(Utf32Str::new(candidate, &mut buf), &mut matcher)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn score_prefers_boundaries_and_runs() {
+        let score = |pattern, candidate| fuzzy_score(pattern, candidate, false);
+
+        // a match on a word boundary beats one in the middle of a word
+        assert!(score("fb", "foo_bar") > score("fb", "foabar"));
+        // consecutive matches beat the same characters across a gap
+        assert!(score("foo", "foobar") > score("foo", "fxoxobar"));
+        // non-matches score `None`
+        assert_eq!(score("xyz", "foobar"), None);
+    }
+}
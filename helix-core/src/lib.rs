@@ -1,6 +1,7 @@
 pub use encoding_rs as encoding;
 
 pub mod auto_pairs;
+pub mod case;
 pub mod case_conversion;
 pub mod chars;
 pub mod comment;
@@ -13,6 +13,7 @@ use crate::{
     },
     line_ending::rope_is_line_ending,
     position::char_idx_at_visual_block_offset,
+    search,
     syntax::LanguageConfiguration,
     text_annotations::TextAnnotations,
     textobject::TextObject,
@@ -387,6 +388,73 @@ where
     })
 }
 
+/// Finds the nearest occurrence of `ch` after (or, with `forward` unset,
+/// strictly before) `start`, like [`search::find_nth_next`] and
+/// [`search::find_nth_prev`] do.
+///
+/// When a syntax tree is available, occurrences inside string or comment
+/// nodes can additionally be skipped so that e.g. `f(` in code ignores
+/// parentheses mentioned in doc comments. Without a tree the skip flags
+/// are ignored and the search is purely textual.
+pub fn find_char_smart(
+    text: RopeSlice,
+    syntax: Option<&Syntax>,
+    start: usize,
+    ch: char,
+    forward: bool,
+    skip_strings: bool,
+    skip_comments: bool,
+) -> Option<usize> {
+    let find = |pos| {
+        if forward {
+            search::find_nth_next(text, ch, pos, 1)
+        } else {
+            search::find_nth_prev(text, ch, pos, 1)
+        }
+    };
+
+    let syntax = match syntax {
+        Some(syntax) if skip_strings || skip_comments => syntax,
+        _ => return find(start),
+    };
+
+    // Node kinds are grammar specific, but the built-in grammars
+    // consistently put "string" and "comment" in the kinds captured as
+    // @string/@comment, so substring matching covers them without running
+    // highlight queries per candidate (the same trade-off match_brackets
+    // makes).
+    let skipped = |pos: usize| {
+        let byte = text.char_to_byte(pos);
+        let mut node = match syntax.descendant_for_byte_range(byte, byte) {
+            Some(node) => node,
+            None => return false,
+        };
+        loop {
+            let kind = node.kind();
+            if (skip_strings && kind.contains("string"))
+                || (skip_comments && kind.contains("comment"))
+            {
+                return true;
+            }
+            node = match node.parent() {
+                Some(parent) => parent,
+                None => return false,
+            };
+        }
+    };
+
+    let mut pos = start;
+    loop {
+        let found = find(pos)?;
+        if !skipped(found) {
+            return Some(found);
+        }
+        // `find_nth_next` considers the character under `pos` while
+        // `find_nth_prev` already scans strictly before it.
+        pos = if forward { found + 1 } else { found };
+    }
+}
+
 /// Possible targets of a word motion
 #[derive(Copy, Clone, Debug)]
 pub enum WordMotionTarget {
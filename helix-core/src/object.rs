@@ -1,4 +1,7 @@
-use crate::{movement::Direction, syntax::TreeCursor, Range, RopeSlice, Selection, Syntax};
+use crate::{
+    movement::Direction, syntax::TreeCursor, Diagnostic, Range, RopeSlice, Selection, Syntax,
+};
+use tree_sitter::Node;
 
 pub fn expand_selection(syntax: &Syntax, text: RopeSlice, selection: Selection) -> Selection {
     let cursor = &mut syntax.walk();
@@ -7,10 +10,14 @@ pub fn expand_selection(syntax: &Syntax, text: RopeSlice, selection: Selection)
         let from = text.char_to_byte(range.from());
         let to = text.char_to_byte(range.to());
 
-        let byte_range = from..to;
         cursor.reset_to_byte_range(from, to);
 
-        while cursor.node().byte_range() == byte_range {
+        // Climb while the node does not strictly contain the selection:
+        // ancestors frequently share the exact same range (expression ->
+        // statement wrappers, single-child nodes), and stopping on one of
+        // those would make this expand a no-op. A single expand always
+        // yields a strictly larger range, or the root.
+        while cursor.node().start_byte() >= from && cursor.node().end_byte() <= to {
             if !cursor.goto_parent() {
                 break;
             }
@@ -24,27 +31,259 @@ pub fn expand_selection(syntax: &Syntax, text: RopeSlice, selection: Selection)
     })
 }
 
+/// Like [`expand_selection`], but stays within the current injection layer:
+/// ranges clamp at the layer's root instead of escaping into the host
+/// language.
+pub fn expand_selection_within_layer(
+    syntax: &Syntax,
+    text: RopeSlice,
+    selection: Selection,
+) -> Selection {
+    let cursor = &mut syntax.walk();
+
+    selection.transform(|range| {
+        let from = text.char_to_byte(range.from());
+        let to = text.char_to_byte(range.to());
+
+        cursor.reset_to_byte_range(from, to);
+
+        // As in `expand_selection`: climb past ancestors sharing the
+        // selection's range so the result is strictly larger or the layer
+        // root.
+        while cursor.node().start_byte() >= from && cursor.node().end_byte() <= to {
+            if !cursor.goto_parent_in_layer() {
+                break;
+            }
+        }
+
+        let node = cursor.node();
+        let from = text.byte_to_char(node.start_byte());
+        let to = text.byte_to_char(node.end_byte());
+
+        Range::new(to, from).with_direction(range.direction())
+    })
+}
+
+/// The inverse of [`expand_selection`]: descends one level towards the node
+/// under the caret instead of unconditionally taking the first child, so
+/// repeated calls progressively narrow the selection onto the cursor.
+pub fn narrow_selection(syntax: &Syntax, text: RopeSlice, selection: Selection) -> Selection {
+    let cursor = &mut syntax.walk();
+
+    selection.transform(|range| {
+        let from = text.char_to_byte(range.from());
+        let to = text.char_to_byte(range.to());
+        let caret = text.char_to_byte(range.cursor(text));
+
+        let byte_range = from..to;
+        cursor.reset_to_byte_range(from, to);
+
+        // Step towards the caret until the node is strictly smaller than the
+        // current selection, skipping over wrappers with the same range (e.g.
+        // an expression statement around an expression).
+        while cursor.node().byte_range() == byte_range {
+            if !cursor.goto_first_child_for_byte(caret) {
+                break;
+            }
+        }
+
+        let node = cursor.node();
+        let from = text.byte_to_char(node.start_byte());
+        let to = text.byte_to_char(node.end_byte());
+
+        Range::new(from, to).with_direction(range.direction())
+    })
+}
+
 pub fn shrink_selection(syntax: &Syntax, text: RopeSlice, selection: Selection) -> Selection {
+    let cursor = &mut syntax.walk();
+
+    selection.transform(|range| {
+        let from = text.char_to_byte(range.from());
+        let to = text.char_to_byte(range.to());
+
+        cursor.reset_to_byte_range(from, to);
+
+        // Land on the child under the selection rather than unconditionally
+        // on the first child, so shrinking from a parent is useful when the
+        // selection only covers one of the later children.
+        cursor.goto_child_containing_range(from, to);
+
+        let node = cursor.node();
+        let from = text.byte_to_char(node.start_byte());
+        let to = text.byte_to_char(node.end_byte());
+
+        Range::new(from, to).with_direction(range.direction())
+    })
+}
+
+/// Expands each range to the nearest enclosing node whose `kind()` is in
+/// `kinds` (e.g. `function_item`, `class_definition`). Ranges without such
+/// an ancestor are left unchanged; returns `None` if that holds for every
+/// range, so callers can report the failure.
+pub fn expand_to_kind(
+    syntax: &Syntax,
+    text: RopeSlice,
+    selection: Selection,
+    kinds: &[&str],
+) -> Option<Selection> {
+    let cursor = &mut syntax.walk();
+    let mut matched = false;
+
+    let selection = selection.transform(|range| {
+        cursor.reset_to_char_range(text, range.from(), range.to());
+
+        loop {
+            let node = cursor.node();
+            if kinds.contains(&node.kind()) {
+                matched = true;
+                let from = text.byte_to_char(node.start_byte());
+                let to = text.byte_to_char(node.end_byte());
+                return Range::new(from, to).with_direction(range.direction());
+            }
+            if !cursor.goto_parent() {
+                return range;
+            }
+        }
+    });
+
+    matched.then_some(selection)
+}
+
+/// Selects the smallest node enclosing each range, extended to whole lines:
+/// from the start of the node's first line to the end of its last line.
+/// Useful for moving or duplicating a block when the node itself starts
+/// mid-line.
+pub fn select_node_linewise(syntax: &Syntax, text: RopeSlice, selection: Selection) -> Selection {
+    let cursor = &mut syntax.walk();
+
+    selection.transform(|range| {
+        cursor.reset_to_char_range(text, range.from(), range.to());
+
+        let node = cursor.node();
+        let from = text.byte_to_char(node.start_byte());
+        let to = text.byte_to_char(node.end_byte());
+
+        // `line_range` keeps a node ending right after a line break from
+        // dragging the next line in.
+        let (start_line, end_line) = Range::new(from, to).line_range(text);
+        let start = text.line_to_char(start_line);
+        let end = text.line_to_char((end_line + 1).min(text.len_lines()));
+
+        Range::new(start, end).with_direction(range.direction())
+    })
+}
+
+pub fn select_next_sibling(syntax: &Syntax, text: RopeSlice, selection: Selection) -> Selection {
     select_node_impl(
         syntax,
         text,
         selection,
         |cursor| {
-            cursor.goto_first_child();
+            while !cursor.goto_next_named_sibling_any_layer() {
+                if !cursor.goto_parent() {
+                    break;
+                }
+            }
         },
-        None,
+        Some(Direction::Forward),
     )
 }
 
-pub fn select_next_sibling(syntax: &Syntax, text: RopeSlice, selection: Selection) -> Selection {
+/// Like [`select_next_sibling`] but hops over siblings that are comments
+/// (doc comments between functions, licence headers, ...), landing on the
+/// next non-comment named sibling. When only comments follow, the motion
+/// stops on the last of them.
+pub fn select_next_sibling_skip_comments(
+    syntax: &Syntax,
+    text: RopeSlice,
+    selection: Selection,
+) -> Selection {
     select_node_impl(
         syntax,
         text,
         selection,
-        |cursor| {
-            while !cursor.goto_next_sibling() {
+        |cursor| loop {
+            while !cursor.goto_next_named_sibling_any_layer() {
                 if !cursor.goto_parent() {
-                    break;
+                    return;
+                }
+            }
+            if !is_comment(&cursor.node()) {
+                return;
+            }
+        },
+        Some(Direction::Forward),
+    )
+}
+
+/// Like [`select_next_sibling`] but applies the motion `n` times per range,
+/// stopping early once the last sibling is reached.
+pub fn select_next_sibling_n(
+    syntax: &Syntax,
+    text: RopeSlice,
+    selection: Selection,
+    n: usize,
+) -> Selection {
+    select_node_impl(
+        syntax,
+        text,
+        selection,
+        |cursor| {
+            for _ in 0..n {
+                while !cursor.goto_next_named_sibling_any_layer() {
+                    if !cursor.goto_parent() {
+                        return;
+                    }
+                }
+            }
+        },
+        Some(Direction::Forward),
+    )
+}
+
+/// Like [`select_next_sibling_n`] but wraps around to the first sibling when
+/// the selection is already on the last one, instead of ascending to the
+/// parent. The forward counterpart of [`select_prev_sibling_wrap_n`].
+pub fn select_next_sibling_wrap_n(
+    syntax: &Syntax,
+    text: RopeSlice,
+    selection: Selection,
+    n: usize,
+) -> Selection {
+    select_node_impl(
+        syntax,
+        text,
+        selection,
+        |cursor| {
+            for _ in 0..n {
+                if !cursor.goto_next_named_sibling() {
+                    while cursor.goto_prev_named_sibling() {}
+                }
+            }
+        },
+        Some(Direction::Forward),
+    )
+}
+
+/// Like [`select_next_sibling_n`] but also lands on anonymous nodes such as
+/// punctuation, which named-sibling navigation skips.
+pub fn select_next_sibling_raw_n(
+    syntax: &Syntax,
+    text: RopeSlice,
+    selection: Selection,
+    n: usize,
+) -> Selection {
+    select_node_impl(
+        syntax,
+        text,
+        selection,
+        |cursor| {
+            for _ in 0..n {
+                while !cursor.goto_next_sibling() {
+                    if !cursor.goto_parent() {
+                        return;
+                    }
                 }
             }
         },
@@ -75,6 +314,35 @@ pub fn select_all_children(syntax: &Syntax, text: RopeSlice, selection: Selectio
     })
 }
 
+/// Like [`select_all_children`], but only selects the direct children whose
+/// `kind()` matches `kind` (e.g. all `parameter`s of a signature, all
+/// `field_declaration`s of a struct). Ranges without matching children are
+/// left untouched.
+pub fn select_children_of_kind(
+    syntax: &Syntax,
+    text: RopeSlice,
+    selection: Selection,
+    kind: &str,
+) -> Selection {
+    selection.transform_iter(|range| {
+        let mut cursor = syntax.walk();
+        let (from, to) = range.into_byte_range(text);
+        cursor.reset_to_byte_range(from, to);
+
+        let children = cursor
+            .named_children()
+            .filter(|child| child.kind() == kind)
+            .map(|child| Range::from_node(child, text, range.direction()))
+            .collect::<Vec<_>>();
+
+        if children.is_empty() {
+            vec![range].into_iter()
+        } else {
+            children.into_iter()
+        }
+    })
+}
+
 fn select_children<'n>(
     cursor: &'n mut TreeCursor<'n>,
     text: RopeSlice,
@@ -98,7 +366,7 @@ pub fn select_prev_sibling(syntax: &Syntax, text: RopeSlice, selection: Selectio
         text,
         selection,
         |cursor| {
-            while !cursor.goto_prev_sibling() {
+            while !cursor.goto_prev_named_sibling_any_layer() {
                 if !cursor.goto_parent() {
                     break;
                 }
@@ -108,6 +376,288 @@ pub fn select_prev_sibling(syntax: &Syntax, text: RopeSlice, selection: Selectio
     )
 }
 
+/// The backward counterpart of [`select_next_sibling_skip_comments`].
+pub fn select_prev_sibling_skip_comments(
+    syntax: &Syntax,
+    text: RopeSlice,
+    selection: Selection,
+) -> Selection {
+    select_node_impl(
+        syntax,
+        text,
+        selection,
+        |cursor| loop {
+            while !cursor.goto_prev_named_sibling_any_layer() {
+                if !cursor.goto_parent() {
+                    return;
+                }
+            }
+            if !is_comment(&cursor.node()) {
+                return;
+            }
+        },
+        Some(Direction::Backward),
+    )
+}
+
+/// Like [`select_prev_sibling`] but wraps around to the last sibling when the
+/// selection is already on the first one, instead of ascending to the parent.
+pub fn select_prev_sibling_wrap(
+    syntax: &Syntax,
+    text: RopeSlice,
+    selection: Selection,
+) -> Selection {
+    select_node_impl(
+        syntax,
+        text,
+        selection,
+        |cursor| {
+            if !cursor.goto_prev_named_sibling() {
+                while cursor.goto_next_named_sibling() {}
+            }
+        },
+        Some(Direction::Backward),
+    )
+}
+
+/// Like [`select_prev_sibling_wrap`] but applies the motion `n` times per
+/// range.
+pub fn select_prev_sibling_wrap_n(
+    syntax: &Syntax,
+    text: RopeSlice,
+    selection: Selection,
+    n: usize,
+) -> Selection {
+    select_node_impl(
+        syntax,
+        text,
+        selection,
+        |cursor| {
+            for _ in 0..n {
+                if !cursor.goto_prev_named_sibling() {
+                    while cursor.goto_next_named_sibling() {}
+                }
+            }
+        },
+        Some(Direction::Backward),
+    )
+}
+
+/// Returns the text of `range` (typically a selection snapped to a syntax node
+/// via [`expand_selection`]) with the common leading indentation of its lines
+/// stripped, so the fragment can be reinserted cleanly at a different indent
+/// level.
+///
+/// Tabs and spaces are measured visually using `tab_width`, so mixed
+/// indentation dedents consistently. The first line and whitespace-only lines
+/// do not contribute to the common indentation.
+pub fn node_text_dedented(text: RopeSlice, range: Range, tab_width: usize) -> String {
+    let fragment = text.slice(range.from()..range.to());
+
+    let line_indent = |line: RopeSlice| -> Option<usize> {
+        let mut width = 0;
+        for ch in line.chars() {
+            match ch {
+                '\t' => width += tab_width - (width % tab_width),
+                ' ' => width += 1,
+                _ => return Some(width),
+            }
+        }
+        // Whitespace-only lines don't count towards the common indentation.
+        None
+    };
+
+    let common_indent = fragment
+        .lines()
+        .skip(1)
+        .filter_map(line_indent)
+        .min()
+        .unwrap_or(0);
+
+    let mut dedented = String::with_capacity(fragment.len_bytes());
+    for (i, line) in fragment.lines().enumerate() {
+        if i == 0 || common_indent == 0 {
+            dedented.extend(line.chunks());
+            continue;
+        }
+
+        let mut width = 0;
+        let mut stripped = 0;
+        for ch in line.chars() {
+            if width >= common_indent {
+                break;
+            }
+            match ch {
+                '\t' => width += tab_width - (width % tab_width),
+                ' ' => width += 1,
+                _ => break,
+            }
+            stripped += 1;
+        }
+        // If a tab straddled the boundary, pad the overshoot with spaces.
+        dedented.extend(std::iter::repeat(' ').take(width.saturating_sub(common_indent)));
+        dedented.extend(line.slice(stripped..).chunks());
+    }
+
+    dedented
+}
+
+/// Like [`select_prev_sibling`] but applies the motion `n` times per range,
+/// stopping early once the first sibling is reached.
+pub fn select_prev_sibling_n(
+    syntax: &Syntax,
+    text: RopeSlice,
+    selection: Selection,
+    n: usize,
+) -> Selection {
+    select_node_impl(
+        syntax,
+        text,
+        selection,
+        |cursor| {
+            for _ in 0..n {
+                while !cursor.goto_prev_named_sibling_any_layer() {
+                    if !cursor.goto_parent() {
+                        return;
+                    }
+                }
+            }
+        },
+        Some(Direction::Backward),
+    )
+}
+
+/// Like [`select_prev_sibling_n`] but also lands on anonymous nodes such as
+/// punctuation, which named-sibling navigation skips.
+pub fn select_prev_sibling_raw_n(
+    syntax: &Syntax,
+    text: RopeSlice,
+    selection: Selection,
+    n: usize,
+) -> Selection {
+    select_node_impl(
+        syntax,
+        text,
+        selection,
+        |cursor| {
+            for _ in 0..n {
+                while !cursor.goto_prev_sibling() {
+                    if !cursor.goto_parent() {
+                        return;
+                    }
+                }
+            }
+        },
+        Some(Direction::Backward),
+    )
+}
+
+/// Selects the comment under (or next to) each cursor: a node the grammar
+/// marks as `extra` whose kind mentions "comment". Runs of comments
+/// separated by a single line break are merged, so a multi-line doc comment
+/// can be operated on as a whole. Ranges that aren't on a comment are left
+/// untouched. Works for both line and block comment grammars.
+pub fn select_comment(syntax: &Syntax, text: RopeSlice, selection: Selection) -> Selection {
+    let cursor = &mut syntax.walk();
+
+    selection.transform(|range| {
+        cursor.reset_to_char_range(text, range.from(), range.to());
+
+        let node = cursor.node();
+        let node = if is_comment(&node) {
+            node
+        } else if let Some(sibling) = node.next_sibling().filter(is_comment) {
+            sibling
+        } else if let Some(sibling) = node.prev_sibling().filter(is_comment) {
+            sibling
+        } else {
+            return range;
+        };
+
+        let mut first = node;
+        while let Some(prev) = first.prev_sibling().filter(is_comment) {
+            if !mergeable_gap(text, prev.end_byte(), first.start_byte()) {
+                break;
+            }
+            first = prev;
+        }
+        let mut last = node;
+        while let Some(next) = last.next_sibling().filter(is_comment) {
+            if !mergeable_gap(text, last.end_byte(), next.start_byte()) {
+                break;
+            }
+            last = next;
+        }
+
+        let from = text.byte_to_char(first.start_byte());
+        let to = text.byte_to_char(last.end_byte());
+        Range::new(from, to).with_direction(range.direction())
+    })
+}
+
+fn is_comment(node: &Node) -> bool {
+    node.is_extra() && node.kind().contains("comment")
+}
+
+/// Snaps each range to the syntax node enclosing the first diagnostic that
+/// starts after it, wrapping around to the first diagnostic at the end of
+/// the document. Selecting the enclosing node gives a more useful selection
+/// than the raw diagnostic span, which is often just a token. `diagnostics`
+/// must be sorted by start position, as `Document::diagnostics` guarantees.
+pub fn select_next_diagnostic_node(
+    syntax: &Syntax,
+    text: RopeSlice,
+    selection: Selection,
+    diagnostics: &[Diagnostic],
+) -> Selection {
+    if diagnostics.is_empty() {
+        return selection;
+    }
+    let cursor = &mut syntax.walk();
+
+    selection.transform(|range| {
+        let next = diagnostics
+            .iter()
+            .find(|diagnostic| diagnostic.range.start > range.to())
+            .unwrap_or(&diagnostics[0]);
+
+        cursor.reset_to_char_range(text, next.range.start, next.range.end);
+
+        let node = cursor.node();
+        let from = text.byte_to_char(node.start_byte());
+        let to = text.byte_to_char(node.end_byte());
+        Range::new(from, to).with_direction(range.direction())
+    })
+}
+
+/// Whether two comments separated by `from..to` belong to the same block:
+/// the gap must be all whitespace and cross at most one line break.
+fn mergeable_gap(text: RopeSlice, from: usize, to: usize) -> bool {
+    let gap = text.byte_slice(from..to);
+    gap.chars().all(char::is_whitespace) && gap.chars().filter(|&ch| ch == '\n').count() <= 1
+}
+
+/// The chain of named node kinds enclosing `range`, outermost first, e.g.
+/// `["source_file", "function_item", "block", "if_expression"]`. Handy for
+/// inspecting grammar structure when writing textobject queries.
+pub fn node_kind_breadcrumb(syntax: &Syntax, text: RopeSlice, range: Range) -> Vec<String> {
+    let cursor = &mut syntax.walk();
+    cursor.reset_to_char_range(text, range.from(), range.to());
+
+    let mut kinds = Vec::new();
+    loop {
+        let node = cursor.node();
+        if node.is_named() {
+            kinds.push(node.kind().to_string());
+        }
+        if !cursor.goto_parent() {
+            break;
+        }
+    }
+    kinds.reverse();
+    kinds
+}
+
 fn select_node_impl<F>(
     syntax: &Syntax,
     text: RopeSlice,
@@ -121,10 +671,7 @@ where
     let cursor = &mut syntax.walk();
 
     selection.transform(|range| {
-        let from = text.char_to_byte(range.from());
-        let to = text.char_to_byte(range.to());
-
-        cursor.reset_to_byte_range(from, to);
+        cursor.reset_to_char_range(text, range.from(), range.to());
 
         motion(cursor);
 
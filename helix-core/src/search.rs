@@ -1,5 +1,19 @@
+use std::ops::Range;
+
+use helix_stdx::rope::{self, RopeSliceExt};
+use unicode_normalization::UnicodeNormalization;
+
 use crate::RopeSlice;
 
+/// Normalizes `text` to Unicode NFC (canonical composition), so that e.g. a
+/// decomposed `e` + U+0301 pattern matches the precomposed `é` in a document.
+///
+/// Only search patterns are normalized — documents are never rewritten — so
+/// char/byte offsets into the text remain untouched.
+pub fn normalize_nfc(text: &str) -> String {
+    text.nfc().collect()
+}
+
 // TODO: switch to std::str::Pattern when it is stable.
 pub trait CharMatcher {
     fn char_match(&self, ch: char) -> bool;
@@ -65,3 +79,68 @@ pub fn find_nth_prev(text: RopeSlice, ch: char, mut pos: usize, n: usize) -> Opt
 
     Some(pos)
 }
+
+/// Finds the first match of `regex` at or after `start_char`, returned as a
+/// char range. The rope-aware regex engine matches the rope's chunks in
+/// place, so the document is never copied into an intermediate `String`.
+pub fn find_regex_forward(
+    text: RopeSlice,
+    regex: &rope::Regex,
+    start_char: usize,
+) -> Option<Range<usize>> {
+    regex
+        .find(text.regex_input_at(start_char..))
+        .map(|mat| text.byte_to_char(mat.start())..text.byte_to_char(mat.end()))
+}
+
+/// The backward counterpart of [`find_regex_forward`]: the last match that
+/// ends at or before `start_char`.
+pub fn find_regex_backward(
+    text: RopeSlice,
+    regex: &rope::Regex,
+    start_char: usize,
+) -> Option<Range<usize>> {
+    let end_byte = text.char_to_byte(start_char);
+    regex
+        .find_iter(text.regex_input())
+        .take_while(|mat| mat.end() <= end_byte)
+        .last()
+        .map(|mat| text.byte_to_char(mat.start())..text.byte_to_char(mat.end()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ropey::Rope;
+
+    #[test]
+    fn test_find_regex_forward_and_backward() {
+        let text = Rope::from("one two three two one");
+        let regex = rope::Regex::new(r"two").unwrap();
+
+        let first = text.to_string().find("two").unwrap();
+        let second = text.to_string().rfind("two").unwrap();
+
+        assert_eq!(
+            find_regex_forward(text.slice(..), &regex, 0),
+            Some(first..first + 3)
+        );
+        // Searching from past the first match lands on the second.
+        assert_eq!(
+            find_regex_forward(text.slice(..), &regex, first + 1),
+            Some(second..second + 3)
+        );
+        assert_eq!(find_regex_forward(text.slice(..), &regex, second + 1), None);
+
+        assert_eq!(
+            find_regex_backward(text.slice(..), &regex, text.len_chars()),
+            Some(second..second + 3)
+        );
+        // Only matches ending at or before the start position count.
+        assert_eq!(
+            find_regex_backward(text.slice(..), &regex, second),
+            Some(first..first + 3)
+        );
+        assert_eq!(find_regex_backward(text.slice(..), &regex, first), None);
+    }
+}
@@ -9,7 +9,7 @@ use crate::{
     },
     line_ending::get_line_ending,
     movement::Direction,
-    Assoc, ChangeSet, RopeGraphemes, RopeSlice,
+    Assoc, ChangeSet, RopeGraphemes, RopeSlice, Syntax,
 };
 use helix_stdx::range::is_subset;
 use helix_stdx::rope::{self, RopeSliceExt};
@@ -874,6 +874,138 @@ pub fn split_on_matches(text: RopeSlice, selection: &Selection, regex: &rope::Re
     Selection::new(result, 0)
 }
 
+/// Extends each range of `selection` to full lines: the start moves to the
+/// beginning of its line and the end past the line ending of its last line.
+/// Range directions are preserved.
+#[must_use]
+pub fn extend_to_line_bounds(text: RopeSlice, selection: Selection) -> Selection {
+    selection.transform(|range| {
+        let (start_line, end_line) = range.line_range(text);
+        let start = text.line_to_char(start_line);
+        let end = text.line_to_char((end_line + 1).min(text.len_lines()));
+
+        Range::new(start, end).with_direction(range.direction())
+    })
+}
+
+/// Finds the innermost `pair` surrounding `char_idx`, taking nesting into
+/// account. A delimiter directly preceded by an odd number of `escape`
+/// characters is ignored.
+///
+/// If the opening and closing delimiters are identical (e.g. quotes),
+/// nesting is ambiguous: the nearest unescaped occurrence on either side is
+/// used, and a cursor sitting on such a delimiter returns `None`.
+///
+/// Returns the positions of the opening and closing delimiters.
+pub fn get_surrounding_pair(
+    text: RopeSlice,
+    char_idx: usize,
+    pair: (char, char),
+    escape: Option<char>,
+) -> Option<(usize, usize)> {
+    let (open, close) = pair;
+    if char_idx >= text.len_chars() {
+        return None;
+    }
+
+    let is_escaped = |idx: usize| {
+        let Some(escape) = escape else { return false };
+        let mut count = 0;
+        let mut idx = idx;
+        while idx > 0 && text.char(idx - 1) == escape {
+            count += 1;
+            idx -= 1;
+        }
+        count % 2 == 1
+    };
+
+    if open == close {
+        if text.char(char_idx) == open && !is_escaped(char_idx) {
+            return None;
+        }
+        let opening = (0..char_idx)
+            .rev()
+            .find(|&idx| text.char(idx) == open && !is_escaped(idx))?;
+        let closing = (char_idx + 1..text.len_chars())
+            .find(|&idx| text.char(idx) == close && !is_escaped(idx))?;
+        return Some((opening, closing));
+    }
+
+    // A closing delimiter at `char_idx` still counts as surrounding the
+    // cursor, so it is skipped when scanning backward; likewise for an
+    // opening delimiter when scanning forward.
+    let mut depth = 0;
+    let opening = (0..=char_idx).rev().find(|&idx| {
+        let ch = text.char(idx);
+        if is_escaped(idx) {
+            false
+        } else if ch == close && idx != char_idx {
+            depth += 1;
+            false
+        } else if ch == open {
+            if depth == 0 {
+                true
+            } else {
+                depth -= 1;
+                false
+            }
+        } else {
+            false
+        }
+    })?;
+
+    let mut depth = 0;
+    let closing = (char_idx..text.len_chars()).find(|&idx| {
+        let ch = text.char(idx);
+        if is_escaped(idx) {
+            false
+        } else if ch == open && idx != char_idx {
+            depth += 1;
+            false
+        } else if ch == close {
+            if depth == 0 {
+                true
+            } else {
+                depth -= 1;
+                false
+            }
+        } else {
+            false
+        }
+    })?;
+
+    Some((opening, closing))
+}
+
+/// Like [`get_surrounding_pair`], but pairs delimiters via the syntax tree,
+/// which stays reliable when the same characters appear unbalanced inside
+/// strings, comments or regex literals.
+pub fn get_surrounding_pair_tree_sitter(
+    syntax: &Syntax,
+    text: RopeSlice,
+    char_idx: usize,
+    pair: (char, char),
+) -> Option<(usize, usize)> {
+    let (open, close) = pair;
+    let byte = text.char_to_byte(char_idx);
+    let root = syntax.tree_for_byte_range(byte, byte).root_node();
+    let mut node = root.descendant_for_byte_range(byte, byte)?;
+
+    loop {
+        let start = text.try_byte_to_char(node.start_byte()).ok()?;
+        let end = text.try_byte_to_char(node.end_byte()).ok()?;
+        if end > start + 1
+            && text.char(start) == open
+            && text.char(end - 1) == close
+            && start <= char_idx
+            && char_idx < end
+        {
+            return Some((start, end - 1));
+        }
+        node = node.parent()?;
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1388,4 +1520,71 @@ mod test {
             vec!((1, 2), (3, 4), (7, 9))
         ));
     }
+
+    #[test]
+    fn test_extend_to_line_bounds() {
+        let text = Rope::from("line one\nline two\n\nline four");
+        let slice = text.slice(..);
+
+        let extend = |ranges: Vec<(usize, usize)>| {
+            let selection =
+                Selection::new(ranges.iter().map(|r| Range::new(r.0, r.1)).collect(), 0);
+            extend_to_line_bounds(slice, selection)
+        };
+
+        // Single-char selection in the middle of a line
+        assert_eq!(extend(vec![(5, 6)]).ranges(), &[Range::new(0, 9)]);
+
+        // Point selection at the very start of the file
+        assert_eq!(extend(vec![(0, 0)]).ranges(), &[Range::new(0, 9)]);
+
+        // Selection ending on the last line, which has no line ending
+        assert_eq!(extend(vec![(20, 22)]).ranges(), &[Range::new(19, 28)]);
+        assert_eq!(extend(vec![(28, 28)]).ranges(), &[Range::new(19, 28)]);
+
+        // An empty line extends to just its newline
+        assert_eq!(extend(vec![(18, 18)]).ranges(), &[Range::new(18, 19)]);
+
+        // Spanning multiple lines, backward direction is kept
+        let backward = extend(vec![(15, 2)]);
+        assert_eq!(backward.ranges(), &[Range::new(18, 0)]);
+        assert_eq!(backward.primary().direction(), Direction::Backward);
+    }
+
+    #[test]
+    fn test_get_surrounding_pair() {
+        let text = Rope::from("(a (b) c)");
+        let slice = text.slice(..);
+
+        // Inside the inner pair
+        assert_eq!(get_surrounding_pair(slice, 4, ('(', ')'), None), Some((3, 5)));
+        // Between the pairs the outer one surrounds the cursor
+        assert_eq!(get_surrounding_pair(slice, 7, ('(', ')'), None), Some((0, 8)));
+        // On a delimiter the pair it belongs to is returned
+        assert_eq!(get_surrounding_pair(slice, 3, ('(', ')'), None), Some((3, 5)));
+        assert_eq!(get_surrounding_pair(slice, 5, ('(', ')'), None), Some((3, 5)));
+
+        // No surrounding pair
+        let text = Rope::from("a (b) c");
+        assert_eq!(get_surrounding_pair(text.slice(..), 6, ('(', ')'), None), None);
+    }
+
+    #[test]
+    fn test_get_surrounding_pair_escaped() {
+        let text = Rope::from(r#" "a \" b" "#);
+        let slice = text.slice(..);
+
+        // The escaped quote is skipped when scanning outward
+        assert_eq!(
+            get_surrounding_pair(slice, 3, ('"', '"'), Some('\\')),
+            Some((1, 8))
+        );
+        // Without escape handling it terminates the pair early
+        assert_eq!(
+            get_surrounding_pair(slice, 3, ('"', '"'), None),
+            Some((1, 5))
+        );
+        // A cursor on an identical-delimiter pair is ambiguous
+        assert_eq!(get_surrounding_pair(slice, 1, ('"', '"'), Some('\\')), None);
+    }
 }
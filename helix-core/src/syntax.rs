@@ -34,7 +34,7 @@ use serde::{ser::SerializeSeq, Deserialize, Serialize};
 
 use helix_loader::grammar::{get_language, load_runtime_file};
 
-pub use tree_cursor::TreeCursor;
+pub use tree_cursor::{TreeCursor, TreeCursorCheckpoint};
 
 fn deserialize_regex<'de, D>(deserializer: D) -> Result<Option<Regex>, D::Error>
 where
@@ -122,6 +122,11 @@ pub struct LanguageConfiguration {
     #[serde(default)]
     pub auto_format: bool,
 
+    /// When auto-formatting on save, only apply the formatting changes that
+    /// overlap the current selection instead of the whole document.
+    #[serde(default)]
+    pub format_selection_on_save: bool,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub formatter: Option<FormatterConfiguration>,
 
@@ -170,6 +175,11 @@ pub struct LanguageConfiguration {
     /// Hardcoded LSP root directories relative to the workspace root, like `examples` or `tools/fuzz`.
     /// Falling back to the current working directory if none are configured.
     pub workspace_lsp_roots: Option<Vec<PathBuf>>,
+    /// Name template for the explorer's "create sibling file" prompt, with
+    /// `{stem}` and `{ext}` expanded from the file under the cursor.
+    /// Defaults to `{stem}.test.{ext}`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sibling_file_template: Option<String>,
     #[serde(default)]
     pub persistent_diagnostic_sources: Vec<String>,
 }
@@ -1039,6 +1049,19 @@ impl Loader {
         self.language_configs.iter()
     }
 
+    /// The `language-id` of the language whose (initialized) highlight
+    /// configuration is `config`. Lets UI name the injection layer a syntax
+    /// tree node belongs to.
+    pub fn language_name_for_highlight_config(
+        &self,
+        config: &Arc<HighlightConfiguration>,
+    ) -> Option<&str> {
+        self.language_configs.iter().find_map(|language_config| {
+            let cached = language_config.highlight_config.get()?.as_ref()?;
+            Arc::ptr_eq(cached, config).then(|| language_config.language_id.as_str())
+        })
+    }
+
     pub fn language_server_configs(&self) -> &HashMap<String, LanguageServerConfiguration> {
         &self.language_server_configs
     }
@@ -1,9 +1,13 @@
 use std::{cmp::Reverse, ops::Range};
 
-use super::{LanguageLayer, LayerId};
+use std::sync::Arc;
+
+use super::{HighlightConfiguration, LanguageLayer, LayerId};
+
+use crate::RopeSlice;
 
 use slotmap::HopSlotMap;
-use tree_sitter::Node;
+use tree_sitter::{Node, Point};
 
 /// The byte range of an injection layer.
 ///
@@ -18,6 +22,17 @@ struct InjectionRange {
     depth: u32,
 }
 
+/// A cursor position saved by [`TreeCursor::checkpoint`] and restored with
+/// [`TreeCursor::restore`], so a motion can try a path and backtrack.
+///
+/// Valid as long as the underlying trees haven't been swapped, i.e. until
+/// the next reparse.
+#[derive(Debug, Clone, Copy)]
+pub struct TreeCursorCheckpoint<'a> {
+    layer: LayerId,
+    node: Node<'a>,
+}
+
 pub struct TreeCursor<'a> {
     layers: &'a HopSlotMap<LayerId, LanguageLayer>,
     root: LayerId,
@@ -65,6 +80,38 @@ impl<'a> TreeCursor<'a> {
         self.cursor
     }
 
+    /// The start of the current node as a row/column [`Point`], for
+    /// breadcrumb and status displays that show "node at line X".
+    pub fn node_start_position(&self) -> Point {
+        self.cursor.start_position()
+    }
+
+    /// The row/column [`Point`] just past the end of the current node.
+    pub fn node_end_position(&self) -> Point {
+        self.cursor.end_position()
+    }
+
+    /// The highlight configuration of the layer the cursor is currently in.
+    /// Resolve it to a language name with
+    /// [`crate::syntax::Loader::language_name_for_highlight_config`].
+    pub fn layer_highlight_config(&self) -> &Arc<HighlightConfiguration> {
+        &self.layers[self.current].config
+    }
+
+    /// Saves the current layer and node, see [`TreeCursorCheckpoint`].
+    pub fn checkpoint(&self) -> TreeCursorCheckpoint<'a> {
+        TreeCursorCheckpoint {
+            layer: self.current,
+            node: self.cursor,
+        }
+    }
+
+    /// Moves the cursor back to a position saved with [`Self::checkpoint`].
+    pub fn restore(&mut self, checkpoint: TreeCursorCheckpoint<'a>) {
+        self.current = checkpoint.layer;
+        self.cursor = checkpoint.node;
+    }
+
     pub fn goto_parent(&mut self) -> bool {
         if let Some(parent) = self.node().parent() {
             self.cursor = parent;
@@ -90,6 +137,18 @@ impl<'a> TreeCursor<'a> {
         true
     }
 
+    /// Like [`Self::goto_parent`], but refuses to ascend past the current
+    /// injection layer's root: returns `false` at the layer boundary instead
+    /// of crossing into the parent layer.
+    pub fn goto_parent_in_layer(&mut self) -> bool {
+        if let Some(parent) = self.node().parent() {
+            self.cursor = parent;
+            return true;
+        }
+
+        false
+    }
+
     pub fn goto_parent_with<P>(&mut self, predicate: P) -> bool
     where
         P: Fn(&Node) -> bool,
@@ -103,6 +162,32 @@ impl<'a> TreeCursor<'a> {
         false
     }
 
+    /// Finds the first descendant of the current node whose kind matches
+    /// `kind`, using a pre-order DFS, so the match closest to the current
+    /// node wins. The cursor itself is not moved.
+    ///
+    /// Only named nodes are matched, and the search depth is capped to avoid
+    /// walking pathologically deep trees.
+    pub fn find_descendant_of_kind(&self, kind: &str) -> Option<Node<'a>> {
+        const MAX_DEPTH: usize = 100;
+
+        let mut stack: Vec<(Node<'a>, usize)> = Vec::new();
+        for i in (0..self.cursor.child_count()).rev() {
+            stack.push((self.cursor.child(i)?, 1));
+        }
+        while let Some((node, depth)) = stack.pop() {
+            if node.is_named() && node.kind() == kind {
+                return Some(node);
+            }
+            if depth < MAX_DEPTH {
+                for i in (0..node.child_count()).rev() {
+                    stack.push((node.child(i)?, depth + 1));
+                }
+            }
+        }
+        None
+    }
+
     /// Finds the injection layer that has exactly the same range as the given `range`.
     fn layer_id_of_byte_range(&self, search_range: Range<usize>) -> Option<LayerId> {
         let start_idx = self
@@ -115,16 +200,15 @@ impl<'a> TreeCursor<'a> {
             .find_map(|range| (range.start == search_range.start).then_some(range.layer_id))
     }
 
-    fn goto_first_child_impl(&mut self, named: bool) -> bool {
+    fn goto_first_child_impl(&mut self, named: bool, enter_injections: bool) -> bool {
         // Check if the current node's range is an exact injection layer range.
-        if let Some(layer_id) = self
-            .layer_id_of_byte_range(self.node().byte_range())
-            .filter(|&layer_id| layer_id != self.current)
-        {
-            // Switch to the child layer.
-            self.current = layer_id;
-            self.cursor = self.layers[self.current].tree().root_node();
-            return true;
+        if enter_injections {
+            if let Some((layer_id, root)) = self.injection_layer_root() {
+                // Switch to the child layer.
+                self.current = layer_id;
+                self.cursor = root;
+                return true;
+            }
         }
 
         let child = if named {
@@ -143,11 +227,116 @@ impl<'a> TreeCursor<'a> {
     }
 
     pub fn goto_first_child(&mut self) -> bool {
-        self.goto_first_child_impl(false)
+        self.goto_first_child_impl(false, true)
     }
 
     pub fn goto_first_named_child(&mut self) -> bool {
-        self.goto_first_child_impl(true)
+        self.goto_first_child_impl(true, true)
+    }
+
+    /// Like [`Self::goto_first_child`], but never substitutes an injection
+    /// layer's root for the current node: the cursor descends only within
+    /// the current tree, mirroring [`Self::goto_parent_in_layer`].
+    pub fn goto_first_child_in_layer(&mut self) -> bool {
+        self.goto_first_child_impl(false, false)
+    }
+
+    /// Descends to the first child extending beyond `byte`, entering an
+    /// injection layer first when the current node's range is exactly a layer
+    /// range. Returns `false` without moving when there is no such child.
+    pub fn goto_first_child_for_byte(&mut self, byte: usize) -> bool {
+        // Check if the current node's range is an exact injection layer range.
+        if let Some(layer_id) = self
+            .layer_id_of_byte_range(self.node().byte_range())
+            .filter(|&layer_id| layer_id != self.current)
+        {
+            // Switch to the child layer.
+            self.current = layer_id;
+            self.cursor = self.layers[self.current].tree().root_node();
+            return true;
+        }
+
+        if let Some(child) = self.first_child_for_byte(byte) {
+            self.cursor = child;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The first child of the current node extending beyond `byte`, found
+    /// with a `tree_sitter` cursor walk ([`Node`] itself has no byte-indexed
+    /// child lookup).
+    fn first_child_for_byte(&self, byte: usize) -> Option<Node<'a>> {
+        let mut walker = self.cursor.walk();
+        walker.goto_first_child_for_byte(byte)?;
+        Some(walker.node())
+    }
+
+    /// Descends to the first child overlapping `start..end`, entering an
+    /// injection layer first when the current node's range is exactly a
+    /// layer range. Returns `false` without moving when no child overlaps
+    /// the range.
+    pub fn goto_child_containing_range(&mut self, start: usize, end: usize) -> bool {
+        if let Some((layer_id, root)) = self.injection_layer_root() {
+            self.current = layer_id;
+            self.cursor = root;
+            return true;
+        }
+
+        // Treat a zero-width cursor as a one byte wide range.
+        let end = end.max(start + 1);
+        match self.first_child_for_byte(start) {
+            Some(child) if child.start_byte() < end => {
+                self.cursor = child;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The injected layer's root node, when the current node's range is an
+    /// exact injection layer range.
+    fn injection_layer_root(&self) -> Option<(LayerId, Node<'a>)> {
+        let layer_id = self
+            .layer_id_of_byte_range(self.node().byte_range())
+            .filter(|&layer_id| layer_id != self.current)?;
+        Some((layer_id, self.layers[layer_id].tree().root_node()))
+    }
+
+    /// Descends to the `n`th child, entering an injection layer first when
+    /// the current node's range is exactly a layer range (`n` then indexes
+    /// the children of the layer's root). Returns `false` without moving
+    /// when there is no such child.
+    pub fn goto_nth_child(&mut self, n: usize) -> bool {
+        let (layer_id, parent) = match self.injection_layer_root() {
+            Some((layer_id, root)) => (Some(layer_id), root),
+            None => (None, self.cursor),
+        };
+
+        match parent.child(n) {
+            Some(child) => {
+                if let Some(layer_id) = layer_id {
+                    self.current = layer_id;
+                }
+                self.cursor = child;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Like [`Self::goto_nth_child`], but descends to the last child.
+    pub fn goto_last_child(&mut self) -> bool {
+        let parent = match self.injection_layer_root() {
+            Some((_, root)) => root,
+            None => self.cursor,
+        };
+
+        match parent.child_count().checked_sub(1) {
+            Some(n) => self.goto_nth_child(n),
+            None => false,
+        }
     }
 
     fn goto_next_sibling_impl(&mut self, named: bool) -> bool {
@@ -173,6 +362,46 @@ impl<'a> TreeCursor<'a> {
         self.goto_next_sibling_impl(true)
     }
 
+    fn goto_next_sibling_any_layer_impl(&mut self, named: bool) -> bool {
+        if self.goto_next_sibling_impl(named) {
+            return true;
+        }
+        // A dead end only counts as a layer boundary on the layer root
+        // itself or one of its direct children.
+        if self.current == self.root
+            || self
+                .cursor
+                .parent()
+                .map_or(false, |parent| parent.parent().is_some())
+        {
+            return false;
+        }
+        let saved = self.checkpoint();
+        // Ascend to the host node of the injection and continue sibling
+        // navigation there; the recursion handles nested injections.
+        while let Some(parent) = self.cursor.parent() {
+            self.cursor = parent;
+        }
+        if self.goto_parent() && self.goto_next_sibling_any_layer_impl(named) {
+            return true;
+        }
+        self.restore(saved);
+        false
+    }
+
+    /// Like [`Self::goto_next_sibling`], but crosses injection layer
+    /// boundaries: at the injection root (or one of its direct children)
+    /// the navigation continues from the injection's host node in the
+    /// parent layer. Returns `false` without moving when there is no next
+    /// sibling in any layer.
+    pub fn goto_next_sibling_any_layer(&mut self) -> bool {
+        self.goto_next_sibling_any_layer_impl(false)
+    }
+
+    pub fn goto_next_named_sibling_any_layer(&mut self) -> bool {
+        self.goto_next_sibling_any_layer_impl(true)
+    }
+
     fn goto_prev_sibling_impl(&mut self, named: bool) -> bool {
         let sibling = if named {
             self.cursor.prev_named_sibling()
@@ -196,6 +425,38 @@ impl<'a> TreeCursor<'a> {
         self.goto_prev_sibling_impl(true)
     }
 
+    fn goto_prev_sibling_any_layer_impl(&mut self, named: bool) -> bool {
+        if self.goto_prev_sibling_impl(named) {
+            return true;
+        }
+        if self.current == self.root
+            || self
+                .cursor
+                .parent()
+                .map_or(false, |parent| parent.parent().is_some())
+        {
+            return false;
+        }
+        let saved = self.checkpoint();
+        while let Some(parent) = self.cursor.parent() {
+            self.cursor = parent;
+        }
+        if self.goto_parent() && self.goto_prev_sibling_any_layer_impl(named) {
+            return true;
+        }
+        self.restore(saved);
+        false
+    }
+
+    /// The backward counterpart of [`Self::goto_next_sibling_any_layer`].
+    pub fn goto_prev_sibling_any_layer(&mut self) -> bool {
+        self.goto_prev_sibling_any_layer_impl(false)
+    }
+
+    pub fn goto_prev_named_sibling_any_layer(&mut self) -> bool {
+        self.goto_prev_sibling_any_layer_impl(true)
+    }
+
     /// Finds the injection layer that contains the given start-end range.
     fn layer_id_containing_byte_range(&self, start: usize, end: usize) -> LayerId {
         let start_idx = self
@@ -215,6 +476,13 @@ impl<'a> TreeCursor<'a> {
         self.cursor = root.descendant_for_byte_range(start, end).unwrap_or(root);
     }
 
+    /// Like [`Self::reset_to_byte_range`], but takes char offsets into
+    /// `text`. Prefer this when starting from a [`crate::Range`] so the
+    /// conversion can't be forgotten.
+    pub fn reset_to_char_range(&mut self, text: RopeSlice, from: usize, to: usize) {
+        self.reset_to_byte_range(text.char_to_byte(from), text.char_to_byte(to));
+    }
+
     /// Returns an iterator over the children of the node the TreeCursor is on
     /// at the time this is called.
     pub fn children(&'a mut self) -> ChildIter<'a> {
@@ -227,6 +495,69 @@ impl<'a> TreeCursor<'a> {
         }
     }
 
+    /// Visits every node in the current node's subtree in pre-order,
+    /// including the current node itself. The cursor is not moved and the
+    /// walk stays within the current layer, never descending into injection
+    /// layers.
+    ///
+    /// The traversal is iterative so that pathologically deep trees cannot
+    /// overflow the call stack.
+    pub fn walk_subtree<F: FnMut(&Node<'a>)>(&self, mut f: F) {
+        let mut stack = vec![self.cursor];
+        while let Some(node) = stack.pop() {
+            f(&node);
+            for i in (0..node.child_count()).rev() {
+                if let Some(child) = node.child(i) {
+                    stack.push(child);
+                }
+            }
+        }
+    }
+
+    fn count_descendants_impl(&mut self, named: bool) -> usize {
+        let saved = self.checkpoint();
+        let root = self.cursor;
+
+        let mut count = 0;
+        if self.goto_first_child() {
+            'outer: loop {
+                if !named || self.node().is_named() {
+                    count += 1;
+                }
+                if self.goto_first_child() {
+                    continue;
+                }
+                loop {
+                    if self.node() == root {
+                        break 'outer;
+                    }
+                    if self.goto_next_sibling() {
+                        continue 'outer;
+                    }
+                    if !self.goto_parent() {
+                        break 'outer;
+                    }
+                }
+            }
+        }
+
+        self.restore(saved);
+        count
+    }
+
+    /// Counts every node (named and unnamed) below the current node,
+    /// descending into injection layers exactly where
+    /// [`Self::goto_first_child`] would, so injected content is counted once
+    /// rather than per layer. The cursor position is restored afterwards.
+    pub fn count_descendants(&mut self) -> usize {
+        self.count_descendants_impl(false)
+    }
+
+    /// Like [`Self::count_descendants`], but counts only named nodes.
+    pub fn count_named_descendants(&mut self) -> usize {
+        self.count_descendants_impl(true)
+    }
+
     /// Returns an iterator over the named children of the node the TreeCursor is on
     /// at the time this is called.
     pub fn named_children(&'a mut self) -> ChildIter<'a> {
@@ -238,6 +569,21 @@ impl<'a> TreeCursor<'a> {
             named: true,
         }
     }
+
+    /// Returns an iterator that yields the current node's subtree in
+    /// pre-order (document order), descending into injection layers exactly
+    /// where [`Self::goto_first_child`] would and resuming the host layer
+    /// afterwards. The cursor moves along with the iterator.
+    pub fn descendants(&'a mut self) -> DescendantIter<'a> {
+        let root = self.node();
+
+        DescendantIter {
+            cursor: self,
+            root,
+            first: true,
+            done: false,
+        }
+    }
 }
 
 pub struct ChildIter<'n> {
@@ -253,7 +599,7 @@ impl<'n> Iterator for ChildIter<'n> {
         // first iteration, just visit the first child
         if self.cursor.node() == self.parent {
             self.cursor
-                .goto_first_child_impl(self.named)
+                .goto_first_child_impl(self.named, true)
                 .then(|| self.cursor.node())
         } else {
             self.cursor
@@ -262,3 +608,46 @@ impl<'n> Iterator for ChildIter<'n> {
         }
     }
 }
+
+pub struct DescendantIter<'n> {
+    cursor: &'n mut TreeCursor<'n>,
+    root: Node<'n>,
+    first: bool,
+    done: bool,
+}
+
+impl<'n> Iterator for DescendantIter<'n> {
+    type Item = Node<'n>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        // First iteration: yield the subtree root itself.
+        if self.first {
+            self.first = false;
+            return Some(self.cursor.node());
+        }
+
+        if self.cursor.goto_first_child() {
+            return Some(self.cursor.node());
+        }
+
+        // No more children: climb back up until a sibling exists, stopping
+        // once the walk returns to the subtree root.
+        loop {
+            if self.cursor.node() == self.root {
+                self.done = true;
+                return None;
+            }
+            if self.cursor.goto_next_sibling() {
+                return Some(self.cursor.node());
+            }
+            if !self.cursor.goto_parent() {
+                self.done = true;
+                return None;
+            }
+        }
+    }
+}
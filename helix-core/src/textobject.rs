@@ -7,7 +7,7 @@ use crate::chars::{categorize_char, char_is_whitespace, CharCategory};
 use crate::graphemes::{next_grapheme_boundary, prev_grapheme_boundary};
 use crate::line_ending::rope_is_line_ending;
 use crate::movement::Direction;
-use crate::syntax::LanguageConfiguration;
+use crate::syntax::{CapturedNode, LanguageConfiguration};
 use crate::Range;
 use crate::{surround, Syntax};
 
@@ -198,6 +198,32 @@ pub fn textobject_paragraph(
     Range::new(anchor, head)
 }
 
+/// Returns the char range `(start, end)` of the paragraph around `line`:
+/// the contiguous run of non-blank lines containing it, bounded by blank
+/// lines or the start/end of the file. On a blank line the run of blank
+/// lines itself is returned. This is a line-based convenience around the
+/// same blank-line scan [`textobject_paragraph`] performs for the `ap`/`ip`
+/// text objects.
+pub fn find_paragraph_bounds(slice: RopeSlice, line: usize) -> (usize, usize) {
+    let on_blank = rope_is_line_ending(slice.line(line));
+
+    let mut start = line;
+    let mut lines = slice.lines_at(start);
+    lines.reverse();
+    let mut lines = lines.map(rope_is_line_ending).peekable();
+    while lines.next_if(|&blank| blank == on_blank).is_some() {
+        start -= 1;
+    }
+
+    let mut end = line;
+    let mut lines = slice.lines_at(end).map(rope_is_line_ending).peekable();
+    while lines.next_if(|&blank| blank == on_blank).is_some() {
+        end += 1;
+    }
+
+    (slice.line_to_char(start), slice.line_to_char(end))
+}
+
 pub fn textobject_pair_surround(
     syntax: Option<&Syntax>,
     slice: RopeSlice,
@@ -290,6 +316,79 @@ pub fn textobject_treesitter(
     get_range().unwrap_or(range)
 }
 
+/// The textobject captures that mark a node as a breadcrumb container.
+/// Only nodes captured by one of these show up in [`breadcrumbs`].
+const BREADCRUMB_CAPTURES: &[&str] = &["function.around", "class.around", "test.around"];
+
+/// Describe the syntactic context of the char position `pos` as a list of
+/// short labels for the enclosing container nodes (functions, types, tests),
+/// outermost first, e.g. `["impl Explorer", "fn handle_event"]`.
+///
+/// Containers are recognized through the language's textobject query, so
+/// languages without one yield an empty list rather than raw node kinds.
+pub fn breadcrumbs(
+    slice: RopeSlice,
+    slice_tree: Node,
+    lang_config: &LanguageConfiguration,
+    pos: usize,
+) -> Vec<String> {
+    let Some(textobject) = lang_config.textobject_query() else {
+        return Vec::new();
+    };
+    let byte_pos = slice.char_to_byte(pos);
+
+    let mut containers: Vec<(usize, String)> = Vec::new();
+    for capture_name in BREADCRUMB_CAPTURES {
+        let mut cursor = QueryCursor::new();
+        // Only nodes whose range intersects the cursor can enclose it, which
+        // keeps the query pass cheap even on large files.
+        cursor.set_byte_range(byte_pos..byte_pos.saturating_add(1));
+        let Some(nodes) = textobject.capture_nodes(capture_name, slice_tree, slice, &mut cursor)
+        else {
+            continue;
+        };
+        for node in nodes {
+            let CapturedNode::Single(node) = node else {
+                continue;
+            };
+            if !node.byte_range().contains(&byte_pos) {
+                continue;
+            }
+            containers.push((node.start_byte(), breadcrumb_label(node, slice)));
+        }
+    }
+
+    containers.sort_by_key(|(start_byte, _)| *start_byte);
+    containers.into_iter().map(|(_, label)| label).collect()
+}
+
+/// A short label for a container node: its leading keyword plus the text of
+/// the `name` field when the grammar provides one (e.g. `fn handle_event`),
+/// falling back to the node's first line cut off where its body starts.
+fn breadcrumb_label(node: Node, slice: RopeSlice) -> String {
+    let first_line: String = slice
+        .byte_slice(node.byte_range())
+        .chars()
+        .take_while(|&ch| ch != '\n')
+        .take(80)
+        .collect();
+
+    if let Some(name_node) = node.child_by_field_name("name") {
+        let name = slice.byte_slice(name_node.byte_range()).to_string();
+        let keyword = first_line.split_whitespace().next().unwrap_or_default();
+        if keyword.is_empty() || keyword == name {
+            return name;
+        }
+        return format!("{} {}", keyword, name);
+    }
+
+    let head = first_line
+        .split(['{', '(', ':'])
+        .next()
+        .unwrap_or(&first_line);
+    head.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
 #[cfg(test)]
 mod test {
     use super::TextObject::*;
@@ -498,6 +597,22 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_find_paragraph_bounds() {
+        let text = Rope::from("first\npara\n\n\nsecond\npara\n");
+        let slice = text.slice(..);
+
+        // Inside a paragraph: the run of non-blank lines around it.
+        assert_eq!(find_paragraph_bounds(slice, 0), (0, 11));
+        assert_eq!(find_paragraph_bounds(slice, 1), (0, 11));
+        // On a blank line: the run of blank lines itself.
+        assert_eq!(find_paragraph_bounds(slice, 2), (11, 13));
+        assert_eq!(find_paragraph_bounds(slice, 3), (11, 13));
+        // File boundaries clamp the scan.
+        assert_eq!(find_paragraph_bounds(slice, 4), (13, 25));
+        assert_eq!(find_paragraph_bounds(slice, 5), (13, 25));
+    }
+
     #[test]
     fn test_textobject_surround() {
         // (text, [(cursor position, textobject, final range, surround char, count), ...])
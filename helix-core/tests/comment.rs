@@ -0,0 +1,28 @@
+use helix_core::comment::{cursor_in_comment_or_string, cursor_in_string};
+use ropey::Rope;
+
+mod common;
+
+#[test]
+fn test_cursor_in_comment_or_string_rust() {
+    let source = "// note\nfn main() {\n    let s = \"text\";\n}\n";
+    let doc = Rope::from_str(source);
+    let syntax = common::build_syntax("source.rust", source);
+    let text = doc.slice(..);
+    let pos = |pat: &str, offset: usize| doc.to_string().find(pat).unwrap() + offset;
+
+    // inside the line comment
+    assert!(cursor_in_comment_or_string(&syntax, text, pos("note", 2)));
+    // right behind the comment, as when typing it out
+    assert!(cursor_in_comment_or_string(&syntax, text, pos("note", 4)));
+    // inside the string literal
+    assert!(cursor_in_comment_or_string(&syntax, text, pos("text", 2)));
+    // on plain code
+    assert!(!cursor_in_comment_or_string(&syntax, text, pos("main", 2)));
+    assert!(!cursor_in_comment_or_string(&syntax, text, pos("let s", 4)));
+
+    // the string-only check excludes comments
+    assert!(cursor_in_string(&syntax, text, pos("text", 2)));
+    assert!(!cursor_in_string(&syntax, text, pos("note", 2)));
+    assert!(!cursor_in_string(&syntax, text, pos("main", 2)));
+}
@@ -0,0 +1,83 @@
+//! Fixtures shared by the syntax-aware integration tests.
+
+use std::sync::{Arc, Once};
+
+use arc_swap::ArcSwap;
+use helix_core::{
+    syntax::{Configuration, Loader},
+    Syntax,
+};
+use ropey::Rope;
+
+/// The languages used across the integration tests in this directory.
+fn test_config() -> Configuration {
+    let config = r#"
+[[language]]
+name = "rust"
+scope = "source.rust"
+injection-regex = "rust"
+file-types = ["rs"]
+roots = []
+
+[[language]]
+name = "json"
+scope = "source.json"
+injection-regex = "json"
+file-types = ["json"]
+roots = []
+
+[[language]]
+name = "python"
+scope = "source.python"
+injection-regex = "python"
+file-types = ["py"]
+roots = []
+
+[[language]]
+name = "markdown"
+scope = "source.md"
+injection-regex = "md|markdown"
+file-types = ["md"]
+roots = []
+
+[[language]]
+name = "html"
+scope = "text.html.basic"
+injection-regex = "html"
+file-types = ["html"]
+roots = []
+
+[[language]]
+name = "javascript"
+scope = "source.js"
+injection-regex = "(js|javascript)"
+file-types = ["js"]
+roots = []
+"#;
+    toml::from_str(config).unwrap()
+}
+
+/// Parses `source` with the grammar registered for `lang_scope` (e.g.
+/// `"source.rust"`), resolving queries from the repository's `runtime`
+/// directory.
+pub fn build_syntax(lang_scope: &str, source: &str) -> Syntax {
+    // set runtime path so we can find the queries; only once, since writing
+    // an env var is not thread safe
+    static RUNTIME: Once = Once::new();
+    RUNTIME.call_once(|| {
+        let mut runtime = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        runtime.push("../runtime");
+        std::env::set_var("HELIX_RUNTIME", runtime.to_str().unwrap());
+    });
+
+    let loader = Loader::new(test_config()).unwrap();
+    let language_config = loader.language_config_for_scope(lang_scope).unwrap();
+    let highlight_config = language_config.highlight_config(&[]).unwrap();
+    let source = Rope::from(source);
+    Syntax::new(
+        source.slice(..),
+        highlight_config,
+        Arc::new(ArcSwap::from_pointee(loader)),
+    )
+    .unwrap()
+}
@@ -103,3 +103,20 @@ where
     );
     true
 }
+
+fn nested_closures() {
+    let result = items
+        .iter()
+        .map(|x| {
+            let incremented = x + 1;
+            move |offset| {
+                let shifted = incremented + offset;
+                shifted * 2
+            }
+        })
+        .filter(|make_shift| {
+            let shift = make_shift(1);
+            shift > 0
+        })
+        .collect::<Vec<_>>();
+}
@@ -0,0 +1,39 @@
+use helix_core::movement;
+use ropey::Rope;
+
+mod common;
+
+#[test]
+fn test_find_char_smart_skips_strings_and_comments() {
+    let source = "fn main() { let s = \"x\"; x(); } // x";
+    let doc = Rope::from(source);
+    let syntax = common::build_syntax("source.rust", source);
+    let text = doc.slice(..);
+
+    // Forward from the start: the occurrence inside the string literal is
+    // skipped in favour of the call.
+    let call = source.find("x()").unwrap();
+    assert_eq!(
+        movement::find_char_smart(text, Some(&syntax), 0, 'x', true, true, true),
+        Some(call)
+    );
+
+    // Backward from the end skips the occurrence in the trailing comment.
+    let end = source.chars().count();
+    assert_eq!(
+        movement::find_char_smart(text, Some(&syntax), end, 'x', false, true, true),
+        Some(call)
+    );
+
+    // With the skip flags cleared (or no tree at all) the search is purely
+    // textual and lands inside the string first.
+    let in_string = source.find("\"x\"").unwrap() + 1;
+    assert_eq!(
+        movement::find_char_smart(text, Some(&syntax), 0, 'x', true, false, false),
+        Some(in_string)
+    );
+    assert_eq!(
+        movement::find_char_smart(text, None, 0, 'x', true, true, true),
+        Some(in_string)
+    );
+}
@@ -0,0 +1,308 @@
+use helix_core::{object, Range, Selection, Syntax};
+use ropey::Rope;
+
+mod common;
+
+#[test]
+fn test_shrink_selection_lands_on_child_under_selection() {
+    let source = "fn main() { let x = (1, (2, 3)); }";
+    let doc = Rope::from(source);
+    let syntax = common::build_syntax("source.rust", source);
+
+    // Cover the inner tuple plus the outer closing parenthesis: the selection
+    // is not aligned to any node, so the enclosing node is the outer tuple
+    // `(1, (2, 3))`. Shrinking should land on the inner tuple under the
+    // selection, not on the outer tuple's first child `(`.
+    let inner = source.find("(2, 3)").unwrap();
+    let selection = Selection::single(inner, source.find(");").unwrap() + 1);
+    let shrunk = object::shrink_selection(&syntax, doc.slice(..), selection);
+    assert_eq!(shrunk.primary(), Range::new(inner, inner + "(2, 3)".len()));
+}
+
+#[test]
+fn test_select_sibling_skips_anonymous_tokens() {
+    let source = "fn main() { foo(alpha, beta, gamma); }";
+    let doc = Rope::from(source);
+    let syntax = common::build_syntax("source.rust", source);
+
+    let alpha = source.find("alpha").unwrap();
+    let beta = source.find("beta").unwrap();
+    let selection = Selection::single(alpha, alpha + "alpha".len());
+
+    // Named navigation hops argument to argument...
+    let next = object::select_next_sibling(&syntax, doc.slice(..), selection.clone());
+    assert_eq!(next.primary(), Range::new(beta, beta + "beta".len()));
+
+    // ...and back again, never landing on the separating comma.
+    let prev = object::select_prev_sibling(&syntax, doc.slice(..), next);
+    assert_eq!(prev.primary(), Range::new(alpha + "alpha".len(), alpha));
+
+    // The raw variant keeps the old behaviour and selects the comma.
+    let raw = object::select_next_sibling_raw_n(&syntax, doc.slice(..), selection, 1);
+    let comma = alpha + "alpha".len();
+    assert_eq!(raw.primary(), Range::new(comma, comma + 1));
+}
+
+#[test]
+fn test_select_next_sibling_wrap_cycles_within_parent() {
+    let source = "fn main() { foo(alpha, beta, gamma); }";
+    let doc = Rope::from(source);
+    let syntax = common::build_syntax("source.rust", source);
+
+    let alpha = source.find("alpha").unwrap();
+    let gamma = source.find("gamma").unwrap();
+    let first = Range::new(alpha, alpha + "alpha".len());
+    let selection = Selection::single(gamma, gamma + "gamma".len());
+
+    // The wrap variant cycles back to the first argument...
+    let wrapped = object::select_next_sibling_wrap_n(&syntax, doc.slice(..), selection.clone(), 1);
+    assert_eq!(wrapped.primary(), first);
+
+    // ...while the default escapes towards the parent's siblings.
+    let escaped = object::select_next_sibling_n(&syntax, doc.slice(..), selection, 1);
+    assert_ne!(escaped.primary(), first);
+
+    // Three wrapping hops from the first argument go full circle.
+    let full =
+        object::select_next_sibling_wrap_n(&syntax, doc.slice(..), Selection::from(first), 3);
+    assert_eq!(full.primary(), first);
+}
+
+#[test]
+fn test_select_sibling_skip_comments_hops_over_doc_comments() {
+    let source = "fn alpha() {}\n\n/// docs\n/// more docs\nfn beta() {}\n";
+    let doc = Rope::from(source);
+    let syntax = common::build_syntax("source.rust", source);
+
+    let alpha_end = "fn alpha() {}".len();
+    let beta = source.find("fn beta").unwrap();
+    let selection = Selection::single(0, alpha_end);
+
+    // The raw motion stops on each doc comment in between...
+    let next = object::select_next_sibling(&syntax, doc.slice(..), selection.clone());
+    let docs = source.find("/// docs").unwrap();
+    assert_eq!(next.primary(), Range::new(docs, docs + "/// docs".len()));
+
+    // ...while the skipping variant hops straight to the next function.
+    let next = object::select_next_sibling_skip_comments(&syntax, doc.slice(..), selection);
+    assert_eq!(
+        next.primary(),
+        Range::new(beta, beta + "fn beta() {}".len())
+    );
+
+    // And back again, past both comment lines.
+    let prev = object::select_prev_sibling_skip_comments(&syntax, doc.slice(..), next);
+    assert_eq!(prev.primary(), Range::new(alpha_end, 0));
+}
+
+#[test]
+fn test_shrink_selection_node_aligned_takes_first_child() {
+    let source = "fn main() { let x = (1, (2, 3)); }";
+    let doc = Rope::from(source);
+    let syntax = common::build_syntax("source.rust", source);
+
+    // A selection covering a whole node still shrinks to its first child.
+    let outer = source.find("(1").unwrap();
+    let selection = Selection::single(outer, outer + "(1, (2, 3))".len());
+    let shrunk = object::shrink_selection(&syntax, doc.slice(..), selection);
+    assert_eq!(shrunk.primary(), Range::new(outer, outer + 1));
+}
+
+#[test]
+fn test_select_all_siblings_selects_call_arguments() {
+    let source = "fn main() { foo(alpha, beta, gamma); }";
+    let doc = Rope::from(source);
+    let syntax = common::build_syntax("source.rust", source);
+
+    // A single cursor inside one argument turns into one range per argument.
+    let alpha = source.find("alpha").unwrap();
+    let selection = Selection::single(alpha, alpha + 1);
+    let selected = object::select_all_siblings(&syntax, doc.slice(..), selection);
+
+    let expected: Vec<Range> = ["alpha", "beta", "gamma"]
+        .iter()
+        .map(|arg| {
+            let start = source.find(arg).unwrap();
+            Range::new(start, start + arg.len())
+        })
+        .collect();
+    assert_eq!(selected.ranges(), expected.as_slice());
+}
+
+#[test]
+fn test_select_all_children_selects_struct_fields() {
+    let source = "struct Foo { alpha: u8, beta: u16 }";
+    let doc = Rope::from(source);
+    let syntax = common::build_syntax("source.rust", source);
+
+    // Selecting the whole field list and descending yields one range per
+    // field declaration, skipping the braces and commas.
+    let body = source.find('{').unwrap();
+    let selection = Selection::single(body, source.len());
+    let selected = object::select_all_children(&syntax, doc.slice(..), selection);
+
+    let expected: Vec<Range> = ["alpha: u8", "beta: u16"]
+        .iter()
+        .map(|field| {
+            let start = source.find(field).unwrap();
+            Range::new(start, start + field.len())
+        })
+        .collect();
+    assert_eq!(selected.ranges(), expected.as_slice());
+}
+
+#[test]
+fn test_expand_to_kind_selects_enclosing_function_rust() {
+    let source = "struct S;\n\nfn main() {\n    let x = 1;\n}\n";
+    let doc = Rope::from(source);
+    let syntax = common::build_syntax("source.rust", source);
+
+    let x = source.find('x').unwrap();
+    let selection = Selection::single(x, x + 1);
+    let selected = object::expand_to_kind(
+        &syntax,
+        doc.slice(..),
+        selection.clone(),
+        &["function_item"],
+    )
+    .unwrap();
+
+    let body = source.find("fn main").unwrap();
+    let end = source.rfind('}').unwrap() + 1;
+    assert_eq!(selected.primary(), Range::new(body, end));
+
+    // No enclosing node of the requested kind: the caller gets `None` and
+    // can report it without touching the selection.
+    assert!(object::expand_to_kind(&syntax, doc.slice(..), selection, &["impl_item"]).is_none());
+}
+
+#[test]
+fn test_expand_to_kind_selects_enclosing_function_and_class_python() {
+    let source = "class Foo:\n    def bar(self):\n        pass\n";
+    let doc = Rope::from(source);
+    let syntax = common::build_syntax("source.python", source);
+
+    let pass = source.find("pass").unwrap();
+    let selection = Selection::single(pass, pass + 1);
+
+    let function = object::expand_to_kind(
+        &syntax,
+        doc.slice(..),
+        selection.clone(),
+        &["function_definition"],
+    )
+    .unwrap();
+    let def = source.find("def bar").unwrap();
+    assert_eq!(function.primary(), Range::new(def, source.trim_end().len()));
+
+    let class =
+        object::expand_to_kind(&syntax, doc.slice(..), selection, &["class_definition"]).unwrap();
+    assert_eq!(class.primary(), Range::new(0, source.trim_end().len()));
+}
+
+/// Expands from `start` until the whole file is selected, asserting that
+/// every single press yields a strictly larger range even when ancestors
+/// share the exact same byte range (single-child wrappers).
+fn assert_expand_strictly_grows(syntax: &Syntax, source: &str, start: Range) {
+    let doc = Rope::from(source);
+    let mut selection = Selection::single(start.anchor, start.head);
+    let mut last = start;
+    let mut presses = 0;
+    while last.len() < source.len() {
+        selection = object::expand_selection(syntax, doc.slice(..), selection);
+        let expanded = selection.primary();
+        assert!(
+            expanded.from() <= last.from()
+                && last.to() <= expanded.to()
+                && expanded.len() > last.len(),
+            "expand stalled at {:?} after {:?}",
+            expanded,
+            last
+        );
+        last = expanded;
+        presses += 1;
+        assert!(presses <= source.len(), "expand did not reach the root");
+    }
+}
+
+#[test]
+fn test_expand_selection_strictly_grows_rust() {
+    // Every parenthesized layer and the expression statement share single
+    // children, producing long ancestor chains with identical ranges.
+    let source = "fn main() { ((((1)))); }";
+    let syntax = common::build_syntax("source.rust", source);
+
+    let one = source.find('1').unwrap();
+    assert_expand_strictly_grows(&syntax, source, Range::new(one, one + 1));
+}
+
+#[test]
+fn test_expand_selection_strictly_grows_json() {
+    let source = "[[[[1]]]]";
+    let syntax = common::build_syntax("source.json", source);
+
+    let one = source.find('1').unwrap();
+    assert_expand_strictly_grows(&syntax, source, Range::new(one, one + 1));
+}
+
+#[test]
+fn test_select_node_linewise_covers_whole_lines() {
+    let source = "fn main() {\n    let x = foo(\n        1,\n    );\n}\n";
+    let doc = Rope::from(source);
+    let syntax = common::build_syntax("source.rust", source);
+
+    // The enclosing call `foo(...)` starts mid-line on line 1 and ends on
+    // line 3; the result covers those lines fully.
+    let foo = source.find("foo").unwrap();
+    let close = source.find(')').unwrap();
+    let selection = Selection::single(foo, close + 1);
+    let linewise = object::select_node_linewise(&syntax, doc.slice(..), selection);
+    assert_eq!(
+        linewise.primary(),
+        Range::new(doc.line_to_char(1), doc.line_to_char(4))
+    );
+}
+
+#[test]
+fn test_tree_cursor_checkpoint_restores_position() {
+    let source = "fn main() { foo(1, 2); }";
+    let doc = Rope::from(source);
+    let syntax = common::build_syntax("source.rust", source);
+
+    let cursor = &mut syntax.walk();
+    let one = source.find('1').unwrap();
+    cursor.reset_to_char_range(doc.slice(..), one, one + 1);
+    let saved = cursor.checkpoint();
+    let node = cursor.node();
+
+    // Wander off: climb to the root and descend somewhere else.
+    while cursor.goto_parent() {}
+    cursor.goto_first_child();
+    assert_ne!(cursor.node(), node);
+
+    cursor.restore(saved);
+    assert_eq!(cursor.node(), node);
+}
+
+#[test]
+fn test_count_descendants_restores_cursor() {
+    let source = "fn main() { foo(1, 2); }";
+    let doc = Rope::from(source);
+    let syntax = common::build_syntax("source.rust", source);
+
+    let cursor = &mut syntax.walk();
+    let before = cursor.node();
+
+    let all = cursor.count_descendants();
+    let named = cursor.count_named_descendants();
+
+    // Anonymous tokens like `(` and `;` only show up in the full count.
+    assert!(named > 0);
+    assert!(all > named);
+    assert_eq!(cursor.node(), before);
+
+    // A leaf has no descendants.
+    let one = source.find('1').unwrap();
+    cursor.reset_to_char_range(doc.slice(..), one, one + 1);
+    assert_eq!(cursor.count_descendants(), 0);
+}
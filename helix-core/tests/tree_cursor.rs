@@ -0,0 +1,204 @@
+use std::ops::Range;
+
+use helix_core::{syntax::TreeCursor, Syntax};
+
+mod common;
+
+/// Walks down from the root towards `range.start` and stops on the first
+/// node whose byte range is exactly `range`.
+fn cursor_at(syntax: &Syntax, range: Range<usize>) -> TreeCursor<'_> {
+    let mut cursor = syntax.walk();
+    while cursor.node().byte_range() != range {
+        assert!(
+            cursor.goto_first_child_for_byte(range.start),
+            "no node spans exactly {:?}",
+            range
+        );
+    }
+    cursor
+}
+
+#[test]
+fn test_goto_last_child_plain() {
+    let syntax = common::build_syntax("source.rust", "fn a() {}\nfn b() {}\n");
+    let mut cursor = syntax.walk();
+
+    assert_eq!(cursor.node().kind(), "source_file");
+    assert!(cursor.goto_last_child());
+    assert_eq!(cursor.node().kind(), "function_item");
+    assert_eq!(cursor.node().start_byte(), 10);
+
+    // Leaves have no children; the cursor stays put.
+    assert!(cursor.goto_nth_child(0));
+    assert_eq!(cursor.node().kind(), "fn");
+    assert!(!cursor.goto_last_child());
+    assert_eq!(cursor.node().kind(), "fn");
+}
+
+#[test]
+fn test_goto_nth_child_plain() {
+    let syntax = common::build_syntax("source.rust", "fn a() {}\nfn b() {}\n");
+    let mut cursor = syntax.walk();
+
+    assert!(cursor.goto_nth_child(1));
+    assert_eq!(cursor.node().kind(), "function_item");
+    assert_eq!(cursor.node().start_byte(), 10);
+
+    // `fn b() {}`: "fn", name, parameters, body.
+    assert!(cursor.goto_nth_child(2));
+    assert_eq!(cursor.node().kind(), "parameters");
+
+    // Out of range: `false` without moving.
+    let parameters = cursor.node();
+    assert!(!cursor.goto_nth_child(parameters.child_count()));
+    assert_eq!(cursor.node(), parameters);
+}
+
+#[test]
+fn test_walk_subtree_visits_subtree_in_pre_order() {
+    let syntax = common::build_syntax("source.rust", "fn a() {}\nfn b() {}\n");
+    let mut cursor = syntax.walk();
+
+    // Walk the second function only: `fn b() {}`.
+    assert!(cursor.goto_last_child());
+    let mut kinds = Vec::new();
+    cursor.walk_subtree(|node| kinds.push(node.kind()));
+    assert_eq!(
+        kinds,
+        [
+            "function_item",
+            "fn",
+            "identifier",
+            "parameters",
+            "(",
+            ")",
+            "block",
+            "{",
+            "}",
+        ]
+    );
+
+    // The cursor did not move.
+    assert_eq!(cursor.node().kind(), "function_item");
+    assert_eq!(cursor.node().start_byte(), 10);
+}
+
+#[test]
+fn test_walk_subtree_stays_in_layer() {
+    let source = "# Title\n\n```rust\nfn main() {}\n```\n";
+    let syntax = common::build_syntax("source.md", source);
+
+    // Walking the whole markdown tree stops at the injection boundary: the
+    // injected rust layer's nodes are not visited.
+    let cursor = syntax.walk();
+    let mut crossed = false;
+    cursor.walk_subtree(|node| crossed |= node.kind() == "function_item");
+    assert!(!crossed);
+}
+
+#[test]
+fn test_sibling_navigation_crosses_layer_boundary() {
+    let source = "<html><body><script>let x = 1;</script><div></div></body></html>";
+    let syntax = common::build_syntax("text.html.basic", source);
+
+    // The statement is the injected javascript layer's only named node; its
+    // conceptual siblings are the script element's tags in the host layer.
+    let statement = source.find("let").unwrap();
+    let mut cursor = syntax.walk();
+    cursor.reset_to_byte_range(statement, statement + "let x = 1;".len());
+    assert_eq!(cursor.node().kind(), "lexical_declaration");
+
+    // Forward: across the boundary onto `</script>`.
+    assert!(cursor.goto_next_named_sibling_any_layer());
+    assert_eq!(cursor.node().kind(), "end_tag");
+    let end_tag = source.find("</script>").unwrap();
+    assert_eq!(cursor.node().start_byte(), end_tag);
+
+    // Backward: back into the script element and across onto `<script>`.
+    cursor.reset_to_byte_range(statement, statement + "let x = 1;".len());
+    assert!(cursor.goto_prev_named_sibling_any_layer());
+    assert_eq!(cursor.node().kind(), "start_tag");
+    assert_eq!(cursor.node().start_byte(), source.find("<script>").unwrap());
+
+    // The layer-local variant still dead-ends at the boundary.
+    cursor.reset_to_byte_range(statement, statement + "let x = 1;".len());
+    assert!(!cursor.goto_next_named_sibling());
+    assert_eq!(cursor.node().kind(), "lexical_declaration");
+}
+
+#[test]
+fn test_reset_to_byte_range_with_many_injection_layers() {
+    // Hundreds of injected layers: the sorted injection-range index built in
+    // `TreeCursor::new` has to resolve the deepest layer for each lookup.
+    let mut source = String::new();
+    for i in 0..200 {
+        source.push_str(&format!("```rust\nfn f{i}() {{}}\n```\n\n"));
+    }
+    let syntax = common::build_syntax("source.md", &source);
+    let mut cursor = syntax.walk();
+
+    for i in (0..200).step_by(7) {
+        let name = format!("f{i}");
+        let start = source.find(&format!("fn {name}")).unwrap() + "fn ".len();
+        cursor.reset_to_byte_range(start, start + name.len());
+        assert_eq!(cursor.node().kind(), "identifier");
+        assert_eq!(cursor.node().start_byte(), start);
+    }
+}
+
+#[test]
+fn test_descendants_crosses_injection_layers() {
+    let source = "# Title\n\n```rust\nfn main() {}\n```\n";
+    let syntax = common::build_syntax("source.md", source);
+
+    // Count the host layer's nodes...
+    let mut host = 0;
+    syntax.walk().walk_subtree(|_| host += 1);
+
+    // ...and the injected rust layer's nodes.
+    let content_start = source.find("fn main").unwrap();
+    let content_range = content_start..content_start + "fn main() {}\n".len();
+    let mut cursor = cursor_at(&syntax, content_range);
+    assert!(cursor.goto_first_child());
+    assert_eq!(cursor.node().kind(), "source_file");
+    let mut injected = 0;
+    cursor.walk_subtree(|_| injected += 1);
+
+    // A document-order traversal visits every node of both layers once.
+    let mut cursor = syntax.walk();
+    let kinds: Vec<_> = cursor.descendants().map(|node| node.kind()).collect();
+    assert_eq!(kinds.len(), host + injected);
+
+    // The injected function shows up between the fence content and the
+    // closing fence delimiter, i.e. in document order.
+    let content = kinds.iter().position(|&k| k == "code_fence_content");
+    let injected_fn = kinds.iter().position(|&k| k == "function_item");
+    assert!(content.is_some() && content < injected_fn);
+}
+
+#[test]
+fn test_goto_children_enters_injection_layer() {
+    let source = "# Title\n\n```rust\nfn main() {}\n```\n";
+    let syntax = common::build_syntax("source.md", source);
+
+    // The fenced code block's content is an injected rust layer.
+    let content_start = source.find("fn main").unwrap();
+    let content_range = content_start..content_start + "fn main() {}\n".len();
+
+    // Descending from the content node crosses into the injected tree: the
+    // children picked are those of the rust layer's root.
+    let mut cursor = cursor_at(&syntax, content_range.clone());
+    assert_eq!(cursor.node().kind(), "code_fence_content");
+    assert!(cursor.goto_last_child());
+    assert_eq!(cursor.node().kind(), "function_item");
+
+    let mut cursor = cursor_at(&syntax, content_range.clone());
+    assert!(cursor.goto_nth_child(0));
+    assert_eq!(cursor.node().kind(), "function_item");
+
+    // The injected source_file has a single child: out of range indices
+    // leave the cursor on the host language node.
+    let mut cursor = cursor_at(&syntax, content_range);
+    assert!(!cursor.goto_nth_child(1));
+    assert_eq!(cursor.node().kind(), "code_fence_content");
+}
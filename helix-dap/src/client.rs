@@ -462,6 +462,16 @@ impl Client {
         self.call::<requests::StepIn>(args)
     }
 
+    pub fn step_in_instruction(&self, thread_id: ThreadId) -> impl Future<Output = Result<Value>> {
+        let args = requests::StepInArguments {
+            thread_id,
+            target_id: None,
+            granularity: Some("instruction".to_owned()),
+        };
+
+        self.call::<requests::StepIn>(args)
+    }
+
     pub fn step_out(&self, thread_id: ThreadId) -> impl Future<Output = Result<Value>> {
         let args = requests::StepOutArguments {
             thread_id,
@@ -480,12 +490,43 @@ impl Client {
         self.call::<requests::Next>(args)
     }
 
+    pub fn next_instruction(&self, thread_id: ThreadId) -> impl Future<Output = Result<Value>> {
+        let args = requests::NextArguments {
+            thread_id,
+            granularity: Some("instruction".to_owned()),
+        };
+
+        self.call::<requests::Next>(args)
+    }
+
     pub fn pause(&self, thread_id: ThreadId) -> impl Future<Output = Result<Value>> {
         let args = requests::PauseArguments { thread_id };
 
         self.call::<requests::Pause>(args)
     }
 
+    pub fn restart_frame(&self, frame_id: usize) -> impl Future<Output = Result<Value>> {
+        let args = requests::RestartFrameArguments { frame_id };
+
+        self.call::<requests::RestartFrame>(args)
+    }
+
+    pub fn disassemble(
+        &self,
+        memory_reference: String,
+        instruction_count: usize,
+    ) -> impl Future<Output = Result<Value>> {
+        let args = requests::DisassembleArguments {
+            memory_reference,
+            offset: None,
+            instruction_offset: None,
+            instruction_count,
+            resolve_symbols: Some(true),
+        };
+
+        self.call::<requests::Disassemble>(args)
+    }
+
     pub async fn eval(
         &self,
         expression: String,
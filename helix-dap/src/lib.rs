@@ -1,9 +1,11 @@
 mod client;
+mod registry;
 mod transport;
 mod types;
 
 pub use client::{Client, ConnectionType};
 pub use events::Event;
+pub use registry::{DebugSession, Registry, SessionId};
 pub use transport::{Payload, Response, Transport};
 pub use types::*;
 
@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::Client;
+
+/// Identifier of a debug session, unique for the lifetime of the editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SessionId(usize);
+
+/// A debug adapter connection together with its user facing name.
+#[derive(Debug)]
+pub struct DebugSession {
+    pub name: String,
+    pub client: Client,
+    /// Maps local source paths to the paths the adapter knows them by, e.g.
+    /// the generated JavaScript for a TypeScript source. Learned from
+    /// set-breakpoints responses and settable with `:dap-add-path-mapping`.
+    pub source_map: HashMap<PathBuf, PathBuf>,
+}
+
+impl DebugSession {
+    /// Translates a local source path to the adapter-side path.
+    pub fn path_to_adapter(&self, path: &Path) -> PathBuf {
+        self.source_map
+            .get(path)
+            .cloned()
+            .unwrap_or_else(|| path.to_path_buf())
+    }
+
+    /// Translates an adapter-side path back to the local source.
+    pub fn path_from_adapter(&self, path: &Path) -> PathBuf {
+        self.source_map
+            .iter()
+            .find_map(|(local, adapter)| (adapter == path).then(|| local.clone()))
+            .unwrap_or_else(|| path.to_path_buf())
+    }
+}
+
+/// All running debug sessions.
+///
+/// At most one session is active at a time: debug commands and incoming
+/// adapter events are routed to it, while the remaining sessions keep
+/// running in the background. Per session state (threads, stack frames,
+/// the stopped thread) lives on each session's [`Client`].
+#[derive(Debug, Default)]
+pub struct Registry {
+    sessions: HashMap<SessionId, DebugSession>,
+    active: Option<SessionId>,
+    counter: usize,
+}
+
+impl Registry {
+    /// Registers a new session and makes it the active one.
+    pub fn insert(&mut self, name: String, client: Client) -> SessionId {
+        self.counter += 1;
+        let id = SessionId(self.counter);
+        let session = DebugSession {
+            name,
+            client,
+            source_map: HashMap::new(),
+        };
+        self.sessions.insert(id, session);
+        self.active = Some(id);
+        id
+    }
+
+    /// Id of the active session, if any.
+    pub fn active_id(&self) -> Option<SessionId> {
+        self.active
+    }
+
+    pub fn active_session(&self) -> Option<&DebugSession> {
+        self.sessions.get(&self.active?)
+    }
+
+    pub fn session_mut(&mut self, id: SessionId) -> Option<&mut DebugSession> {
+        self.sessions.get_mut(&id)
+    }
+
+    pub fn client_mut(&mut self, id: SessionId) -> Option<&mut Client> {
+        self.sessions
+            .get_mut(&id)
+            .map(|session| &mut session.client)
+    }
+
+    pub fn active_session_mut(&mut self) -> Option<&mut DebugSession> {
+        self.sessions.get_mut(&self.active?)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+
+    pub fn active_client(&self) -> Option<&Client> {
+        self.sessions
+            .get(&self.active?)
+            .map(|session| &session.client)
+    }
+
+    pub fn active_client_mut(&mut self) -> Option<&mut Client> {
+        self.sessions
+            .get_mut(&self.active?)
+            .map(|session| &mut session.client)
+    }
+
+    /// Name of the active session, if any.
+    pub fn active_name(&self) -> Option<&str> {
+        self.sessions
+            .get(&self.active?)
+            .map(|session| session.name.as_str())
+    }
+
+    /// Makes the session named `name` the active one. Returns `false` if no
+    /// session has that name.
+    pub fn switch(&mut self, name: &str) -> bool {
+        match self
+            .sessions
+            .iter()
+            .find(|(_, session)| session.name == name)
+        {
+            Some((&id, _)) => {
+                self.active = Some(id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Renames the active session. Returns `false` if there is none.
+    pub fn rename_active(&mut self, name: String) -> bool {
+        let Some(id) = self.active else { return false };
+        match self.sessions.get_mut(&id) {
+            Some(session) => {
+                session.name = name;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the session `id`. When it was the active one, an arbitrary
+    /// remaining session becomes active in its place.
+    pub fn remove(&mut self, id: SessionId) -> Option<DebugSession> {
+        let session = self.sessions.remove(&id)?;
+        if self.active == Some(id) {
+            self.active = self.sessions.keys().next().copied();
+        }
+        Some(session)
+    }
+
+    /// Removes the active session, making an arbitrary remaining session
+    /// active in its place.
+    pub fn remove_active(&mut self) -> Option<DebugSession> {
+        self.remove(self.active?)
+    }
+
+    /// Iterates over the names of all sessions in no particular order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.sessions.values().map(|session| session.name.as_str())
+    }
+}
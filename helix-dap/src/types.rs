@@ -239,6 +239,27 @@ pub struct StackFrame {
     pub presentation_hint: Option<String>,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisassembledInstruction {
+    pub address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instruction_bytes: Option<String>,
+    pub instruction: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<Source>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<usize>,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Thread {
@@ -658,6 +679,49 @@ pub mod requests {
         const COMMAND: &'static str = "pause";
     }
 
+    #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct RestartFrameArguments {
+        pub frame_id: usize,
+    }
+
+    #[derive(Debug)]
+    pub enum RestartFrame {}
+
+    impl Request for RestartFrame {
+        type Arguments = RestartFrameArguments;
+        type Result = ();
+        const COMMAND: &'static str = "restartFrame";
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct DisassembleArguments {
+        pub memory_reference: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub offset: Option<isize>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub instruction_offset: Option<isize>,
+        pub instruction_count: usize,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub resolve_symbols: Option<bool>,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct DisassembleResponse {
+        pub instructions: Vec<DisassembledInstruction>,
+    }
+
+    #[derive(Debug)]
+    pub enum Disassemble {}
+
+    impl Request for Disassemble {
+        type Arguments = DisassembleArguments;
+        type Result = DisassembleResponse;
+        const COMMAND: &'static str = "disassemble";
+    }
+
     #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
     #[serde(rename_all = "camelCase")]
     pub struct EvaluateArguments {
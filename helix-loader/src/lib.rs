@@ -132,6 +132,16 @@ pub fn cache_dir() -> PathBuf {
     path
 }
 
+pub fn state_dir() -> PathBuf {
+    // TODO: allow env var override
+    let strategy = choose_base_strategy().expect("Unable to find the state directory!");
+    // Not every platform has a state directory; fall back to the cache
+    // directory there.
+    let mut path = strategy.state_dir().unwrap_or_else(|| strategy.cache_dir());
+    path.push("helix");
+    path
+}
+
 pub fn config_file() -> PathBuf {
     CONFIG_FILE.get().map(|path| path.to_path_buf()).unwrap()
 }
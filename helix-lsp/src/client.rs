@@ -33,6 +33,16 @@ use tokio::{
     },
 };
 
+/// Goto-implementation under the newer `textDocument/implementors` method
+/// name; params and response are identical to the standard request.
+enum Implementors {}
+
+impl lsp::request::Request for Implementors {
+    type Params = lsp::GotoDefinitionParams;
+    type Result = Option<lsp::GotoDefinitionResponse>;
+    const METHOD: &'static str = "textDocument/implementors";
+}
+
 fn workspace_for_uri(uri: lsp::Url) -> WorkspaceFolder {
     lsp::WorkspaceFolder {
         name: uri
@@ -60,6 +70,33 @@ pub struct Client {
     initialize_notify: Arc<Notify>,
     /// workspace folders added while the server is still initializing
     req_timeout: u64,
+    workspace_diagnostics: Mutex<WorkspaceDiagnosticCache>,
+}
+
+/// Results of the last `workspace/diagnostic` pull (LSP 3.17), keyed by
+/// document URI. The workspace diagnostics picker merges these with the
+/// per-document push diagnostics.
+#[derive(Debug, Default)]
+pub struct WorkspaceDiagnosticCache {
+    entries: HashMap<lsp::Url, Vec<lsp::Diagnostic>>,
+}
+
+impl WorkspaceDiagnosticCache {
+    /// Folds `report` into the cache. Unchanged document reports mean the
+    /// previously cached diagnostics for that URI are still valid, so only
+    /// full reports overwrite entries.
+    fn update(&mut self, report: lsp::WorkspaceDiagnosticReport) {
+        for item in report.items {
+            if let lsp::WorkspaceDocumentDiagnosticReport::Full(full) = item {
+                self.entries
+                    .insert(full.uri, full.full_document_diagnostic_report.items);
+            }
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&lsp::Url, &Vec<lsp::Diagnostic>)> {
+        self.entries.iter()
+    }
 }
 
 impl Client {
@@ -232,6 +269,7 @@ impl Client {
             root_uri,
             workspace_folders: Mutex::new(workspace_folders),
             initialize_notify: initialize_notify.clone(),
+            workspace_diagnostics: Mutex::new(WorkspaceDiagnosticCache::default()),
         };
 
         Ok((client, server_rx, initialize_notify))
@@ -357,6 +395,20 @@ impl Client {
         }
     }
 
+    /// Whether the server advertises the non-standard
+    /// `textDocument/implementors` request under its experimental
+    /// capabilities.
+    ///
+    /// Client has to be initialized otherwise this function panics
+    pub fn supports_implementors(&self) -> bool {
+        self.capabilities()
+            .experimental
+            .as_ref()
+            .and_then(|experimental| experimental.get("implementors"))
+            .and_then(|implementors| implementors.as_bool())
+            .unwrap_or(false)
+    }
+
     pub fn offset_encoding(&self) -> OffsetEncoding {
         self.capabilities()
             .position_encoding
@@ -551,6 +603,9 @@ impl Client {
                     inlay_hint: Some(lsp::InlayHintWorkspaceClientCapabilities {
                         refresh_support: Some(false),
                     }),
+                    diagnostic: Some(lsp::DiagnosticWorkspaceClientCapabilities {
+                        refresh_support: Some(false),
+                    }),
                     workspace_edit: Some(lsp::WorkspaceEditClientCapabilities {
                         document_changes: Some(true),
                         resource_operations: Some(vec![
@@ -1371,6 +1426,21 @@ impl Client {
         ))
     }
 
+    /// Like [`Self::goto_implementation`], but using the newer
+    /// `textDocument/implementors` method name. Servers that adopted it
+    /// don't necessarily announce the implementation capability, so no
+    /// capability check is done here; callers should check
+    /// [`Self::supports_implementors`] when the standard request is
+    /// unavailable.
+    pub fn goto_implementors(
+        &self,
+        text_document: lsp::TextDocumentIdentifier,
+        position: lsp::Position,
+        work_done_token: Option<lsp::ProgressToken>,
+    ) -> impl Future<Output = Result<Value>> {
+        self.goto_request::<Implementors>(text_document, position, work_done_token)
+    }
+
     pub fn goto_reference(
         &self,
         text_document: lsp::TextDocumentIdentifier,
@@ -1466,6 +1536,59 @@ impl Client {
         Some(self.call::<lsp::request::WorkspaceSymbolRequest>(params))
     }
 
+    /// The cached results of the last `workspace/diagnostic` pull.
+    pub fn workspace_diagnostics(&self) -> parking_lot::MutexGuard<'_, WorkspaceDiagnosticCache> {
+        self.workspace_diagnostics.lock()
+    }
+
+    /// Pulls workspace-level diagnostics (LSP 3.17) and folds the report
+    /// into the cache. Returns early if the server does not advertise
+    /// workspace diagnostic support.
+    pub fn pull_workspace_diagnostics(
+        self: &Arc<Self>,
+    ) -> Option<impl Future<Output = Result<()>>> {
+        let capabilities = self.capabilities.get().unwrap();
+
+        let supported = match &capabilities.diagnostic_provider {
+            Some(lsp::DiagnosticServerCapabilities::Options(options)) => {
+                options.workspace_diagnostics
+            }
+            Some(lsp::DiagnosticServerCapabilities::RegistrationOptions(options)) => {
+                options.diagnostic_options.workspace_diagnostics
+            }
+            None => false,
+        };
+        if !supported {
+            return None;
+        }
+
+        let params = lsp::WorkspaceDiagnosticParams {
+            identifier: None,
+            // Without previous result ids every pull is a full one; servers
+            // answer those with full reports only, so the cache never goes
+            // stale on unchanged entries it doesn't have.
+            previous_result_ids: Vec::new(),
+            work_done_progress_params: lsp::WorkDoneProgressParams::default(),
+            partial_result_params: lsp::PartialResultParams::default(),
+        };
+        let request = self.call::<lsp::request::WorkspaceDiagnosticRequest>(params);
+        let client = Arc::clone(self);
+
+        Some(async move {
+            let json = request.await?;
+            let response: lsp::WorkspaceDiagnosticReportResult = serde_json::from_value(json)?;
+            match response {
+                lsp::WorkspaceDiagnosticReportResult::Report(report) => {
+                    client.workspace_diagnostics.lock().update(report);
+                }
+                // Partial results are only sent when the request carries a
+                // partial result token, which this one doesn't.
+                lsp::WorkspaceDiagnosticReportResult::Partial(_) => {}
+            }
+            Ok(())
+        })
+    }
+
     pub fn code_actions(
         &self,
         text_document: lsp::TextDocumentIdentifier,
@@ -1537,6 +1660,26 @@ impl Client {
             Ok(response)
         })
     }
+    pub fn copilot_check_file_status(
+        &self,
+        uri: String,
+    ) -> impl Future<Output = Result<copilot_types::FileStatusResponse>> {
+        let request = self
+            .call::<copilot_types::CheckFileStatus>(copilot_types::CheckFileStatusParams { uri });
+
+        async move {
+            let json = request.await?;
+            let response: copilot_types::FileStatusResponse = serde_json::from_value(json)?;
+            Ok(response)
+        }
+    }
+
+    pub fn copilot_did_focus(&self, uri: String) -> impl Future<Output = Result<()>> {
+        self.notify::<copilot_types::DidFocus>(copilot_types::DidFocusParams {
+            text_document: copilot_types::DidFocusTextDocument { uri },
+        })
+    }
+
     pub fn command(&self, command: lsp::Command) -> Option<impl Future<Output = Result<Value>>> {
         let capabilities = self.capabilities.get().unwrap();
 
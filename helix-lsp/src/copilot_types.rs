@@ -1,4 +1,4 @@
-use crate::lsp::{request::Request, Position, Range};
+use crate::lsp::{notification::Notification, request::Request, Position, Range};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug)]
@@ -30,6 +30,55 @@ impl Request for CompletionRequest {
     const METHOD: &'static str = "getCompletionsCycling";
 }
 
+/// Tells the agent which document is active so it can prioritize it for
+/// cross-file context.
+#[derive(Debug)]
+pub enum DidFocus {}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DidFocusParams {
+    pub text_document: DidFocusTextDocument,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DidFocusTextDocument {
+    pub uri: String,
+}
+
+impl Notification for DidFocus {
+    type Params = DidFocusParams;
+    const METHOD: &'static str = "textDocument/didFocus";
+}
+
+/// Content-exclusion check: asks the agent whether a document may be sent
+/// to the model at all. Agents without server-side exclusion policies
+/// answer every uri with `"included"`.
+#[derive(Debug)]
+pub enum CheckFileStatus {}
+
+#[derive(Serialize, Deserialize)]
+pub struct CheckFileStatusParams {
+    pub uri: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FileStatusResponse {
+    pub status: String,
+}
+
+impl FileStatusResponse {
+    pub fn blocked(&self) -> bool {
+        self.status.eq_ignore_ascii_case("blocked")
+    }
+}
+
+impl Request for CheckFileStatus {
+    type Params = CheckFileStatusParams;
+    type Result = FileStatusResponse;
+    const METHOD: &'static str = "checkFileStatus";
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CompletionResponse {
     pub completions: Vec<Completion>,
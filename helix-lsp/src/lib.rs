@@ -1070,4 +1070,51 @@ mod tests {
         let transaction = generate_transaction_from_edits(&source, edits, OffsetEncoding::Utf8);
         assert!(transaction.apply(&mut source));
     }
+
+    #[test]
+    fn copilot_transactions_replace_the_completion_range() {
+        use super::copilot_types;
+
+        // `Completion` keeps most fields private; build fixtures the way the
+        // agent delivers them.
+        fn completion(range: lsp::Range, text: &str) -> copilot_types::Completion {
+            serde_json::from_value(serde_json::json!({
+                "uuid": "fixture",
+                "range": range,
+                "displayText": text,
+                "position": range.start,
+                "docVersion": null,
+                "point": null,
+                "region": null,
+                "text": text,
+            }))
+            .unwrap()
+        }
+
+        fn apply(doc: &mut Rope, range: lsp::Range, text: &str) {
+            let response = copilot_types::CompletionResponse {
+                completions: vec![completion(range, text)],
+            };
+            let transactions = generate_transactions_from_copilot_response(
+                doc,
+                response,
+                OffsetEncoding::Utf8,
+            );
+            assert_eq!(transactions.len(), 1);
+            assert!(transactions[0].apply(doc));
+        }
+
+        // A range spanning from column 0 replaces the already-typed text
+        // instead of inserting a second copy of it.
+        let mut doc = Rope::from_str("let nu\n");
+        let range = lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(0, 6));
+        apply(&mut doc, range, "let number = 42;");
+        assert_eq!(doc, "let number = 42;\n");
+
+        // An empty range starting mid-line is a pure insertion at the cursor.
+        let mut doc = Rope::from_str("let nu\n");
+        let range = lsp::Range::new(lsp::Position::new(0, 6), lsp::Position::new(0, 6));
+        apply(&mut doc, range, "mber = 42;");
+        assert_eq!(doc, "let number = 42;\n");
+    }
 }
@@ -0,0 +1,119 @@
+//! A stub copilot agent for the integration tests.
+//!
+//! Speaks just enough of the stdio JSON-RPC protocol to stand in for the
+//! real agent: it answers `initialize`, the sign-in and status requests and
+//! `getCompletionsCycling` with canned fixtures. When `COPILOT_MOCK_LOG`
+//! points to a file, every received method is appended to it so tests can
+//! assert which requests and notifications the editor sent.
+
+use std::io::{BufRead, BufReader, Write};
+
+use serde_json::{json, Value};
+
+fn main() {
+    let stdin = std::io::stdin();
+    let mut reader = BufReader::new(stdin.lock());
+    let mut log = std::env::var_os("COPILOT_MOCK_LOG").map(|path| {
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .expect("COPILOT_MOCK_LOG is not writable")
+    });
+
+    while let Some(message) = read_message(&mut reader) {
+        let method = message.get("method").and_then(Value::as_str).unwrap_or("");
+        if let Some(log) = log.as_mut() {
+            writeln!(log, "{}", method).and_then(|_| log.flush()).ok();
+        }
+
+        let id = match message.get("id") {
+            Some(id) => id.clone(),
+            // Notifications need no reply.
+            None => {
+                if method == "exit" {
+                    return;
+                }
+                continue;
+            }
+        };
+
+        let result = match method {
+            "initialize" => json!({
+                "capabilities": {
+                    "textDocumentSync": { "openClose": true, "change": 1 },
+                },
+                "serverInfo": { "name": "copilot-mock-agent" },
+            }),
+            "getCompletionsCycling" => completions(&message),
+            "signInInitiate" | "signInConfirm" => {
+                json!({ "status": "AlreadySignedIn", "user": "mock-user" })
+            }
+            "checkStatus" => json!({ "status": "OK", "user": "mock-user" }),
+            // Content exclusion: blocks every document when the test asks
+            // for it via COPILOT_MOCK_FILE_STATUS=blocked.
+            "checkFileStatus" => json!({
+                "status": std::env::var("COPILOT_MOCK_FILE_STATUS")
+                    .unwrap_or_else(|_| "included".to_string()),
+            }),
+            // `shutdown` and anything unrecognized get an empty success
+            // reply so the client never hangs waiting on us.
+            _ => Value::Null,
+        };
+        write_message(&json!({ "jsonrpc": "2.0", "id": id, "result": result }));
+    }
+}
+
+/// Two insertions at the request's cursor position, so the fixtures stay
+/// valid wherever the test happens to place the cursor.
+fn completions(message: &Value) -> Value {
+    let position = message
+        .pointer("/params/doc/position")
+        .cloned()
+        .unwrap_or_else(|| json!({ "line": 0, "character": 0 }));
+    let completion = |index: usize, text: &str| {
+        json!({
+            "uuid": format!("mock-uuid-{}", index),
+            "range": { "start": position, "end": position },
+            "displayText": text,
+            "position": position,
+            "docVersion": null,
+            "point": null,
+            "region": null,
+            "text": text,
+        })
+    };
+    json!({
+        "completions": [
+            completion(1, " and the first mock suggestion"),
+            completion(2, " and the second mock suggestion"),
+        ]
+    })
+}
+
+fn read_message(reader: &mut impl BufRead) -> Option<Value> {
+    let mut content_length: Option<usize> = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).ok()? == 0 {
+            return None;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some(value) = line.strip_prefix("Content-Length: ") {
+            content_length = value.parse().ok();
+        }
+    }
+    let mut content = vec![0; content_length?];
+    reader.read_exact(&mut content).ok()?;
+    serde_json::from_slice(&content).ok()
+}
+
+fn write_message(message: &Value) {
+    let content = message.to_string();
+    let mut stdout = std::io::stdout().lock();
+    write!(stdout, "Content-Length: {}\r\n\r\n{}", content.len(), content).unwrap();
+    stdout.flush().unwrap();
+}
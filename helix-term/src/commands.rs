@@ -15,6 +15,7 @@ use tui::text::Span;
 pub use typed::*;
 
 use helix_core::{
+    case::{self, CaseMode},
     char_idx_at_visual_offset,
     chars::char_is_word,
     comment,
@@ -40,7 +41,7 @@ use helix_core::{
 };
 use helix_view::{
     document::{FormatterError, Mode, SCRATCH_BUFFER_NAME},
-    editor::Action,
+    editor::{Action, FindMode},
     info::Info,
     input::KeyEvent,
     keyboard::KeyCode,
@@ -373,6 +374,9 @@ impl MappableCommand {
         search_selection_detect_word_boundaries, "Use current selection as the search pattern, automatically wrapping with `\\b` on word boundaries",
         make_search_word_bounded, "Modify current search to make it word bounded",
         global_search, "Global search in workspace folder",
+        quickfix_list, "Open quickfix list picker",
+        goto_next_quickfix, "Goto next quickfix entry",
+        goto_prev_quickfix, "Goto previous quickfix entry",
         extend_line, "Select current line, if already selected, extend to another line based on the anchor",
         extend_line_below, "Select current line, if already selected, extend to next line",
         extend_line_above, "Select current line, if already selected, extend to previous line",
@@ -503,11 +507,22 @@ impl MappableCommand {
         rotate_selection_contents_backward, "Rotate selections contents backward",
         reverse_selection_contents, "Reverse selections contents",
         expand_selection, "Expand selection to parent syntax node",
+        expand_selection_within_layer, "Expand selection to parent syntax node without crossing injection layers",
         shrink_selection, "Shrink selection to previously expanded syntax node",
+        narrow_selection, "Narrow selection to the child syntax node under the cursor",
         select_next_sibling, "Select next sibling in the syntax tree",
         select_prev_sibling, "Select previous sibling the in syntax tree",
+        select_next_sibling_raw, "Select next sibling in the syntax tree, including anonymous nodes",
+        select_prev_sibling_raw, "Select previous sibling in the syntax tree, including anonymous nodes",
+        select_prev_sibling_wrap, "Select previous sibling in the syntax tree, wrapping to the last sibling at the first one",
         select_all_siblings, "Select all siblings of the current node",
         select_all_children, "Select all children of the current node",
+        select_comment, "Select the comment under the cursor, merging adjacent line comments",
+        select_next_diagnostic_node, "Select the syntax node enclosing the next diagnostic",
+        select_function, "Select the enclosing function",
+        select_class, "Select the enclosing class or type definition",
+        swap_node_next_sibling, "Swap the node under the primary selection with its next sibling",
+        swap_node_prev_sibling, "Swap the node under the primary selection with its previous sibling",
         jump_forward, "Jump forward on jumplist",
         jump_backward, "Jump backward on jumplist",
         save_selection, "Save current selection to jumplist",
@@ -530,6 +545,8 @@ impl MappableCommand {
         wonly, "Close windows except current",
         select_register, "Select register",
         insert_register, "Insert register",
+        insert_unicode, "Insert character by hex Unicode code point",
+        insert_digraph, "Insert character by two-character digraph code",
         align_view_middle, "Align view middle",
         align_view_top, "Align view top",
         align_view_center, "Align view center",
@@ -558,12 +575,16 @@ impl MappableCommand {
         goto_prev_paragraph, "Goto previous paragraph",
         dap_launch, "Launch debug target",
         dap_restart, "Restart debugging session",
+        dap_restart_frame, "Restart the current stack frame",
         dap_toggle_breakpoint, "Toggle breakpoint",
         dap_continue, "Continue program execution",
         dap_pause, "Pause program execution",
         dap_step_in, "Step in",
         dap_step_out, "Step out",
         dap_next, "Step to next",
+        dap_step_in_instruction, "Step in one machine instruction",
+        dap_next_instruction, "Step over one machine instruction",
+        dap_disassemble, "Open a disassembly of the current stack frame",
         dap_variables, "List variables",
         dap_terminate, "End debug session",
         dap_edit_condition, "Edit breakpoint condition on current line",
@@ -1530,6 +1551,10 @@ fn find_char(cx: &mut Context, direction: Direction, inclusive: bool, extend: bo
             _ => return,
         };
         let motion = move |editor: &mut Editor| {
+            if editor.find_mode == FindMode::Smart {
+                find_char_smart_impl(editor, direction, inclusive, extend, ch, count);
+                return;
+            }
             match direction {
                 Direction::Forward => {
                     find_char_impl(editor, &find_next_char_impl, inclusive, extend, ch, count)
@@ -1618,6 +1643,70 @@ fn find_prev_char_impl(
     }
 }
 
+/// The smart-mode counterpart of [`find_char_impl`]: same cursor and
+/// till-mode handling, but occurrences inside strings and comments are
+/// skipped via [`movement::find_char_smart`] when a syntax tree exists.
+fn find_char_smart_impl(
+    editor: &mut Editor,
+    direction: Direction,
+    inclusive: bool,
+    extend: bool,
+    ch: char,
+    count: usize,
+) {
+    let (view, doc) = current!(editor);
+    let text = doc.text().slice(..);
+    let syntax = doc.syntax();
+
+    let selection = doc.selection(view.id).clone().transform(|range| {
+        let search_start_pos = if range.anchor < range.head {
+            range.head - 1
+        } else {
+            range.head
+        };
+
+        let forward = direction == Direction::Forward;
+        let next = |pos| movement::find_char_smart(text, syntax, pos, ch, forward, true, true);
+        let next_after = |hit: usize| if forward { next(hit + 1) } else { next(hit) };
+
+        let mut found = if forward {
+            next((search_start_pos + 1).min(text.len_chars()))
+        } else {
+            next(search_start_pos)
+        };
+        if !inclusive {
+            // like the literal impls, `t`/`T` must not stall on a match
+            // directly next to the cursor
+            let adjacent = if forward {
+                Some(search_start_pos + 1)
+            } else {
+                search_start_pos.checked_sub(1)
+            };
+            if found.is_some() && found == adjacent {
+                found = found.and_then(next_after);
+            }
+        }
+        for _ in 1..count {
+            found = found.and_then(next_after);
+        }
+
+        let pos = found.map(|pos| match (inclusive, forward) {
+            (true, _) => pos,
+            (false, true) => pos - 1,
+            (false, false) => (pos + 1).min(text.len_chars()),
+        });
+
+        pos.map_or(range, |pos| {
+            if extend {
+                range.put_cursor(text, pos, true)
+            } else {
+                Range::point(range.cursor(text)).put_cursor(text, pos, true)
+            }
+        })
+    });
+    doc.set_selection(view.id, selection);
+}
+
 fn find_till_char(cx: &mut Context) {
     find_char(cx, Direction::Forward, false, false);
 }
@@ -1714,31 +1803,32 @@ where
 }
 
 fn switch_case(cx: &mut Context) {
-    switch_case_impl(cx, |string| {
+    let locale = cx.editor.config().locale;
+    switch_case_impl(cx, move |string| {
         string
-            .chars()
-            .flat_map(|ch| {
-                if ch.is_lowercase() {
-                    ch.to_uppercase().collect()
-                } else if ch.is_uppercase() {
-                    ch.to_lowercase().collect()
-                } else {
-                    vec![ch]
-                }
-            })
+            .chunks()
+            .map(|chunk| case::change_case(chunk, CaseMode::Switch, locale))
             .collect()
     });
 }
 
 fn switch_to_uppercase(cx: &mut Context) {
-    switch_case_impl(cx, |string| {
-        string.chunks().map(|chunk| chunk.to_uppercase()).collect()
+    let locale = cx.editor.config().locale;
+    switch_case_impl(cx, move |string| {
+        string
+            .chunks()
+            .map(|chunk| case::change_case(chunk, CaseMode::Upper, locale))
+            .collect()
     });
 }
 
 fn switch_to_lowercase(cx: &mut Context) {
-    switch_case_impl(cx, |string| {
-        string.chunks().map(|chunk| chunk.to_lowercase()).collect()
+    let locale = cx.editor.config().locale;
+    switch_case_impl(cx, move |string| {
+        string
+            .chunks()
+            .map(|chunk| case::change_case(chunk, CaseMode::Lower, locale))
+            .collect()
     });
 }
 
@@ -2223,13 +2313,18 @@ fn search_next_or_prev_impl(cx: &mut Context, movement: Movement, direction: Dir
             false
         };
         let wrap_around = search_config.wrap_around;
+        let pattern = if search_config.unicode_normalize {
+            Cow::Owned(search::normalize_nfc(&query))
+        } else {
+            Cow::Borrowed(query.as_ref())
+        };
         if let Ok(regex) = rope::RegexBuilder::new()
             .syntax(
                 rope::Config::new()
                     .case_insensitive(case_insensitive)
                     .multi_line(true),
             )
-            .build(&query)
+            .build(&pattern)
         {
             for _ in 0..count {
                 search_impl(
@@ -2422,6 +2517,11 @@ fn global_search(cx: &mut Context) {
             .map(|doc| (doc.path().cloned(), doc.text().to_owned()))
             .collect();
 
+        // Every query run replaces the persisted quickfix list, so the last
+        // search stays navigable after the picker is dismissed.
+        let quickfix = editor.quickfix.clone();
+        *quickfix.lock() = Some(helix_view::editor::QuickfixList::default());
+
         let matcher = match RegexMatcherBuilder::new()
             .case_smart(config.smart_case)
             .build(query)
@@ -2466,6 +2566,7 @@ fn global_search(cx: &mut Context) {
                     let mut searcher = searcher.clone();
                     let matcher = matcher.clone();
                     let injector = injector.clone();
+                    let quickfix = quickfix.clone();
                     let documents = &documents;
                     Box::new(move |entry: Result<DirEntry, ignore::Error>| -> WalkState {
                         let entry = match entry {
@@ -2480,11 +2581,21 @@ fn global_search(cx: &mut Context) {
                         };
 
                         let mut stop = false;
-                        let sink = sinks::UTF8(|line_num, _line_content| {
+                        let sink = sinks::UTF8(|line_num, line_content| {
                             stop = injector
                                 .push(FileResult::new(entry.path(), line_num as usize - 1))
                                 .is_err();
 
+                            if !stop {
+                                if let Some(list) = quickfix.lock().as_mut() {
+                                    list.entries.push(helix_view::editor::QuickfixEntry {
+                                        path: entry.path().to_path_buf(),
+                                        line: line_num as usize - 1,
+                                        text: line_content.trim_end().to_string(),
+                                    });
+                                }
+                            }
+
                             Ok(!stop)
                         });
                         let doc = documents.iter().find(|&(doc_path, _)| {
@@ -2573,6 +2684,132 @@ fn global_search(cx: &mut Context) {
     cx.push_layer(Box::new(overlaid(picker)));
 }
 
+/// Opens `entry`'s file and selects its line, mirroring the jump out of the
+/// global search picker. Returns `false` when the entry can't be reached
+/// anymore.
+fn jump_to_quickfix_entry(
+    editor: &mut Editor,
+    entry: &helix_view::editor::QuickfixEntry,
+    action: Action,
+) -> bool {
+    let doc = match editor.open(&entry.path, action) {
+        Ok(id) => doc_mut!(editor, &id),
+        Err(e) => {
+            editor.set_error(format!(
+                "Failed to open file '{}': {}",
+                entry.path.display(),
+                e
+            ));
+            return false;
+        }
+    };
+
+    let view = view_mut!(editor);
+    let text = doc.text();
+    if entry.line >= text.len_lines() {
+        editor.set_error(
+            "The line you jumped to does not exist anymore because the file has changed.",
+        );
+        return false;
+    }
+    let start = text.line_to_char(entry.line);
+    let end = text.line_to_char((entry.line + 1).min(text.len_lines()));
+
+    doc.set_selection(view.id, Selection::single(start, end));
+    if action.align_view(view, doc.id()) {
+        align_view(doc, view, Align::Center);
+    }
+    true
+}
+
+fn quickfix_list(cx: &mut Context) {
+    use helix_view::editor::QuickfixEntry;
+
+    let quickfix = cx.editor.quickfix.clone();
+    let entries = match quickfix.lock().as_ref() {
+        None => {
+            cx.editor
+                .set_error("No quickfix list, run a global search first");
+            return;
+        }
+        Some(list) => list.entries.clone(),
+    };
+    if entries.is_empty() {
+        cx.editor.set_error("Quickfix list is empty");
+        return;
+    }
+
+    let columns = [
+        PickerColumn::new("path", |item: &QuickfixEntry, _| {
+            let path = helix_stdx::path::get_relative_path(&item.path);
+            format!("{}:{}", path.to_string_lossy(), item.line + 1).into()
+        }),
+        PickerColumn::new("contents", |item: &QuickfixEntry, _| {
+            item.text.clone().into()
+        }),
+    ];
+
+    let picker = Picker::new(
+        columns,
+        1, // contents
+        entries,
+        (),
+        |cx, entry: &QuickfixEntry, action| {
+            // Continue cycling from the picked entry
+            if let Some(list) = cx.editor.quickfix.lock().as_mut() {
+                list.current = list
+                    .entries
+                    .iter()
+                    .position(|e| e.path == entry.path && e.line == entry.line);
+            }
+            jump_to_quickfix_entry(cx.editor, entry, action);
+        },
+    )
+    .with_preview(|_editor, entry| {
+        Some((entry.path.as_path().into(), Some((entry.line, entry.line))))
+    });
+
+    cx.push_layer(Box::new(overlaid(picker)));
+}
+
+fn goto_quickfix_impl(cx: &mut Context, direction: Direction) {
+    let quickfix = cx.editor.quickfix.clone();
+    let (index, len, entry) = {
+        let mut quickfix = quickfix.lock();
+        let Some(list) = quickfix.as_mut() else {
+            cx.editor
+                .set_error("No quickfix list, run a global search first");
+            return;
+        };
+        if list.entries.is_empty() {
+            cx.editor.set_error("Quickfix list is empty");
+            return;
+        }
+        let len = list.entries.len();
+        let index = match (list.current, direction) {
+            (None, Direction::Forward) => 0,
+            (None, Direction::Backward) => len - 1,
+            (Some(current), Direction::Forward) => (current + 1) % len,
+            (Some(current), Direction::Backward) => (current + len - 1) % len,
+        };
+        list.current = Some(index);
+        (index, len, list.entries[index].clone())
+    };
+
+    if jump_to_quickfix_entry(cx.editor, &entry, Action::Replace) {
+        cx.editor
+            .set_status(format!("quickfix {}/{}: {}", index + 1, len, entry.text));
+    }
+}
+
+fn goto_next_quickfix(cx: &mut Context) {
+    goto_quickfix_impl(cx, Direction::Forward);
+}
+
+fn goto_prev_quickfix(cx: &mut Context) {
+    goto_quickfix_impl(cx, Direction::Backward);
+}
+
 enum Extend {
     Above,
     Below,
@@ -2691,15 +2928,7 @@ fn extend_to_line_bounds(cx: &mut Context) {
 
     doc.set_selection(
         view.id,
-        doc.selection(view.id).clone().transform(|range| {
-            let text = doc.text();
-
-            let (start_line, end_line) = range.line_range(text.slice(..));
-            let start = text.line_to_char(start_line);
-            let end = text.line_to_char((end_line + 1).min(text.len_lines()));
-
-            Range::new(start, end).with_direction(range.direction())
-        }),
+        selection::extend_to_line_bounds(doc.text().slice(..), doc.selection(view.id).clone()),
     );
 }
 
@@ -2951,24 +3180,100 @@ fn append_mode(cx: &mut Context) {
     doc.set_selection(view.id, selection);
 }
 
-pub fn copilot_picker(cx: &mut Context) {
-    use helix_view::document::CopilotState;
-    use ui::copilot_picker::CopilotCompletionPicker;
+/// Asks for (or reports) per-workspace copilot consent when a manual
+/// request hits a workspace that isn't approved yet. A denied workspace
+/// stays denied until `:copilot-enable-workspace` is run.
+fn copilot_request_consent(cx: &mut Context) {
+    use helix_view::copilot_consent::{self, Consent};
 
-    let (view, doc) = current!(cx.editor);
+    let root = find_workspace().0;
+    match copilot_consent::consent(&root) {
+        Consent::Denied => cx.editor.set_error(
+            "Copilot is disabled for this workspace (run :copilot-enable-workspace to enable it)",
+        ),
+        Consent::Unasked => {
+            cx.editor
+                .set_status(format!("Enable Copilot for {}? (y/n)", root.display()));
+            cx.on_next_key(move |cx, event| match event.char() {
+                Some('y') => {
+                    copilot_consent::record(&root, true);
+                    doc!(cx.editor).request_copilot_completion();
+                    cx.editor.clear_status();
+                }
+                Some('n') => {
+                    copilot_consent::record(&root, false);
+                    cx.editor.set_status("Copilot disabled for this workspace");
+                }
+                _ => cx.editor.clear_status(),
+            });
+        }
+        // `copilot_workspace_allowed` covers the allowed case before we get
+        // here; nothing to do if a race still lands on it.
+        Consent::Allowed => {}
+    }
+}
+
+pub fn copilot_picker(cx: &mut Context) {
+    let doc = doc!(cx.editor);
 
     let state = doc.copilot_state.lock();
     let copilot_state = match state.as_ref() {
-        None => return,
+        None => {
+            // No response yet: request one regardless of the
+            // `copilot.only-in-comments` restriction, since the user asked
+            // explicitly. The next invocation picks up the response.
+            drop(state);
+            if doc.copilot_excluded() {
+                cx.editor.set_status("excluded by .copilotignore");
+            } else if doc.copilot_too_large() {
+                cx.editor.set_status("copilot disabled (large file)");
+            } else if doc!(cx.editor).copilot_workspace_allowed() {
+                doc!(cx.editor).request_copilot_completion();
+            } else {
+                copilot_request_consent(cx);
+            }
+            return;
+        }
         Some(copilot_state) => (*copilot_state).clone(),
     };
     drop(state);
+
+    // The rest has to run with compositor access: only one suggestion
+    // session may be active at a time, so a picker from a previous response
+    // is torn down (restoring the text it was previewing over) before the
+    // new session applies its first suggestion.
+    cx.callback
+        .push(Box::new(move |compositor: &mut Compositor, cx| {
+            open_copilot_session(compositor, cx.editor, copilot_state);
+        }));
+}
+
+/// Installs a picker session for `copilot_state`, tearing down any session
+/// from a previous response first (only one may be active at a time, and the
+/// old one has to restore the text it was previewing over). Shared by the
+/// manual trigger above and the picker's accept-and-next action, which
+/// reopens the session once the follow-up response arrives.
+pub(crate) fn open_copilot_session(
+    compositor: &mut Compositor,
+    editor: &mut Editor,
+    copilot_state: helix_view::document::CopilotState,
+) {
+    use helix_view::document::CopilotState;
+    use ui::copilot_picker::CopilotCompletionPicker;
+
     let CopilotState {
         response,
         doc_at_req,
         offset_encoding,
     } = copilot_state;
 
+    if let Some(old) = compositor.remove(CopilotCompletionPicker::ID) {
+        if let Some(picker) = old.as_any().downcast_ref::<CopilotCompletionPicker>() {
+            picker.abort(editor);
+        }
+    }
+
+    let (view, doc) = current!(editor);
     if doc.text() != &doc_at_req {
         return;
     }
@@ -2979,12 +3284,14 @@ pub fn copilot_picker(cx: &mut Context) {
         offset_encoding,
     );
 
-    match CopilotCompletionPicker::new(doc.text().clone(), transactions) {
-        None => return,
-        Some((picker, first_completion)) => {
-            doc.apply(&first_completion, view.id);
-            cx.push_layer(Box::new(picker));
-        }
+    if let Some((picker, first_completion)) =
+        CopilotCompletionPicker::new(doc.text().clone(), transactions)
+    {
+        let lines = ui::copilot_picker::suggestion_line_range(doc.text(), &first_completion);
+        doc.apply(&first_completion, view.id);
+        doc.copilot_preview_lines = lines;
+        compositor.push(Box::new(picker));
+        helix_view::copilot_stats::record_shown();
     }
 }
 
@@ -3031,7 +3338,7 @@ fn open_or_focus_explorer(cx: &mut Context) {
         |compositor: &mut Compositor, cx: &mut compositor::Context| {
             if let Some(editor) = compositor.find::<ui::EditorView>() {
                 match editor.explorer.as_mut() {
-                    Some(explore) => explore.focus(),
+                    Some(explore) => explore.focus(cx.editor),
                     None => match ui::Explorer::new(cx) {
                         Ok(explore) => editor.explorer = Some(explore),
                         Err(err) => cx.editor.set_error(format!("{}", err)),
@@ -3524,6 +3831,21 @@ async fn make_format_callback(
         match format {
             Ok(format) => {
                 if doc.version() == doc_version {
+                    // On save, `format-selection-on-save` narrows the
+                    // formatting changes down to the current selection.
+                    let format = if write.is_some()
+                        && doc
+                            .language_config()
+                            .is_some_and(|config| config.format_selection_on_save)
+                    {
+                        restrict_transaction_to_selection(
+                            doc.text(),
+                            &format,
+                            doc.selection(view.id),
+                        )
+                    } else {
+                        format
+                    };
                     doc.apply(&format, view.id);
                     doc.append_changes_to_history(view);
                     doc.detect_indent_and_line_ending();
@@ -4038,12 +4360,15 @@ pub mod insert {
         let (view, doc) = current_ref!(cx.editor);
         let view_id = view.id;
 
-        if matches!(
-            cx.editor.config().smart_tab,
-            Some(SmartTabConfig { enable: true, .. })
-        ) {
+        if let Some(SmartTabConfig {
+            enable: true,
+            complete,
+            ..
+        }) = cx.editor.config().smart_tab
+        {
+            let text = doc.text().slice(..);
             let cursors_after_whitespace = doc.selection(view_id).ranges().iter().all(|range| {
-                let cursor = range.cursor(doc.text().slice(..));
+                let cursor = range.cursor(text);
                 let current_line_num = doc.text().char_to_line(cursor);
                 let current_line_start = doc.text().line_to_char(current_line_num);
                 let left = doc.text().slice(current_line_start..cursor);
@@ -4054,7 +4379,16 @@ pub mod insert {
                 if doc.active_snippet.is_some() {
                     goto_next_tabstop(cx);
                 } else {
-                    move_parent_node_end(cx);
+                    let cursor = doc.selection(view_id).primary().cursor(text);
+                    let mid_word = cursor > 0
+                        && char_is_word(text.char(graphemes::prev_grapheme_boundary(
+                            text, cursor,
+                        )));
+                    if complete && mid_word {
+                        completion(cx);
+                    } else {
+                        move_parent_node_end(cx);
+                    }
                 }
                 return;
             }
@@ -4802,6 +5136,25 @@ fn unindent(cx: &mut Context) {
     exit_select_mode(cx);
 }
 
+/// Keeps only the changes of `transaction` that overlap `selection`, so a
+/// whole-document format can be applied to just the selected ranges.
+fn restrict_transaction_to_selection(
+    doc_text: &Rope,
+    transaction: &Transaction,
+    selection: &Selection,
+) -> Transaction {
+    let changes: Vec<_> = transaction
+        .changes_iter()
+        .filter(|(from, to, _)| {
+            selection
+                .ranges()
+                .iter()
+                .any(|range| range.from() <= *to && range.to() >= *from)
+        })
+        .collect();
+    Transaction::change(doc_text, changes.into_iter())
+}
+
 fn format_selections(cx: &mut Context) {
     use helix_lsp::{lsp, util::range_to_lsp_range};
 
@@ -4828,8 +5181,34 @@ fn format_selections(cx: &mut Context) {
             )
         })
     else {
-        cx.editor
-            .set_error("No configured language server supports range formatting");
+        // Fall back to whole-document formatting, keeping only the changes
+        // that overlap the selection.
+        let Some(language_server) = doc
+            .language_servers_with_feature(LanguageServerFeature::Format)
+            .next()
+        else {
+            cx.editor
+                .set_error("No configured language server supports range formatting");
+            return;
+        };
+        let offset_encoding = language_server.offset_encoding();
+        let future = language_server
+            .text_document_formatting(
+                doc.identifier(),
+                lsp::FormattingOptions {
+                    tab_size: doc.tab_width() as u32,
+                    insert_spaces: matches!(doc.indent_style, IndentStyle::Spaces(_)),
+                    ..Default::default()
+                },
+                None,
+            )
+            .unwrap();
+        let edits = tokio::task::block_in_place(|| helix_lsp::block_on(future)).unwrap_or_default();
+        let transaction =
+            helix_lsp::util::generate_transaction_from_edits(doc.text(), edits, offset_encoding);
+        let transaction =
+            restrict_transaction_to_selection(doc.text(), &transaction, doc.selection(view_id));
+        doc.apply(&transaction, view_id);
         return;
     };
 
@@ -5246,8 +5625,33 @@ fn expand_selection(cx: &mut Context) {
 
             // check if selection is different from the last one
             if *current_selection != selection {
-                // save current selection so it can be restored using shrink_selection
-                view.object_selections.push(current_selection.clone());
+                // save the step so it can be retraced using shrink_selection
+                view.object_selections
+                    .push((current_selection.clone(), selection.clone()));
+
+                doc.set_selection(view.id, selection);
+            }
+        }
+    };
+    cx.editor.apply_motion(motion);
+}
+
+fn expand_selection_within_layer(cx: &mut Context) {
+    let motion = |editor: &mut Editor| {
+        let (view, doc) = current!(editor);
+
+        if let Some(syntax) = doc.syntax() {
+            let text = doc.text().slice(..);
+
+            let current_selection = doc.selection(view.id);
+            let selection =
+                object::expand_selection_within_layer(syntax, text, current_selection.clone());
+
+            // check if selection is different from the last one
+            if *current_selection != selection {
+                // save the step so it can be retraced using shrink_selection
+                view.object_selections
+                    .push((current_selection.clone(), selection.clone()));
 
                 doc.set_selection(view.id, selection);
             }
@@ -5260,13 +5664,14 @@ fn shrink_selection(cx: &mut Context) {
     let motion = |editor: &mut Editor| {
         let (view, doc) = current!(editor);
         let current_selection = doc.selection(view.id);
-        // try to restore previous selection
-        if let Some(prev_selection) = view.object_selections.pop() {
-            if current_selection.contains(&prev_selection) {
+        // try to retrace the last expand/sibling step
+        if let Some((prev_selection, expanded)) = view.object_selections.pop() {
+            if expanded == *current_selection {
                 doc.set_selection(view.id, prev_selection);
                 return;
             } else {
-                // clear existing selection as they can't be shrunk to anyway
+                // the selection diverged from the history, so the remaining
+                // steps can't be retraced anymore
                 view.object_selections.clear();
             }
         }
@@ -5280,6 +5685,32 @@ fn shrink_selection(cx: &mut Context) {
     cx.editor.apply_motion(motion);
 }
 
+fn narrow_selection(cx: &mut Context) {
+    let motion = |editor: &mut Editor| {
+        let (view, doc) = current!(editor);
+
+        if let Some(syntax) = doc.syntax() {
+            let text = doc.text().slice(..);
+            let selection = object::narrow_selection(syntax, text, doc.selection(view.id).clone());
+            doc.set_selection(view.id, selection);
+        }
+    };
+    cx.editor.apply_motion(motion);
+}
+
+fn select_comment(cx: &mut Context) {
+    let motion = |editor: &mut Editor| {
+        let (view, doc) = current!(editor);
+
+        if let Some(syntax) = doc.syntax() {
+            let text = doc.text().slice(..);
+            let selection = object::select_comment(syntax, text, doc.selection(view.id).clone());
+            doc.set_selection(view.id, selection);
+        }
+    };
+    cx.editor.apply_motion(motion);
+}
+
 fn select_sibling_impl<F>(cx: &mut Context, sibling_fn: F)
 where
     F: Fn(&helix_core::Syntax, RopeSlice, Selection) -> Selection + 'static,
@@ -5291,6 +5722,12 @@ where
             let text = doc.text().slice(..);
             let current_selection = doc.selection(view.id);
             let selection = sibling_fn(syntax, text, current_selection.clone());
+            // sibling moves are part of the incremental-selection history
+            // too, so shrink_selection can retrace them
+            if *current_selection != selection {
+                view.object_selections
+                    .push((current_selection.clone(), selection.clone()));
+            }
             doc.set_selection(view.id, selection);
         }
     };
@@ -5298,11 +5735,48 @@ where
 }
 
 fn select_next_sibling(cx: &mut Context) {
-    select_sibling_impl(cx, object::select_next_sibling)
+    let count = cx.count();
+    let wrap = cx.editor.config().tree_sibling_wrap;
+    select_sibling_impl(cx, move |syntax, text, selection| {
+        if wrap {
+            object::select_next_sibling_wrap_n(syntax, text, selection, count)
+        } else {
+            object::select_next_sibling_n(syntax, text, selection, count)
+        }
+    })
 }
 
 fn select_prev_sibling(cx: &mut Context) {
-    select_sibling_impl(cx, object::select_prev_sibling)
+    let count = cx.count();
+    let wrap = cx.editor.config().tree_sibling_wrap;
+    select_sibling_impl(cx, move |syntax, text, selection| {
+        if wrap {
+            object::select_prev_sibling_wrap_n(syntax, text, selection, count)
+        } else {
+            object::select_prev_sibling_n(syntax, text, selection, count)
+        }
+    })
+}
+
+fn select_next_sibling_raw(cx: &mut Context) {
+    let count = cx.count();
+    select_sibling_impl(cx, move |syntax, text, selection| {
+        object::select_next_sibling_raw_n(syntax, text, selection, count)
+    })
+}
+
+fn select_prev_sibling_raw(cx: &mut Context) {
+    let count = cx.count();
+    select_sibling_impl(cx, move |syntax, text, selection| {
+        object::select_prev_sibling_raw_n(syntax, text, selection, count)
+    })
+}
+
+fn select_prev_sibling_wrap(cx: &mut Context) {
+    let count = cx.count();
+    select_sibling_impl(cx, move |syntax, text, selection| {
+        object::select_prev_sibling_wrap_n(syntax, text, selection, count)
+    })
 }
 
 fn move_node_bound_impl(cx: &mut Context, dir: Direction, movement: Movement) {
@@ -5374,6 +5848,178 @@ fn select_all_children(cx: &mut Context) {
     cx.editor.apply_motion(motion);
 }
 
+fn select_next_diagnostic_node(cx: &mut Context) {
+    let motion = |editor: &mut Editor| {
+        let (view, doc) = current!(editor);
+
+        if let Some(syntax) = doc.syntax() {
+            let text = doc.text().slice(..);
+            let selection = object::select_next_diagnostic_node(
+                syntax,
+                text,
+                doc.selection(view.id).clone(),
+                doc.diagnostics(),
+            );
+            doc.set_selection(view.id, selection);
+        }
+    };
+    cx.editor.apply_motion(motion);
+}
+
+fn select_function(cx: &mut Context) {
+    select_enclosing_impl(
+        cx,
+        "function",
+        &[
+            "function_item",
+            "function_definition",
+            "function_declaration",
+            "method_definition",
+            "method_declaration",
+            "arrow_function",
+        ],
+    )
+}
+
+fn select_class(cx: &mut Context) {
+    select_enclosing_impl(
+        cx,
+        "class",
+        &[
+            "class_definition",
+            "class_declaration",
+            "struct_item",
+            "enum_item",
+            "union_item",
+            "trait_item",
+            "impl_item",
+            "interface_declaration",
+        ],
+    )
+}
+
+/// Expands the selection to the enclosing `object_name` textobject where the
+/// language ships textobject queries, falling back to the raw node `kinds`
+/// for languages without them.
+fn select_enclosing_impl(
+    cx: &mut Context,
+    object_name: &'static str,
+    kinds: &'static [&'static str],
+) {
+    let count = cx.count();
+    let motion = move |editor: &mut Editor| {
+        let (view, doc) = current!(editor);
+        let text = doc.text().slice(..);
+
+        let Some(syntax) = doc.syntax() else {
+            editor.set_error("Syntax trees are not available in current buffer");
+            return;
+        };
+
+        if let Some(lang_config) = doc
+            .language_config()
+            .filter(|config| config.textobject_query().is_some())
+        {
+            let selection = doc.selection(view.id).clone().transform(|range| {
+                textobject::textobject_treesitter(
+                    text,
+                    range,
+                    textobject::TextObject::Around,
+                    object_name,
+                    syntax.tree().root_node(),
+                    lang_config,
+                    count,
+                )
+            });
+            doc.set_selection(view.id, selection);
+            return;
+        }
+
+        let selection = doc.selection(view.id).clone();
+        match object::expand_to_kind(syntax, text, selection, kinds) {
+            Some(selection) => doc.set_selection(view.id, selection),
+            None => editor.set_error(format!("No enclosing {object_name} found")),
+        }
+    };
+    cx.editor.apply_motion(motion);
+}
+
+fn swap_node_next_sibling(cx: &mut Context) {
+    swap_node_sibling_impl(cx, Direction::Forward);
+}
+
+fn swap_node_prev_sibling(cx: &mut Context) {
+    swap_node_sibling_impl(cx, Direction::Backward);
+}
+
+/// Swaps the text of the node under the primary selection with its next or
+/// previous named sibling in a single transaction, so separators between
+/// the two (commas, newlines) stay in place. The selection follows the
+/// moved node.
+fn swap_node_sibling_impl(cx: &mut Context, direction: Direction) {
+    let (view, doc) = current!(cx.editor);
+    let Some(syntax) = doc.syntax() else {
+        cx.editor
+            .set_error("Syntax trees are not available in current buffer");
+        return;
+    };
+    let text = doc.text().slice(..);
+    let selection = doc.selection(view.id).clone();
+    let primary_index = selection.primary_index();
+    let range = selection.primary();
+
+    let from = text.char_to_byte(range.from());
+    let to = text.char_to_byte(range.to());
+    let Some(node) = syntax.named_descendant_for_byte_range(from, to) else {
+        return;
+    };
+    let sibling = match direction {
+        Direction::Forward => node.next_named_sibling(),
+        Direction::Backward => node.prev_named_sibling(),
+    };
+    let Some(sibling) = sibling else {
+        cx.editor.set_error(match direction {
+            Direction::Forward => "No next sibling to swap with",
+            Direction::Backward => "No previous sibling to swap with",
+        });
+        return;
+    };
+
+    let (first, second) = if node.start_byte() < sibling.start_byte() {
+        (node, sibling)
+    } else {
+        (sibling, node)
+    };
+    let first_from = text.byte_to_char(first.start_byte());
+    let first_to = text.byte_to_char(first.end_byte());
+    let second_from = text.byte_to_char(second.start_byte());
+    let second_to = text.byte_to_char(second.end_byte());
+
+    let first_fragment: Tendril = text.slice(first_from..first_to).chunks().collect();
+    let second_fragment: Tendril = text.slice(second_from..second_to).chunks().collect();
+
+    // The moved node lands in the other slot; when moving forward that slot
+    // shifts by the length difference of the exchanged texts.
+    let first_len = first_to - first_from;
+    let second_len = second_to - second_from;
+    let moved = match direction {
+        Direction::Forward => Range::new(second_to - first_len, second_to),
+        Direction::Backward => Range::new(first_from, first_from + second_len),
+    }
+    .with_direction(range.direction());
+
+    let transaction = Transaction::change(
+        doc.text(),
+        [
+            (first_from, first_to, Some(second_fragment)),
+            (second_from, second_to, Some(first_fragment)),
+        ]
+        .into_iter(),
+    )
+    .with_selection(selection.replace(primary_index, moved));
+    doc.apply(&transaction, view.id);
+}
+
 fn match_brackets(cx: &mut Context) {
     let (view, doc) = current!(cx.editor);
     let is_select = cx.editor.mode == Mode::Select;
@@ -5579,6 +6225,75 @@ fn insert_register(cx: &mut Context) {
     })
 }
 
+fn insert_unicode(cx: &mut Context) {
+    unicode_input(cx, String::new());
+}
+
+/// Collects hex digits into `buffer`, previewing the code point in a status
+/// popup; enter inserts the character at every cursor, escape cancels. This
+/// is the GTK/GNOME `ctrl+shift+u` input method.
+fn unicode_input(cx: &mut Context, buffer: String) {
+    let preview = match char_for_hex(&buffer) {
+        Some(ch) => format!("'{}'", ch),
+        None if buffer.is_empty() => String::new(),
+        None => "invalid".to_string(),
+    };
+    cx.editor.autoinfo = Some(Info::new(
+        "insert-unicode",
+        &[(format!("U+{}", buffer), preview)],
+    ));
+
+    cx.on_next_key(move |cx, event| {
+        let mut buffer = buffer;
+        cx.editor.autoinfo = None;
+        match event.code {
+            KeyCode::Char(ch) if ch.is_ascii_hexdigit() => {
+                buffer.push(ch.to_ascii_uppercase());
+                unicode_input(cx, buffer);
+            }
+            KeyCode::Backspace => {
+                buffer.pop();
+                unicode_input(cx, buffer);
+            }
+            KeyCode::Enter => match char_for_hex(&buffer) {
+                Some(ch) => insert::insert_char(cx, ch),
+                None => cx
+                    .editor
+                    .set_error(format!("invalid Unicode code point: U+{}", buffer)),
+            },
+            _ => (),
+        }
+    });
+}
+
+fn char_for_hex(hex: &str) -> Option<char> {
+    u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+}
+
+/// Reads a two-character digraph code and inserts the character it names at
+/// every cursor, like Vim's insert-mode `CTRL-K`. User digraphs from
+/// `editor.digraphs` take precedence over the built-in table.
+fn insert_digraph(cx: &mut Context) {
+    cx.on_next_key(move |cx, event| {
+        let Some(first) = event.char() else { return };
+        cx.editor.set_status(format!("digraph: {}", first));
+        cx.on_next_key(move |cx, event| {
+            cx.editor.clear_status();
+            let Some(second) = event.char() else { return };
+            let code = format!("{}{}", first, second);
+            if let Some(custom) = cx.editor.config().digraphs.get(&code).cloned() {
+                for ch in custom.chars() {
+                    insert::insert_char(cx, ch);
+                }
+            } else if let Some(ch) = crate::digraphs::lookup(&code) {
+                insert::insert_char(cx, ch);
+            } else {
+                cx.editor.set_error(format!("unknown digraph: {}", code));
+            }
+        });
+    });
+}
+
 fn align_view_top(cx: &mut Context) {
     let (view, doc) = current!(cx.editor);
     align_view(doc, view, Align::Top);
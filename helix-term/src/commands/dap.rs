@@ -6,10 +6,12 @@ use crate::{
 };
 use dap::{StackFrame, Thread, ThreadStates};
 use helix_core::syntax::{DebugArgumentValue, DebugConfigCompletion, DebugTemplate};
+use helix_core::{Selection, Transaction};
 use helix_dap::{self as dap, Client};
 use helix_lsp::block_on;
-use helix_view::editor::Breakpoint;
+use helix_view::editor::{Action, Breakpoint};
 
+use futures_util::StreamExt;
 use serde_json::{to_value, Value};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tui::text::Spans;
@@ -59,7 +61,11 @@ fn thread_picker(
                 move |cx, thread, _action| callback_fn(cx.editor, thread),
             )
             .with_preview(move |editor, thread| {
-                let frames = editor.debugger.as_ref()?.stack_frames.get(&thread.id)?;
+                let frames = editor
+                    .debugger
+                    .active_client()?
+                    .stack_frames
+                    .get(&thread.id)?;
                 let frame = frames.first()?;
                 let path = frame.source.as_ref()?.path.as_ref()?.as_path();
                 let pos = Some((
@@ -152,6 +158,10 @@ pub fn dap_start_impl(
     }
     .ok_or_else(|| anyhow!("No debug config with given name"))?;
 
+    // Sessions are named after the launch config; :dap-rename-session can
+    // change the name later.
+    let session_name = template.name.clone();
+
     let mut args: HashMap<&str, Value> = HashMap::new();
 
     for (k, t) in &template.args {
@@ -222,18 +232,15 @@ pub fn dap_start_impl(
     };
 
     // TODO: either await "initialized" or buffer commands until event is received
-    cx.editor.debugger = Some(debugger);
-    let stream = UnboundedReceiverStream::new(events);
-    cx.editor.debugger_events.push(stream);
+    let id = cx.editor.debugger.insert(session_name, debugger);
+    // Tag the payloads with the session id so events from background
+    // sessions are routed to their own session, not the active one.
+    let stream = UnboundedReceiverStream::new(events).map(move |payload| (id, payload));
+    cx.editor.debugger_events.push(stream.boxed());
     Ok(())
 }
 
 pub fn dap_launch(cx: &mut Context) {
-    if cx.editor.debugger.is_some() {
-        cx.editor.set_error("Debugger is already running");
-        return;
-    }
-
     let doc = doc!(cx.editor);
 
     let config = match doc
@@ -283,7 +290,7 @@ pub fn dap_launch(cx: &mut Context) {
 }
 
 pub fn dap_restart(cx: &mut Context) {
-    let debugger = match &cx.editor.debugger {
+    let debugger = match cx.editor.debugger.active_client() {
         Some(debugger) => debugger,
         None => {
             cx.editor.set_error("Debugger is not running");
@@ -312,6 +319,113 @@ pub fn dap_restart(cx: &mut Context) {
     );
 }
 
+pub fn dap_restart_frame(cx: &mut Context) {
+    let debugger = debugger!(cx.editor);
+
+    if !debugger
+        .capabilities()
+        .supports_restart_frame
+        .unwrap_or(false)
+    {
+        cx.editor
+            .set_error("Debugger does not support frame restarts");
+        return;
+    }
+
+    let (frame, thread_id) = match (debugger.active_frame, debugger.thread_id) {
+        (Some(frame), Some(thread_id)) => (frame, thread_id),
+        _ => {
+            cx.editor.set_error("Cannot find current stack frame");
+            return;
+        }
+    };
+    let frame_id = debugger.stack_frames[&thread_id][frame].id;
+
+    let request = debugger.restart_frame(frame_id);
+    dap_callback(cx.jobs, request, move |editor, _compositor, _resp: ()| {
+        // The adapter follows up with a stopped event, but that alone does
+        // not force a re-fetch when the thread was already marked stopped:
+        // refresh the stack and jump to the restarted frame explicitly.
+        block_on(select_thread_id(editor, thread_id, true));
+    });
+}
+
+pub fn dap_disassemble(cx: &mut Context) {
+    let debugger = debugger!(cx.editor);
+
+    if !debugger
+        .capabilities()
+        .supports_disassemble_request
+        .unwrap_or(false)
+    {
+        cx.editor.set_error("Debugger does not support disassembly");
+        return;
+    }
+
+    let (frame, thread_id) = match (debugger.active_frame, debugger.thread_id) {
+        (Some(frame), Some(thread_id)) => (frame, thread_id),
+        _ => {
+            cx.editor.set_error("Cannot find current stack frame");
+            return;
+        }
+    };
+    let reference = match debugger.stack_frames[&thread_id][frame]
+        .instruction_pointer_reference
+        .clone()
+    {
+        Some(reference) => reference,
+        None => {
+            cx.editor
+                .set_error("Current stack frame has no instruction pointer reference");
+            return;
+        }
+    };
+
+    let request = debugger.disassemble(reference.clone(), 64);
+    dap_callback(
+        cx.jobs,
+        request,
+        move |editor, _compositor, response: dap::requests::DisassembleResponse| {
+            let mut contents = String::new();
+            let mut current_line = 0;
+            for (i, instruction) in response.instructions.iter().enumerate() {
+                let marker = if instruction.address == reference {
+                    current_line = i;
+                    "=>"
+                } else {
+                    "  "
+                };
+                contents.push_str(&format!(
+                    "{} {}  {:<24}  {}",
+                    marker,
+                    instruction.address,
+                    instruction.instruction_bytes.as_deref().unwrap_or(""),
+                    instruction.instruction
+                ));
+                if let (Some(source), Some(line)) = (&instruction.location, instruction.line) {
+                    if let Some(name) = source.name.as_deref() {
+                        contents.push_str(&format!("  ; {}:{}", name, line));
+                    }
+                }
+                contents.push('\n');
+            }
+
+            let loader = editor.syn_loader.clone();
+            editor.new_file(Action::HorizontalSplit);
+            let (view, doc) = current!(editor);
+            let transaction =
+                Transaction::change(doc.text(), [(0, 0, Some(contents.into()))].into_iter());
+            doc.apply(&transaction, view.id);
+            // The disassembly is a snapshot of the adapter's memory, not a file.
+            doc.readonly = true;
+            // Highlight the mnemonics with the nasm grammar when available.
+            let _ = doc.set_language_by_language_id("nasm", loader);
+            let pos = doc.text().line_to_char(current_line);
+            doc.set_selection(view.id, Selection::point(pos));
+        },
+    );
+}
+
 fn debug_parameter_prompt(
     completions: Vec<DebugConfigCompletion>,
     config_name: String,
@@ -416,9 +530,9 @@ pub fn dap_toggle_breakpoint_impl(cx: &mut Context, path: PathBuf, line: usize)
         });
     }
 
-    let debugger = debugger!(cx.editor);
+    let session = debug_session!(cx.editor);
 
-    if let Err(e) = breakpoints_changed(debugger, path, breakpoints) {
+    if let Err(e) = breakpoints_changed(session, path, breakpoints) {
         cx.editor
             .set_error(format!("Failed to set breakpoints: {}", e));
     }
@@ -497,6 +611,34 @@ pub fn dap_next(cx: &mut Context) {
     }
 }
 
+pub fn dap_step_in_instruction(cx: &mut Context) {
+    let debugger = debugger!(cx.editor);
+
+    if let Some(thread_id) = debugger.thread_id {
+        let request = debugger.step_in_instruction(thread_id);
+        dap_callback(cx.jobs, request, |editor, _compositor, _response: ()| {
+            debugger!(editor).resume_application();
+        });
+    } else {
+        cx.editor
+            .set_error("Currently active thread is not stopped. Switch the thread.");
+    }
+}
+
+pub fn dap_next_instruction(cx: &mut Context) {
+    let debugger = debugger!(cx.editor);
+
+    if let Some(thread_id) = debugger.thread_id {
+        let request = debugger.next_instruction(thread_id);
+        dap_callback(cx.jobs, request, |editor, _compositor, _response: ()| {
+            debugger!(editor).resume_application();
+        });
+    } else {
+        cx.editor
+            .set_error("Currently active thread is not stopped. Switch the thread.");
+    }
+}
+
 pub fn dap_variables(cx: &mut Context) {
     let debugger = debugger!(cx.editor);
 
@@ -587,7 +729,7 @@ pub fn dap_terminate(cx: &mut Context) {
     let request = debugger.disconnect(None);
     dap_callback(cx.jobs, request, |editor, _compositor, _response: ()| {
         // editor.set_error(format!("Failed to disconnect: {}", e));
-        editor.debugger = None;
+        editor.debugger.remove_active();
     });
 }
 
@@ -648,9 +790,9 @@ pub fn dap_edit_condition(cx: &mut Context) {
                             input => Some(input.to_owned()),
                         };
 
-                        let debugger = debugger!(cx.editor);
+                        let session = debug_session!(cx.editor);
 
-                        if let Err(e) = breakpoints_changed(debugger, path.clone(), breakpoints) {
+                        if let Err(e) = breakpoints_changed(session, path.clone(), breakpoints) {
                             cx.editor
                                 .set_error(format!("Failed to set breakpoints: {}", e));
                         }
@@ -690,8 +832,8 @@ pub fn dap_edit_log(cx: &mut Context) {
                             input => Some(input.to_owned()),
                         };
 
-                        let debugger = debugger!(cx.editor);
-                        if let Err(e) = breakpoints_changed(debugger, path.clone(), breakpoints) {
+                        let session = debug_session!(cx.editor);
+                        if let Err(e) = breakpoints_changed(session, path.clone(), breakpoints) {
                             cx.editor
                                 .set_error(format!("Failed to set breakpoints: {}", e));
                         }
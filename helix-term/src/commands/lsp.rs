@@ -14,7 +14,8 @@ use tui::{text::Span, widgets::Row};
 use super::{align_view, push_jump, Align, Context, Editor};
 
 use helix_core::{
-    syntax::LanguageServerFeature, text_annotations::InlineAnnotation, Selection, Uri,
+    diff::compare_ropes, syntax::LanguageServerFeature, text_annotations::InlineAnnotation, Rope,
+    Selection, Uri,
 };
 use helix_stdx::path;
 use helix_view::{
@@ -36,7 +37,8 @@ use std::{
     collections::{BTreeMap, HashSet},
     fmt::Display,
     future::Future,
-    path::Path,
+    path::{Path, PathBuf},
+    sync::Arc,
 };
 
 /// Gets the first language server that is attached to a document which supports a specific feature.
@@ -546,11 +548,46 @@ pub fn diagnostics_picker(cx: &mut Context) {
 
 pub fn workspace_diagnostics_picker(cx: &mut Context) {
     // TODO not yet filtered by LanguageServerFeature, need to do something similar as Document::shown_diagnostics here for all open documents
-    let diagnostics = cx.editor.diagnostics.clone();
+    let mut diagnostics = cx.editor.diagnostics.clone();
+    // Merge in the cached `workspace/diagnostic` pull results. Push
+    // diagnostics win for documents that have any, since they are never
+    // older than the last pull.
+    for language_server in cx.editor.language_servers.iter_clients() {
+        for (url, diags) in language_server.workspace_diagnostics().iter() {
+            let Ok(uri) = Uri::try_from(url.clone()) else {
+                continue;
+            };
+            diagnostics.entry(uri).or_insert_with(|| {
+                diags
+                    .iter()
+                    .map(|diag| (diag.clone(), language_server.id()))
+                    .collect()
+            });
+        }
+    }
     let picker = diag_picker(cx, diagnostics, DiagnosticsFormat::ShowSourcePath);
     cx.push_layer(Box::new(overlaid(picker)));
 }
 
+/// Kicks off a `workspace/diagnostic` pull on every initialized language
+/// server that supports it, refreshing the per-client caches the workspace
+/// diagnostics picker reads from. Run on `:lsp-restart` and by the optional
+/// `lsp.workspace-diagnostic-poll` timer.
+pub fn pull_workspace_diagnostics(editor: &Editor) {
+    for language_server in editor.language_servers.iter_clients() {
+        if !language_server.is_initialized() {
+            continue;
+        }
+        if let Some(future) = language_server.pull_workspace_diagnostics() {
+            tokio::spawn(async move {
+                if let Err(err) = future.await {
+                    log::error!("workspace diagnostic pull failed: {err}");
+                }
+            });
+        }
+    }
+}
+
 struct CodeActionOrCommandItem {
     lsp_item: lsp::CodeActionOrCommand,
     language_server_id: LanguageServerId,
@@ -623,15 +660,19 @@ fn action_fixes_diagnostics(action: &CodeActionOrCommand) -> bool {
     )
 }
 
-pub fn code_action(cx: &mut Context) {
+/// Collects the pending `textDocument/codeAction` requests for the primary
+/// selection, one per language server that supports them, each yielding its
+/// actions sorted the way the code action menu displays them.
+fn code_action_requests(
+    cx: &mut Context,
+) -> FuturesOrdered<impl Future<Output = anyhow::Result<Vec<CodeActionOrCommandItem>>>> {
     let (view, doc) = current!(cx.editor);
 
     let selection_range = doc.selection(view.id).primary();
 
     let mut seen_language_servers = HashSet::new();
 
-    let mut futures: FuturesOrdered<_> = doc
-        .language_servers_with_feature(LanguageServerFeature::CodeAction)
+    doc.language_servers_with_feature(LanguageServerFeature::CodeAction)
         .filter(|ls| seen_language_servers.insert(ls.id()))
         // TODO this should probably already been filtered in something like "language_servers_with_feature"
         .filter_map(|language_server| {
@@ -716,7 +757,11 @@ pub fn code_action(cx: &mut Context) {
                 })
                 .collect())
         })
-        .collect();
+        .collect()
+}
+
+pub fn code_action(cx: &mut Context) {
+    let mut futures = code_action_requests(cx);
 
     if futures.is_empty() {
         cx.editor
@@ -743,55 +788,326 @@ pub fn code_action(cx: &mut Context) {
 
                 // always present here
                 let action = action.unwrap();
-                let Some(language_server) = editor.language_server_by_id(action.language_server_id)
-                else {
-                    editor.set_error("Language Server disappeared");
-                    return;
-                };
-                let offset_encoding = language_server.offset_encoding();
+                apply_code_action(editor, action);
+            });
+            picker.move_down(); // pre-select the first item
 
-                match &action.lsp_item {
-                    lsp::CodeActionOrCommand::Command(command) => {
-                        log::debug!("code action command: {:?}", command);
-                        execute_lsp_command(editor, action.language_server_id, command.clone());
-                    }
-                    lsp::CodeActionOrCommand::CodeAction(code_action) => {
-                        log::debug!("code action: {:?}", code_action);
-                        // we support lsp "codeAction/resolve" for `edit` and `command` fields
-                        let mut resolved_code_action = None;
-                        if code_action.edit.is_none() || code_action.command.is_none() {
-                            if let Some(future) =
-                                language_server.resolve_code_action(code_action.clone())
-                            {
-                                if let Ok(response) = helix_lsp::block_on(future) {
-                                    if let Ok(code_action) =
-                                        serde_json::from_value::<CodeAction>(response)
-                                    {
-                                        resolved_code_action = Some(code_action);
-                                    }
-                                }
-                            }
-                        }
-                        let resolved_code_action =
-                            resolved_code_action.as_ref().unwrap_or(code_action);
+            let popup = Popup::new("code-action", picker).with_scrollbar(false);
 
-                        if let Some(ref workspace_edit) = resolved_code_action.edit {
-                            let _ = editor.apply_workspace_edit(offset_encoding, workspace_edit);
-                        }
+            compositor.replace_or_push("code-action", popup);
+        };
+
+        Ok(Callback::EditorCompositor(Box::new(call)))
+    });
+}
+
+/// Sends `codeAction/resolve` for an action that came back without its
+/// `edit` or `command` filled in and returns the resolved action.
+fn resolve_code_action(editor: &Editor, action: &CodeActionOrCommandItem) -> Option<CodeAction> {
+    let lsp::CodeActionOrCommand::CodeAction(code_action) = &action.lsp_item else {
+        return None;
+    };
+    if code_action.edit.is_some() && code_action.command.is_some() {
+        return None;
+    }
+    let language_server = editor.language_server_by_id(action.language_server_id)?;
+    let future = language_server.resolve_code_action(code_action.clone())?;
+    let response = block_on(future).ok()?;
+    serde_json::from_value(response).ok()
+}
+
+fn apply_code_action(editor: &mut Editor, action: &CodeActionOrCommandItem) {
+    let Some(language_server) = editor.language_server_by_id(action.language_server_id) else {
+        editor.set_error("Language Server disappeared");
+        return;
+    };
+    let offset_encoding = language_server.offset_encoding();
 
-                        // if code action provides both edit and command first the edit
-                        // should be applied and then the command
-                        if let Some(command) = &code_action.command {
-                            execute_lsp_command(editor, action.language_server_id, command.clone());
+    match &action.lsp_item {
+        lsp::CodeActionOrCommand::Command(command) => {
+            log::debug!("code action command: {:?}", command);
+            execute_lsp_command(editor, action.language_server_id, command.clone());
+        }
+        lsp::CodeActionOrCommand::CodeAction(code_action) => {
+            log::debug!("code action: {:?}", code_action);
+            // we support lsp "codeAction/resolve" for `edit` and `command` fields
+            let resolved_code_action = resolve_code_action(editor, action);
+            let resolved_code_action = resolved_code_action.as_ref().unwrap_or(code_action);
+
+            if let Some(ref workspace_edit) = resolved_code_action.edit {
+                let _ = editor.apply_workspace_edit(offset_encoding, workspace_edit);
+            }
+
+            // if code action provides both edit and command first the edit
+            // should be applied and then the command
+            if let Some(command) = &code_action.command {
+                execute_lsp_command(editor, action.language_server_id, command.clone());
+            }
+        }
+    }
+}
+
+/// A code action shown in the [`code_action_list`] picker, keyed to the
+/// in-memory document previewing what the action would do.
+struct CodeActionListItem {
+    action: CodeActionOrCommandItem,
+    kind: String,
+    preview_path: Arc<Path>,
+}
+
+/// Renders `workspace_edit` as a unified diff against the current contents
+/// of the files it touches.
+fn workspace_edit_diff(
+    editor: &Editor,
+    workspace_edit: &lsp::WorkspaceEdit,
+    offset_encoding: OffsetEncoding,
+) -> String {
+    use std::fmt::Write;
+
+    fn text_document_edits(document_edit: &lsp::TextDocumentEdit) -> (lsp::Url, Vec<lsp::TextEdit>) {
+        let edits = document_edit
+            .edits
+            .iter()
+            .map(|edit| match edit {
+                lsp::OneOf::Left(text_edit) => text_edit,
+                lsp::OneOf::Right(annotated_text_edit) => &annotated_text_edit.text_edit,
+            })
+            .cloned()
+            .collect();
+        (document_edit.text_document.uri.clone(), edits)
+    }
+
+    let mut out = String::new();
+    let mut changes: Vec<(lsp::Url, Vec<lsp::TextEdit>)> = Vec::new();
+    if let Some(ref document_changes) = workspace_edit.document_changes {
+        match document_changes {
+            lsp::DocumentChanges::Edits(document_edits) => {
+                changes.extend(document_edits.iter().map(text_document_edits));
+            }
+            lsp::DocumentChanges::Operations(operations) => {
+                for operation in operations {
+                    match operation {
+                        lsp::DocumentChangeOperation::Edit(document_edit) => {
+                            changes.push(text_document_edits(document_edit));
+                        }
+                        lsp::DocumentChangeOperation::Op(lsp::ResourceOp::Create(op)) => {
+                            writeln!(out, "create {}", op.uri).unwrap();
+                        }
+                        lsp::DocumentChangeOperation::Op(lsp::ResourceOp::Rename(op)) => {
+                            writeln!(out, "rename {} -> {}", op.old_uri, op.new_uri).unwrap();
+                        }
+                        lsp::DocumentChangeOperation::Op(lsp::ResourceOp::Delete(op)) => {
+                            writeln!(out, "delete {}", op.uri).unwrap();
                         }
                     }
                 }
-            });
-            picker.move_down(); // pre-select the first item
+            }
+        }
+    } else if let Some(ref workspace_changes) = workspace_edit.changes {
+        changes.extend(
+            workspace_changes
+                .iter()
+                .map(|(uri, edits)| (uri.clone(), edits.clone())),
+        );
+    }
 
-            let popup = Popup::new("code-action", picker).with_scrollbar(false);
+    for (url, text_edits) in changes {
+        let Some(file_path) = Uri::try_from(&url)
+            .ok()
+            .and_then(|uri| uri.as_path().map(Path::to_path_buf))
+        else {
+            continue;
+        };
+        // Diff against what the user sees: the open document if there is
+        // one, otherwise the file on disk (or nothing, for new files).
+        let old = editor
+            .document_by_path(&file_path)
+            .map(|doc| doc.text().clone())
+            .or_else(|| std::fs::read_to_string(&file_path).ok().map(Rope::from))
+            .unwrap_or_default();
+        let transaction = helix_lsp::util::generate_transaction_from_edits(
+            &old,
+            text_edits,
+            offset_encoding,
+        );
+        let mut new = old.clone();
+        transaction.apply(&mut new);
+
+        let relative = path::get_relative_path(&file_path);
+        writeln!(out, "--- a/{}", relative.display()).unwrap();
+        writeln!(out, "+++ b/{}", relative.display()).unwrap();
+
+        // Re-diff line-wise so a server that sends one whole-document edit
+        // still previews as just the lines it actually changes.
+        let mut line_offset = 0isize;
+        for (from, to, replacement) in compare_ropes(&old, &new).changes_iter() {
+            let start_line = old.char_to_line(from);
+            let end_line = if to > from {
+                old.char_to_line(to - 1)
+            } else {
+                start_line
+            };
+            let hunk_start = old.line_to_char(start_line);
+            let hunk_end = old.line_to_char((end_line + 1).min(old.len_lines()));
+            let removed = old.slice(hunk_start..hunk_end).to_string();
+            let mut added = old.slice(hunk_start..from).to_string();
+            if let Some(replacement) = &replacement {
+                added.push_str(replacement);
+            }
+            added.push_str(&old.slice(to..hunk_end).to_string());
+
+            let removed_lines = removed.lines().count();
+            let added_lines = added.lines().count();
+            writeln!(
+                out,
+                "@@ -{},{} +{},{} @@",
+                start_line + 1,
+                removed_lines,
+                (start_line as isize + line_offset) as usize + 1,
+                added_lines
+            )
+            .unwrap();
+            for line in removed.lines() {
+                writeln!(out, "-{}", line).unwrap();
+            }
+            for line in added.lines() {
+                writeln!(out, "+{}", line).unwrap();
+            }
+            line_offset += added_lines as isize - removed_lines as isize;
+        }
+    }
 
-            compositor.replace_or_push("code-action", popup);
+    if out.is_empty() {
+        out.push_str("The workspace edit is empty");
+    }
+    out
+}
+
+/// Renders what `action` would do, for the picker's preview pane: the
+/// (resolved) workspace edit as a unified diff, or the command the action
+/// runs. The `bool` says whether the text is a diff.
+fn code_action_preview(editor: &Editor, action: &CodeActionOrCommandItem) -> (String, bool) {
+    fn describe_command(command: &lsp::Command) -> String {
+        let mut text = format!("command: {}\n", command.command);
+        for argument in command.arguments.as_deref().unwrap_or_default() {
+            text.push_str(&format!("argument: {}\n", argument));
+        }
+        text
+    }
+
+    let code_action = match &action.lsp_item {
+        lsp::CodeActionOrCommand::Command(command) => return (describe_command(command), false),
+        lsp::CodeActionOrCommand::CodeAction(code_action) => code_action,
+    };
+    let Some(offset_encoding) = editor
+        .language_server_by_id(action.language_server_id)
+        .map(|language_server| language_server.offset_encoding())
+    else {
+        return ("Language Server disappeared".to_string(), false);
+    };
+
+    let resolved = resolve_code_action(editor, action);
+    let code_action = resolved.as_ref().unwrap_or(code_action);
+    if let Some(ref workspace_edit) = code_action.edit {
+        (
+            workspace_edit_diff(editor, workspace_edit, offset_encoding),
+            true,
+        )
+    } else if let Some(ref command) = code_action.command {
+        (describe_command(command), false)
+    } else {
+        (
+            "The language server returned no edit for this action".to_string(),
+            false,
+        )
+    }
+}
+
+/// Like [`code_action`], but as a picker that previews what the focused
+/// action would do: its resolved workspace edit rendered as a unified diff,
+/// or, for actions that only run a command, the command and its arguments.
+pub fn code_action_list(cx: &mut Context) {
+    let mut futures = code_action_requests(cx);
+
+    if futures.is_empty() {
+        cx.editor
+            .set_error("No configured language server supports code actions");
+        return;
+    }
+
+    cx.jobs.callback(async move {
+        let mut actions = Vec::new();
+        while let Some(mut lsp_items) = futures.try_next().await? {
+            actions.append(&mut lsp_items);
+        }
+
+        let call = move |editor: &mut Editor, compositor: &mut Compositor| {
+            if actions.is_empty() {
+                editor.set_error("No code actions available");
+                return;
+            }
+
+            let mut items = Vec::with_capacity(actions.len());
+            let mut previews = Vec::with_capacity(actions.len());
+            for (idx, action) in actions.into_iter().enumerate() {
+                let (text, is_diff) = code_action_preview(editor, &action);
+                // The path is only a cache key, shared with the preview
+                // callback below; it does not exist on disk.
+                let preview_path: Arc<Path> = PathBuf::from(format!("action-{idx}.diff")).into();
+                let mut doc = Document::from(Rope::from(text), None, editor.config.clone());
+                if is_diff {
+                    // Route the preview through the diff grammar so changed
+                    // lines get syntax highlighted.
+                    doc.set_language(
+                        editor
+                            .syn_loader
+                            .load()
+                            .language_config_for_scope("source.diff"),
+                        Some(editor.syn_loader.clone()),
+                    );
+                }
+                previews.push((preview_path.clone(), doc));
+                let kind = match &action.lsp_item {
+                    lsp::CodeActionOrCommand::CodeAction(CodeAction {
+                        kind: Some(kind), ..
+                    }) => kind.as_str().to_string(),
+                    lsp::CodeActionOrCommand::CodeAction(_) => String::new(),
+                    lsp::CodeActionOrCommand::Command(_) => "command".to_string(),
+                };
+                items.push(CodeActionListItem {
+                    action,
+                    kind,
+                    preview_path,
+                });
+            }
+
+            let columns = [
+                ui::PickerColumn::new("action", |item: &CodeActionListItem, _| {
+                    match &item.action.lsp_item {
+                        lsp::CodeActionOrCommand::CodeAction(action) => {
+                            action.title.as_str().into()
+                        }
+                        lsp::CodeActionOrCommand::Command(command) => {
+                            command.title.as_str().into()
+                        }
+                    }
+                }),
+                ui::PickerColumn::new("kind", |item: &CodeActionListItem, _| {
+                    item.kind.as_str().into()
+                }),
+            ];
+            let picker = Picker::new(
+                columns,
+                0,
+                items,
+                (),
+                |cx, item: &CodeActionListItem, _action| {
+                    apply_code_action(cx.editor, &item.action);
+                },
+            )
+            .with_preview(|_editor, item| Some((item.preview_path.as_ref().into(), None)))
+            .with_preview_documents(previews);
+            compositor.push(Box::new(overlaid(picker)));
         };
 
         Ok(Callback::EditorCompositor(Box::new(call)))
@@ -959,10 +1275,47 @@ pub fn goto_type_definition(cx: &mut Context) {
 }
 
 pub fn goto_implementation(cx: &mut Context) {
-    goto_single_impl(
-        cx,
-        LanguageServerFeature::GotoImplementation,
-        |ls, pos, doc_id| ls.goto_implementation(doc_id, pos, None),
+    let (view, doc) = current!(cx.editor);
+
+    // Prefer a server advertising the standard capability, but fall back to
+    // one serving the newer `textDocument/implementors` request: servers
+    // that adopted it don't necessarily set `implementation_provider`.
+    let language_server = doc
+        .language_servers_with_feature(LanguageServerFeature::GotoImplementation)
+        .next()
+        .or_else(|| {
+            doc.language_servers()
+                .find(|language_server| language_server.supports_implementors())
+        });
+    let language_server = match language_server {
+        Some(language_server) => language_server,
+        None => {
+            cx.editor.set_status(format!(
+                "No configured language server supports {}",
+                LanguageServerFeature::GotoImplementation
+            ));
+            return;
+        }
+    };
+    let offset_encoding = language_server.offset_encoding();
+    let pos = doc.position(view.id, offset_encoding);
+    let future = match language_server.goto_implementation(doc.identifier(), pos, None) {
+        Some(future) => future.boxed(),
+        None => language_server
+            .goto_implementors(doc.identifier(), pos, None)
+            .boxed(),
+    };
+
+    cx.callback(
+        future,
+        move |editor, compositor, response: Option<lsp::GotoDefinitionResponse>| {
+            let items = to_locations(response);
+            if items.is_empty() {
+                editor.set_error("No implementation found.");
+            } else {
+                goto_impl(editor, compositor, items, offset_encoding);
+            }
+        },
     );
 }
 
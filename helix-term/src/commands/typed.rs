@@ -11,7 +11,7 @@ use helix_core::indent::MAX_INDENT;
 use helix_core::{line_ending, shellwords::Shellwords};
 use helix_stdx::path::home_dir;
 use helix_view::document::{read_to_string, DEFAULT_LANGUAGE_NAME};
-use helix_view::editor::{CloseError, ConfigEvent};
+use helix_view::editor::{CloseError, ConfigEvent, FindMode};
 use serde_json::Value;
 use ui::completers::{self, Completer};
 
@@ -464,6 +464,27 @@ fn format(
     Ok(())
 }
 
+fn format_selection(
+    cx: &mut compositor::Context,
+    _args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let mut cx = Context {
+        register: None,
+        count: None,
+        editor: cx.editor,
+        callback: Vec::new(),
+        on_next_key_callback: None,
+        jobs: cx.jobs,
+    };
+    format_selections(&mut cx);
+    Ok(())
+}
+
 fn set_indent_style(
     cx: &mut compositor::Context,
     args: &[Cow<str>],
@@ -505,6 +526,35 @@ fn set_indent_style(
     Ok(())
 }
 
+/// Sets or reports how the `f`/`t` family of commands matches characters.
+fn set_find_mode(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    // If no argument, report the current find mode.
+    if args.is_empty() {
+        cx.editor.set_status(match cx.editor.find_mode {
+            FindMode::Literal => "literal",
+            FindMode::Smart => "smart",
+        });
+        return Ok(());
+    }
+
+    cx.editor.find_mode = match args.first().map(AsRef::as_ref) {
+        Some("smart") => FindMode::Smart,
+        Some("literal") => FindMode::Literal,
+        Some(arg) => bail!("invalid find mode '{}', expected 'smart' or 'literal'", arg),
+        None => unreachable!(),
+    };
+
+    Ok(())
+}
+
 /// Sets or reports the current document's line ending setting.
 fn set_line_ending(
     cx: &mut compositor::Context,
@@ -1521,6 +1571,17 @@ fn lsp_restart(
         cx.editor.refresh_language_servers(document_id);
     }
 
+    // Re-pull workspace diagnostics once the restarted servers have had a
+    // chance to initialize; servers still starting up are skipped by the
+    // pull and covered by the next poll tick instead.
+    tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+        crate::job::dispatch(|editor, _| {
+            crate::commands::lsp::pull_workspace_diagnostics(editor);
+        })
+        .await;
+    });
+
     Ok(())
 }
 
@@ -1553,6 +1614,240 @@ fn lsp_stop(
     Ok(())
 }
 
+fn lsp_capabilities(
+    cx: &mut compositor::Context,
+    _args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    // `true` when a capability is present and not explicitly disabled;
+    // handles both plain booleans and the option structs servers may
+    // advertise instead.
+    fn supported<T: serde::Serialize>(capability: &Option<T>) -> bool {
+        match capability {
+            None => false,
+            Some(capability) => {
+                serde_json::to_value(capability).map_or(true, |value| value != Value::Bool(false))
+            }
+        }
+    }
+
+    let doc = doc!(cx.editor);
+    let mut contents = String::new();
+    for ls in doc.language_servers() {
+        let caps = ls.capabilities();
+        let rows = [
+            ("hover", supported(&caps.hover_provider)),
+            ("completion", supported(&caps.completion_provider)),
+            ("signature help", supported(&caps.signature_help_provider)),
+            ("goto definition", supported(&caps.definition_provider)),
+            ("goto declaration", supported(&caps.declaration_provider)),
+            (
+                "goto type definition",
+                supported(&caps.type_definition_provider),
+            ),
+            (
+                "goto implementation",
+                supported(&caps.implementation_provider),
+            ),
+            ("find references", supported(&caps.references_provider)),
+            (
+                "document highlight",
+                supported(&caps.document_highlight_provider),
+            ),
+            (
+                "document symbols",
+                supported(&caps.document_symbol_provider),
+            ),
+            (
+                "workspace symbols",
+                supported(&caps.workspace_symbol_provider),
+            ),
+            ("code actions", supported(&caps.code_action_provider)),
+            ("code lens", supported(&caps.code_lens_provider)),
+            ("formatting", supported(&caps.document_formatting_provider)),
+            (
+                "range formatting",
+                supported(&caps.document_range_formatting_provider),
+            ),
+            ("rename", supported(&caps.rename_provider)),
+            ("folding ranges", supported(&caps.folding_range_provider)),
+            ("inlay hints", supported(&caps.inlay_hint_provider)),
+            (
+                "workspace commands",
+                supported(&caps.execute_command_provider),
+            ),
+            ("semantic tokens", supported(&caps.semantic_tokens_provider)),
+        ];
+
+        if !contents.is_empty() {
+            contents.push('\n');
+        }
+        writeln!(contents, "{}:", ls.name())?;
+        let width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+        for (label, supported) in rows {
+            let mark = if supported { '✓' } else { '✗' };
+            writeln!(contents, "  {label:width$}  {mark}")?;
+        }
+    }
+    ensure!(
+        !contents.is_empty(),
+        "No active language servers for this document"
+    );
+
+    let callback = async move {
+        let call: job::Callback = Callback::EditorCompositor(Box::new(
+            move |_editor: &mut Editor, compositor: &mut Compositor| {
+                let contents = ui::Text::new(contents);
+                let popup = Popup::new("lsp-capabilities", contents).auto_close(true);
+                compositor.replace_or_push("lsp-capabilities", popup);
+            },
+        ));
+        Ok(call)
+    };
+
+    cx.jobs.callback(callback);
+
+    Ok(())
+}
+
+fn lsp_code_action_list(
+    cx: &mut compositor::Context,
+    _args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let mut cx = Context {
+        register: None,
+        count: None,
+        editor: cx.editor,
+        callback: Vec::new(),
+        on_next_key_callback: None,
+        jobs: cx.jobs,
+    };
+    lsp::code_action_list(&mut cx);
+    Ok(())
+}
+
+/// One ancestor level shown by `:tree-sitter-node`: the node kind with its
+/// field name, the injection layer's language and the node's location.
+struct SyntaxNodeLevel {
+    kind: String,
+    field: Option<String>,
+    language: String,
+    location: String,
+    range: Range,
+}
+
+fn tree_sitter_node(
+    cx: &mut compositor::Context,
+    _args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    use helix_core::tree_sitter::Node;
+
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    /// The field name `node` is stored under in its parent, e.g. `body` for
+    /// the block of a `function_item`.
+    fn field_name(node: &Node) -> Option<String> {
+        let parent = node.parent()?;
+        (0..parent.child_count())
+            .find(|&i| parent.child(i).as_ref() == Some(node))
+            .and_then(|i| parent.field_name_for_child(i as u32))
+            .map(str::to_string)
+    }
+
+    let (view, doc) = current_ref!(cx.editor);
+    let Some(syntax) = doc.syntax() else {
+        cx.editor.set_error("Syntax tree is not available");
+        return Ok(());
+    };
+    let text = doc.text().slice(..);
+    let primary_selection = doc.selection(view.id).primary();
+    let loader = cx.editor.syn_loader.load();
+
+    let cursor = &mut syntax.walk();
+    cursor.reset_to_char_range(text, primary_selection.from(), primary_selection.to());
+
+    // Innermost level first, like the cursor walks.
+    let mut levels = Vec::new();
+    loop {
+        let node = cursor.node();
+        let language = loader
+            .language_name_for_highlight_config(cursor.layer_highlight_config())
+            .unwrap_or(DEFAULT_LANGUAGE_NAME)
+            .to_string();
+        let start = cursor.node_start_position();
+        let end = cursor.node_end_position();
+        levels.push(SyntaxNodeLevel {
+            kind: node.kind().to_string(),
+            field: field_name(&node),
+            language,
+            location: format!(
+                "{}..{} ({}:{}-{}:{})",
+                node.start_byte(),
+                node.end_byte(),
+                start.row + 1,
+                start.column + 1,
+                end.row + 1,
+                end.column + 1,
+            ),
+            range: Range::new(
+                text.byte_to_char(node.start_byte()),
+                text.byte_to_char(node.end_byte()),
+            ),
+        });
+        if !cursor.goto_parent() {
+            break;
+        }
+    }
+
+    let callback = async move {
+        let call: job::Callback = Callback::EditorCompositor(Box::new(
+            move |_editor: &mut Editor, compositor: &mut Compositor| {
+                let columns = [
+                    ui::PickerColumn::new("node", |item: &SyntaxNodeLevel, _: &()| {
+                        match &item.field {
+                            Some(field) => format!("{}: {}", field, item.kind).into(),
+                            None => item.kind.as_str().into(),
+                        }
+                    }),
+                    ui::PickerColumn::new("language", |item: &SyntaxNodeLevel, _: &()| {
+                        item.language.as_str().into()
+                    }),
+                    ui::PickerColumn::new("range", |item: &SyntaxNodeLevel, _: &()| {
+                        item.location.as_str().into()
+                    }),
+                ];
+                let picker = ui::Picker::new(
+                    columns,
+                    0,
+                    levels,
+                    (),
+                    |cx, item: &SyntaxNodeLevel, _action| {
+                        let (view, doc) = current!(cx.editor);
+                        doc.set_selection(view.id, Selection::from(item.range));
+                    },
+                );
+                compositor.push(Box::new(overlaid(picker)));
+            },
+        ));
+        Ok(call)
+    };
+    cx.jobs.callback(callback);
+
+    Ok(())
+}
+
 fn tree_sitter_scopes(
     cx: &mut compositor::Context,
     _args: &[Cow<str>],
@@ -1740,7 +2035,7 @@ fn debug_eval(
         return Ok(());
     }
 
-    if let Some(debugger) = cx.editor.debugger.as_mut() {
+    if let Some(debugger) = cx.editor.debugger.active_client_mut() {
         let (frame, thread_id) = match (debugger.active_frame, debugger.thread_id) {
             (Some(frame), Some(thread_id)) => (frame, thread_id),
             _ => {
@@ -1795,6 +2090,89 @@ fn debug_remote(
     dap_start_impl(cx, name.as_deref(), address, Some(args))
 }
 
+fn dap_switch(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let name = args.first().context("No session name provided")?;
+    if !cx.editor.debugger.switch(name) {
+        let names: Vec<_> = cx.editor.debugger.names().collect();
+        bail!(
+            "No debug session named '{}' (running: {})",
+            name,
+            names.join(", ")
+        );
+    }
+    cx.editor
+        .set_status(format!("Switched to debug session '{}'", name));
+    Ok(())
+}
+
+fn dap_rename_session(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let name = args.first().context("No session name provided")?;
+    ensure!(
+        cx.editor.debugger.rename_active(name.to_string()),
+        "No active debug session"
+    );
+    Ok(())
+}
+
+fn dap_add_path_mapping(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    ensure!(args.len() == 2, ":dap-add-path-mapping takes <from> <to>");
+    let session = cx
+        .editor
+        .debugger
+        .active_session_mut()
+        .context("No active debug session")?;
+    session.source_map.insert(
+        helix_stdx::path::canonicalize(args[0].as_ref()),
+        helix_stdx::path::canonicalize(args[1].as_ref()),
+    );
+    Ok(())
+}
+
+fn dap_disassemble(
+    cx: &mut compositor::Context,
+    _args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let mut cx = Context {
+        register: None,
+        count: None,
+        editor: cx.editor,
+        callback: Vec::new(),
+        on_next_key_callback: None,
+        jobs: cx.jobs,
+    };
+    dap::dap_disassemble(&mut cx);
+    Ok(())
+}
+
 fn tutor(
     cx: &mut compositor::Context,
     _args: &[Cow<str>],
@@ -1938,6 +2316,43 @@ fn set_option(
     Ok(())
 }
 
+/// Turn an individual mouse feature on or off at runtime, e.g.
+/// `:mouse scroll off`.
+fn set_mouse(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    if args.len() != 2 {
+        anyhow::bail!("Bad arguments. Usage: `:mouse <feature> on|off`");
+    }
+    let value = match args[1].as_ref() {
+        "on" => true,
+        "off" => false,
+        arg => anyhow::bail!("Expected `on` or `off`, got `{}`", arg),
+    };
+
+    let mut config = cx.editor.config().deref().clone();
+    match args[0].as_ref() {
+        "enable" => config.mouse.enable = value,
+        "click-to-move-cursor" => config.mouse.click_to_move_cursor = value,
+        "scroll" => config.mouse.scroll = value,
+        "drag-to-select" => config.mouse.drag_to_select = value,
+        "double-click-word-select" => config.mouse.double_click_word_select = value,
+        feature => anyhow::bail!("Unknown mouse feature `{}`", feature),
+    }
+
+    cx.editor
+        .config_events
+        .0
+        .send(ConfigEvent::Update(Box::new(config)))?;
+    Ok(())
+}
+
 /// Toggle boolean config option at runtime. Access nested values by dot
 /// syntax, for example to toggle smart case search, use `:toggle search.smart-
 /// case`.
@@ -2152,6 +2567,40 @@ fn reflow(
     Ok(())
 }
 
+/// Open a file in the xxd-style hex view. With no argument the current
+/// document's file is used, which is the practical route for files that fail
+/// UTF-8 decoding or are detected as binary.
+fn hex_view(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let path = match args.first() {
+        Some(path) => helix_stdx::path::expand_tilde(Path::new(path.as_ref())).into_owned(),
+        None => doc!(cx.editor)
+            .path()
+            .cloned()
+            .ok_or_else(|| anyhow!("current buffer has no path"))?,
+    };
+
+    let document = ui::hex::HexDocument::open(&path)?;
+    let callback = async move {
+        let call: job::Callback = Callback::EditorCompositor(Box::new(
+            move |_editor: &mut Editor, compositor: &mut Compositor| {
+                compositor.replace_or_push("hex-view", ui::hex::HexView::new(document));
+            },
+        ));
+        Ok(call)
+    };
+    cx.jobs.callback(callback);
+
+    Ok(())
+}
+
 fn tree_sitter_subtree(
     cx: &mut compositor::Context,
     _args: &[Cow<str>],
@@ -2191,6 +2640,166 @@ fn tree_sitter_subtree(
     Ok(())
 }
 
+fn copilot_enable_workspace(
+    cx: &mut compositor::Context,
+    _args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let root = helix_loader::find_workspace().0;
+    helix_view::copilot_consent::record(&root, true);
+    cx.editor
+        .set_status(format!("Copilot enabled for {}", root.display()));
+
+    Ok(())
+}
+
+fn copilot_status(
+    cx: &mut compositor::Context,
+    _args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let stats = helix_view::copilot_stats::snapshot();
+    let config = cx.editor.config();
+    cx.editor.set_status(format!(
+        "copilot: {} shown, {} accepted, {} partially accepted, {} rejected, {} errors (limits: {} bytes, {} lines)",
+        stats.shown,
+        stats.accepted_full,
+        stats.accepted_partial,
+        stats.rejected,
+        stats.errors,
+        config.copilot.max_file_size,
+        config.copilot.max_line_count,
+    ));
+
+    Ok(())
+}
+
+fn copilot_reset_stats(
+    cx: &mut compositor::Context,
+    _args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    helix_view::copilot_stats::reset();
+    cx.editor.set_status("Copilot statistics reset");
+
+    Ok(())
+}
+
+fn close_quickfix(
+    cx: &mut compositor::Context,
+    _args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    *cx.editor.quickfix.lock() = None;
+
+    Ok(())
+}
+
+fn explorer_sort(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+    ensure!(
+        args.len() == 1,
+        ":explorer-sort takes the sort mode as its only argument"
+    );
+    let sort = args[0].parse::<ui::ExplorerSort>()?;
+
+    let callback = async move {
+        let call: job::Callback = Callback::EditorCompositor(Box::new(
+            move |editor: &mut Editor, compositor: &mut Compositor| {
+                let Some(editor_view) = compositor.find::<ui::EditorView>() else {
+                    return;
+                };
+                match editor_view.explorer.as_mut() {
+                    Some(explorer) => explorer.set_sort(sort),
+                    None => editor.set_error("Explorer is not opened"),
+                }
+            },
+        ));
+        Ok(call)
+    };
+    cx.jobs.callback(callback);
+
+    Ok(())
+}
+
+fn explorer_find_file(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let query = args.first().map(|query| query.to_string());
+    let callback = async move {
+        let call: job::Callback = Callback::EditorCompositor(Box::new(
+            move |editor: &mut Editor, compositor: &mut Compositor| {
+                let picker = match compositor
+                    .find::<ui::EditorView>()
+                    .and_then(|editor_view| editor_view.explorer.as_ref())
+                {
+                    Some(explorer) => explorer.find_file_picker(editor, query.as_deref()),
+                    None => {
+                        editor.set_error("Explorer is not opened");
+                        return;
+                    }
+                };
+                compositor.push(picker);
+            },
+        ));
+        Ok(call)
+    };
+    cx.jobs.callback(callback);
+
+    Ok(())
+}
+
+fn tree_sitter_breadcrumb(
+    cx: &mut compositor::Context,
+    _args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let (view, doc) = current!(cx.editor);
+
+    let Some(syntax) = doc.syntax() else {
+        cx.editor.set_error("Syntax tree is not available");
+        return Ok(());
+    };
+    let text = doc.text().slice(..);
+    let primary_selection = doc.selection(view.id).primary();
+    let kinds = helix_core::object::node_kind_breadcrumb(syntax, text, primary_selection);
+    cx.editor.set_status(kinds.join(" > "));
+
+    Ok(())
+}
+
 fn open_config(
     cx: &mut compositor::Context,
     _args: &[Cow<str>],
@@ -2659,6 +3268,13 @@ pub const TYPABLE_COMMAND_LIST: &[TypableCommand] = &[
         fun: format,
         signature: CommandSignature::none(),
     },
+    TypableCommand {
+        name: "format-selection",
+        aliases: &[],
+        doc: "Format the current selection via the language server's range formatting, falling back to restricting a whole-document format to the selection.",
+        fun: format_selection,
+        signature: CommandSignature::none(),
+    },
     TypableCommand {
         name: "indent-style",
         aliases: &[],
@@ -2666,6 +3282,13 @@ pub const TYPABLE_COMMAND_LIST: &[TypableCommand] = &[
         fun: set_indent_style,
         signature: CommandSignature::none(),
     },
+    TypableCommand {
+        name: "set-find-mode",
+        aliases: &[],
+        doc: "Set how f/t match characters: 'smart' skips matches inside strings and comments, 'literal' jumps to every occurrence.",
+        fun: set_find_mode,
+        signature: CommandSignature::none(),
+    },
     TypableCommand {
         name: "line-ending",
         aliases: &[],
@@ -2921,6 +3544,20 @@ pub const TYPABLE_COMMAND_LIST: &[TypableCommand] = &[
         fun: lsp_stop,
         signature: CommandSignature::none(),
     },
+    TypableCommand {
+        name: "lsp-capabilities",
+        aliases: &["lsp-show-capabilities"],
+        doc: "Show which capabilities the language servers used by the current doc advertise",
+        fun: lsp_capabilities,
+        signature: CommandSignature::none(),
+    },
+    TypableCommand {
+        name: "lsp-code-action-list",
+        aliases: &[],
+        doc: "Open a picker of code actions for the primary selection, previewing each action's changes as a diff",
+        fun: lsp_code_action_list,
+        signature: CommandSignature::none(),
+    },
     TypableCommand {
         name: "tree-sitter-scopes",
         aliases: &[],
@@ -2956,6 +3593,34 @@ pub const TYPABLE_COMMAND_LIST: &[TypableCommand] = &[
         fun: debug_eval,
         signature: CommandSignature::none(),
     },
+    TypableCommand {
+        name: "dap-switch",
+        aliases: &[],
+        doc: "Make the debug session with the given name the active one.",
+        fun: dap_switch,
+        signature: CommandSignature::none(),
+    },
+    TypableCommand {
+        name: "dap-rename-session",
+        aliases: &[],
+        doc: "Rename the active debug session.",
+        fun: dap_rename_session,
+        signature: CommandSignature::none(),
+    },
+    TypableCommand {
+        name: "dap-add-path-mapping",
+        aliases: &[],
+        doc: "Map a local source path to the path the debug adapter knows it by, for source-mapped breakpoints.",
+        fun: dap_add_path_mapping,
+        signature: CommandSignature::all(completers::filename),
+    },
+    TypableCommand {
+        name: "dap-disassemble",
+        aliases: &[],
+        doc: "Open a read-only disassembly of the current stack frame.",
+        fun: dap_disassemble,
+        signature: CommandSignature::none(),
+    },
     TypableCommand {
         name: "vsplit",
         aliases: &["vs"],
@@ -3013,6 +3678,13 @@ pub const TYPABLE_COMMAND_LIST: &[TypableCommand] = &[
         // TODO: Add support for completion of the options value(s), when appropriate.
         signature: CommandSignature::positional(&[completers::setting]),
     },
+    TypableCommand {
+        name: "mouse",
+        aliases: &[],
+        doc: "Toggle a mouse feature at runtime, for example `:mouse scroll off`.\nFeatures: enable, click-to-move-cursor, scroll, drag-to-select, double-click-word-select.",
+        fun: set_mouse,
+        signature: CommandSignature::none(),
+    },
     TypableCommand {
         name: "toggle-option",
         aliases: &["toggle"],
@@ -3048,6 +3720,13 @@ pub const TYPABLE_COMMAND_LIST: &[TypableCommand] = &[
         fun: reflow,
         signature: CommandSignature::none(),
     },
+    TypableCommand {
+        name: "hex-view",
+        aliases: &["hex"],
+        doc: "Open the current file (or the given path) in a hex view showing offset, hex bytes and ASCII columns.",
+        fun: hex_view,
+        signature: CommandSignature::positional(&[completers::filename]),
+    },
     TypableCommand {
         name: "tree-sitter-subtree",
         aliases: &["ts-subtree"],
@@ -3055,6 +3734,62 @@ pub const TYPABLE_COMMAND_LIST: &[TypableCommand] = &[
         fun: tree_sitter_subtree,
         signature: CommandSignature::none(),
     },
+    TypableCommand {
+        name: "tree-sitter-breadcrumb",
+        aliases: &["ts-breadcrumb"],
+        doc: "Show the chain of named syntax node kinds enclosing the primary selection in the status line.",
+        fun: tree_sitter_breadcrumb,
+        signature: CommandSignature::none(),
+    },
+    TypableCommand {
+        name: "tree-sitter-node",
+        aliases: &["ts-node"],
+        doc: "Inspect the syntax nodes under the primary cursor: each ancestor's kind, field name, injection language and range. Enter selects the chosen node.",
+        fun: tree_sitter_node,
+        signature: CommandSignature::none(),
+    },
+    TypableCommand {
+        name: "copilot-enable-workspace",
+        aliases: &[],
+        doc: "Enable copilot for the current workspace, overriding an earlier denial of the consent prompt.",
+        fun: copilot_enable_workspace,
+        signature: CommandSignature::none(),
+    },
+    TypableCommand {
+        name: "copilot-status",
+        aliases: &[],
+        doc: "Show how many copilot suggestions were shown, accepted, rejected and how many requests failed this session.",
+        fun: copilot_status,
+        signature: CommandSignature::none(),
+    },
+    TypableCommand {
+        name: "copilot-reset-stats",
+        aliases: &[],
+        doc: "Reset the session counters shown by :copilot-status.",
+        fun: copilot_reset_stats,
+        signature: CommandSignature::none(),
+    },
+    TypableCommand {
+        name: "close-quickfix",
+        aliases: &[],
+        doc: "Discard the quickfix list.",
+        fun: close_quickfix,
+        signature: CommandSignature::none(),
+    },
+    TypableCommand {
+        name: "explorer-sort",
+        aliases: &[],
+        doc: "Change the explorer ordering ('dirs-first', 'name' or 'reversed'), re-sorting the tree in place.",
+        fun: explorer_sort,
+        signature: CommandSignature::none(),
+    },
+    TypableCommand {
+        name: "explorer-find-file",
+        aliases: &[],
+        doc: "Open the file picker scoped to the explorer's current root, with an optional initial query. The picked file is revealed in the tree.",
+        fun: explorer_find_file,
+        signature: CommandSignature::none(),
+    },
     TypableCommand {
         name: "config-reload",
         aliases: &[],
@@ -44,7 +44,7 @@ impl Context<'_> {
     /// let mut cx = Context::dummy(&mut jobs, &mut editor);
     /// ```
     #[cfg(test)]
-    pub fn dummy(jobs: &'a mut Jobs, editor: &'a mut helix_view::Editor) -> Context<'a> {
+    pub fn dummy<'a>(jobs: &'a mut Jobs, editor: &'a mut helix_view::Editor) -> Context<'a> {
         Context {
             jobs,
             scroll: None,
@@ -66,14 +66,20 @@ impl Context<'_> {
         use std::sync::Arc;
 
         let config = Arc::new(ArcSwap::from_pointee(Config::default()));
+        let lang_loader = syntax::Loader::new(Configuration {
+            language: vec![],
+            language_server: Default::default(),
+        })
+        .unwrap();
+        let handlers = crate::handlers::setup(config.clone());
         Editor::new(
             Rect::new(0, 0, 60, 120),
             Arc::new(theme::Loader::new(&[])),
-            Arc::new(syntax::Loader::new(Configuration { language: vec![] })),
-            Arc::new(Arc::new(Map::new(
-                Arc::clone(&config),
-                |config: &Config| &config.editor,
-            ))),
+            Arc::new(ArcSwap::from_pointee(lang_loader)),
+            Arc::new(Map::new(Arc::clone(&config), |config: &Config| {
+                &config.editor
+            })),
+            handlers,
         )
     }
 }
@@ -0,0 +1,222 @@
+//! Vim-compatible digraphs for `insert_digraph`.
+//!
+//! A digraph maps a two-character code to a single Unicode character, e.g.
+//! `a:` to `ä` or `->` to `→`. The codes follow RFC 1345 as used by Vim's
+//! `CTRL-K` insert-mode command; this table covers the commonly used subset
+//! (Latin accents, punctuation, currency, Greek, arrows and math symbols).
+//! Users can extend or override it via `editor.digraphs` in the config.
+
+/// Built-in digraph table, `(code, character)` pairs.
+static DIGRAPHS: &[(&str, char)] = &[
+    // Latin letters with diacritics
+    ("A!", 'À'),
+    ("A'", 'Á'),
+    ("A>", 'Â'),
+    ("A?", 'Ã'),
+    ("A:", 'Ä'),
+    ("AA", 'Å'),
+    ("AE", 'Æ'),
+    ("C,", 'Ç'),
+    ("E!", 'È'),
+    ("E'", 'É'),
+    ("E>", 'Ê'),
+    ("E:", 'Ë'),
+    ("I!", 'Ì'),
+    ("I'", 'Í'),
+    ("I>", 'Î'),
+    ("I:", 'Ï'),
+    ("D-", 'Ð'),
+    ("N?", 'Ñ'),
+    ("O!", 'Ò'),
+    ("O'", 'Ó'),
+    ("O>", 'Ô'),
+    ("O?", 'Õ'),
+    ("O:", 'Ö'),
+    ("O/", 'Ø'),
+    ("U!", 'Ù'),
+    ("U'", 'Ú'),
+    ("U>", 'Û'),
+    ("U:", 'Ü'),
+    ("Y'", 'Ý'),
+    ("TH", 'Þ'),
+    ("ss", 'ß'),
+    ("a!", 'à'),
+    ("a'", 'á'),
+    ("a>", 'â'),
+    ("a?", 'ã'),
+    ("a:", 'ä'),
+    ("aa", 'å'),
+    ("ae", 'æ'),
+    ("c,", 'ç'),
+    ("e!", 'è'),
+    ("e'", 'é'),
+    ("e>", 'ê'),
+    ("e:", 'ë'),
+    ("i!", 'ì'),
+    ("i'", 'í'),
+    ("i>", 'î'),
+    ("i:", 'ï'),
+    ("d-", 'ð'),
+    ("n?", 'ñ'),
+    ("o!", 'ò'),
+    ("o'", 'ó'),
+    ("o>", 'ô'),
+    ("o?", 'õ'),
+    ("o:", 'ö'),
+    ("o/", 'ø'),
+    ("u!", 'ù'),
+    ("u'", 'ú'),
+    ("u>", 'û'),
+    ("u:", 'ü'),
+    ("y'", 'ý'),
+    ("th", 'þ'),
+    ("y:", 'ÿ'),
+    // Punctuation and typography
+    ("!!", '¡'),
+    ("??", '¿'),
+    ("<<", '«'),
+    (">>", '»'),
+    ("'6", '\u{2018}'),
+    ("'9", '\u{2019}'),
+    ("\"6", '\u{201C}'),
+    ("\"9", '\u{201D}'),
+    ("-N", '\u{2013}'),
+    ("-M", '\u{2014}'),
+    ("-1", '\u{2010}'),
+    (".M", '·'),
+    ("SE", '§'),
+    ("PI", '¶'),
+    ("DG", '°'),
+    ("Co", '©'),
+    ("Rg", '®'),
+    ("TM", '™'),
+    ("NS", '\u{00A0}'),
+    ("-a", 'ª'),
+    ("-o", 'º'),
+    // Currency
+    ("Ct", '¢'),
+    ("Pd", '£'),
+    ("Ye", '¥'),
+    ("Cu", '¤'),
+    ("Eu", '€'),
+    ("=e", '€'),
+    // Fractions and superscripts
+    ("14", '¼'),
+    ("12", '½'),
+    ("34", '¾'),
+    ("1S", '¹'),
+    ("2S", '²'),
+    ("3S", '³'),
+    // Greek
+    ("A*", 'Α'),
+    ("B*", 'Β'),
+    ("G*", 'Γ'),
+    ("D*", 'Δ'),
+    ("E*", 'Ε'),
+    ("Z*", 'Ζ'),
+    ("Y*", 'Η'),
+    ("H*", 'Θ'),
+    ("I*", 'Ι'),
+    ("K*", 'Κ'),
+    ("L*", 'Λ'),
+    ("M*", 'Μ'),
+    ("N*", 'Ν'),
+    ("C*", 'Ξ'),
+    ("O*", 'Ο'),
+    ("P*", 'Π'),
+    ("R*", 'Ρ'),
+    ("S*", 'Σ'),
+    ("T*", 'Τ'),
+    ("U*", 'Υ'),
+    ("F*", 'Φ'),
+    ("X*", 'Χ'),
+    ("Q*", 'Ψ'),
+    ("W*", 'Ω'),
+    ("a*", 'α'),
+    ("b*", 'β'),
+    ("g*", 'γ'),
+    ("d*", 'δ'),
+    ("e*", 'ε'),
+    ("z*", 'ζ'),
+    ("y*", 'η'),
+    ("h*", 'θ'),
+    ("i*", 'ι'),
+    ("k*", 'κ'),
+    ("l*", 'λ'),
+    ("m*", 'μ'),
+    ("n*", 'ν'),
+    ("c*", 'ξ'),
+    ("o*", 'ο'),
+    ("p*", 'π'),
+    ("r*", 'ρ'),
+    ("s*", 'σ'),
+    ("t*", 'τ'),
+    ("u*", 'υ'),
+    ("f*", 'φ'),
+    ("x*", 'χ'),
+    ("q*", 'ψ'),
+    ("w*", 'ω'),
+    // Arrows
+    ("<-", '←'),
+    ("-!", '↑'),
+    ("->", '→'),
+    ("-v", '↓'),
+    ("<>", '↔'),
+    ("UD", '↕'),
+    ("<=", '⇐'),
+    ("=>", '⇒'),
+    ("==", '⇔'),
+    // Math
+    ("+-", '±'),
+    ("*X", '×'),
+    ("-:", '÷'),
+    ("My", 'µ'),
+    ("NO", '¬'),
+    ("00", '∞'),
+    ("RT", '√'),
+    ("dP", '∂'),
+    ("+Z", '∑'),
+    ("*P", '∏'),
+    ("In", '∫'),
+    ("!=", '≠'),
+    ("=<", '≤'),
+    (">=", '≥'),
+    ("?2", '≈'),
+    ("(-", '∈'),
+    ("-)", '∋'),
+    ("AN", '∧'),
+    ("OR", '∨'),
+    ("/0", '∅'),
+    ("OK", '✓'),
+    ("XX", '✗'),
+];
+
+/// Looks up `code` in the built-in table.
+pub fn lookup(code: &str) -> Option<char> {
+    DIGRAPHS
+        .iter()
+        .find(|(digraph, _)| *digraph == code)
+        .map(|&(_, ch)| ch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn common_digraphs_resolve() {
+        assert_eq!(lookup("a:"), Some('ä'));
+        assert_eq!(lookup("->"), Some('→'));
+        assert_eq!(lookup("Eu"), Some('€'));
+        assert_eq!(lookup("l*"), Some('λ'));
+        assert_eq!(lookup("zz"), None);
+    }
+
+    #[test]
+    fn codes_are_unique() {
+        let mut codes: Vec<_> = DIGRAPHS.iter().map(|(code, _)| code).collect();
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes.len(), DIGRAPHS.len());
+    }
+}
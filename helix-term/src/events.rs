@@ -13,7 +13,22 @@ events! {
     PostCommand<'a, 'cx> { command: & 'a MappableCommand, cx: &'a mut commands::Context<'cx> }
 }
 
+// In integration tests the registry is per tokio runtime, so every
+// application has to register the events on its own runtime.
+#[cfg(feature = "integration")]
 pub fn register() {
+    register_impl();
+}
+
+#[cfg(not(feature = "integration"))]
+pub fn register() {
+    // `handlers::setup` is called once per process, but tests construct
+    // multiple editors; registering an event twice panics.
+    static ONCE: std::sync::Once = std::sync::Once::new();
+    ONCE.call_once(register_impl);
+}
+
+fn register_impl() {
     register_event::<OnModeSwitch>();
     register_event::<PostInsertChar>();
     register_event::<PostCommand>();
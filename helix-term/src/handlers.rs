@@ -14,6 +14,7 @@ pub use helix_view::handlers::Handlers;
 
 mod auto_save;
 pub mod completion;
+mod copilot;
 mod diagnostics;
 mod signature_help;
 mod snippet;
@@ -32,6 +33,7 @@ pub fn setup(config: Arc<ArcSwap<Config>>) -> Handlers {
     };
 
     completion::register_hooks(&handlers);
+    copilot::register_hooks(&handlers);
     signature_help::register_hooks(&handlers);
     auto_save::register_hooks(&handlers);
     diagnostics::register_hooks(&handlers);
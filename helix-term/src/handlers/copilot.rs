@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+use helix_event::{register_hook, send_blocking, AsyncHook};
+use helix_view::events::DocumentFocusLost;
+use helix_view::handlers::Handlers;
+use tokio::time::Instant;
+
+use crate::job;
+
+/// Notifies the copilot agent which document is focused via
+/// `textDocument/didFocus`, debounced so rapid buffer cycling doesn't spam
+/// the agent.
+#[derive(Debug, Default)]
+pub(super) struct CopilotFocusHandler {}
+
+impl helix_event::AsyncHook for CopilotFocusHandler {
+    type Event = ();
+
+    fn handle_event(&mut self, _event: (), _: Option<Instant>) -> Option<Instant> {
+        Some(Instant::now() + Duration::from_millis(150))
+    }
+
+    fn finish_debounce(&mut self) {
+        job::dispatch_blocking(|editor, _| {
+            let doc = helix_view::doc!(editor);
+            let Some(copilot) = doc
+                .language_servers()
+                .find(|language_server| language_server.name() == "copilot")
+            else {
+                return;
+            };
+            if doc.copilot_excluded() {
+                return;
+            }
+            // Scratch buffers have no url; send a synthetic untitled uri so
+            // the agent still knows the previous document lost focus.
+            let uri = doc
+                .url()
+                .map(|url| url.to_string())
+                .unwrap_or_else(|| format!("untitled:{}", doc.id()));
+            tokio::spawn(copilot.copilot_did_focus(uri));
+        })
+    }
+}
+
+pub(super) fn register_hooks(_handlers: &Handlers) {
+    let tx = CopilotFocusHandler::default().spawn();
+    register_hook!(move |_event: &mut DocumentFocusLost<'_>| {
+        send_blocking(&tx, ());
+        Ok(())
+    });
+}
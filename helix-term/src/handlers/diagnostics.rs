@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use helix_event::{register_hook, send_blocking};
 use helix_view::document::Mode;
 use helix_view::events::DiagnosticsDidChange;
@@ -5,8 +7,25 @@ use helix_view::handlers::diagnostics::DiagnosticEvent;
 use helix_view::handlers::Handlers;
 
 use crate::events::OnModeSwitch;
+use crate::job;
+
+const WORKSPACE_DIAGNOSTIC_POLL_INTERVAL: Duration = Duration::from_secs(30);
 
 pub(super) fn register_hooks(_handlers: &Handlers) {
+    // Periodically re-pull workspace-level diagnostics while
+    // `lsp.workspace-diagnostic-poll` is enabled, so the workspace
+    // diagnostics picker stays current without a manual `:lsp-restart`.
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(WORKSPACE_DIAGNOSTIC_POLL_INTERVAL).await;
+            job::dispatch(|editor, _| {
+                if editor.config().lsp.workspace_diagnostic_poll {
+                    crate::commands::lsp::pull_workspace_diagnostics(editor);
+                }
+            })
+            .await;
+        }
+    });
     register_hook!(move |event: &mut DiagnosticsDidChange<'_>| {
         if event.editor.mode != Mode::Insert {
             for (view, _) in event.editor.tree.views_mut() {
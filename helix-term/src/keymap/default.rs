@@ -90,6 +90,8 @@ pub fn default() -> HashMap<Mode, KeyTrie> {
         "A-I" | "A-S-down" => select_all_children,
         "A-p" | "A-left" => select_prev_sibling,
         "A-n" | "A-right" => select_next_sibling,
+        "A-P" | "A-S-left" => swap_node_prev_sibling,
+        "A-N" | "A-S-right" => swap_node_next_sibling,
         "A-e" => move_parent_node_end,
         "A-b" => move_parent_node_start,
         "A-a" => select_all_siblings,
@@ -119,6 +121,7 @@ pub fn default() -> HashMap<Mode, KeyTrie> {
             "e" => goto_prev_entry,
             "T" => goto_prev_test,
             "p" => goto_prev_paragraph,
+            "q" => goto_prev_quickfix,
             "space" => add_newline_above,
         },
         "]" => { "Right bracket"
@@ -133,6 +136,7 @@ pub fn default() -> HashMap<Mode, KeyTrie> {
             "e" => goto_next_entry,
             "T" => goto_next_test,
             "p" => goto_next_paragraph,
+            "q" => goto_next_quickfix,
             "space" => add_newline_below,
         },
 
@@ -231,9 +235,11 @@ pub fn default() -> HashMap<Mode, KeyTrie> {
             "g" => changed_file_picker,
             "a" => code_action,
             "'" => last_picker,
+            "q" => quickfix_list,
             "G" => { "Debug (experimental)" sticky=true
                 "l" => dap_launch,
                 "r" => dap_restart,
+                "A-r" => dap_restart_frame,
                 "b" => dap_toggle_breakpoint,
                 "c" => dap_continue,
                 "h" => dap_pause,
@@ -334,6 +340,9 @@ pub fn default() -> HashMap<Mode, KeyTrie> {
 
         "C-a" => increment,
         "C-x" => decrement,
+
+        "F10" => dap_next_instruction,
+        "F11" => dap_step_in_instruction,
     });
     let mut select = normal.clone();
     select.merge_nodes(keymap!({ "Select mode"
@@ -381,7 +390,8 @@ pub fn default() -> HashMap<Mode, KeyTrie> {
         "C-w" | "A-backspace" => delete_word_backward,
         "A-d" | "A-del" => delete_word_forward,
         "C-u" => kill_to_line_start,
-        "C-k" => kill_to_line_end,
+        "C-k" => insert_digraph,
+        "C-S-u" => insert_unicode,
         "C-h" | "backspace" | "S-backspace" => delete_char_backward,
         "C-d" | "del" => delete_char_forward,
         "C-j" | "ret" => insert_newline,
@@ -142,6 +142,7 @@ pub struct Completion {
     #[allow(dead_code)]
     trigger_offset: usize,
     filter: String,
+    fuzzy_match: bool,
     resolve_handler: ResolveHandler,
 }
 
@@ -334,14 +335,22 @@ impl Completion {
             // TODO: expand nucleo api to allow moving straight to a Utf32String here
             // and avoid allocation during matching
             filter: String::from(fragment),
+            fuzzy_match: editor.config().completion_fuzzy_match,
             resolve_handler: ResolveHandler::new(),
         };
 
         // need to recompute immediately in case start_offset != trigger_offset
-        completion
-            .popup
-            .contents_mut()
-            .score(&completion.filter, false);
+        if completion.fuzzy_match {
+            completion
+                .popup
+                .contents_mut()
+                .score_fuzzy(&completion.filter);
+        } else {
+            completion
+                .popup
+                .contents_mut()
+                .score_prefix(&completion.filter, false);
+        }
 
         completion
     }
@@ -387,7 +396,11 @@ impl Completion {
                 }
             }
         }
-        menu.score(&self.filter, c.is_some());
+        if self.fuzzy_match {
+            menu.score_fuzzy(&self.filter);
+        } else {
+            menu.score_prefix(&self.filter, c.is_some());
+        }
     }
 
     pub fn is_empty(&self) -> bool {
@@ -1,9 +1,216 @@
-use helix_core::{Rope, Transaction};
+use std::fmt::Write;
 
-use crate::{
-    compositor::{Callback, Component, Context, Event, EventResult},
-    ctrl, key,
-};
+use helix_core::graphemes::grapheme_width;
+use helix_core::unicode::segmentation::UnicodeSegmentation;
+use helix_core::{Rope, RopeSlice, Transaction};
+use helix_view::input::KeyEvent;
+use helix_view::keyboard::{KeyCode, KeyModifiers};
+use helix_view::theme::{Modifier, Style, Theme};
+use helix_view::{Document, ViewId};
+
+use crate::compositor::{Callback, Component, Context, Event, EventResult};
+
+/// Style used to render a pending copilot suggestion as ghost text.
+///
+/// Themes define this as `ui.virtual.copilot`; for themes that don't, the
+/// lookup falls back to `ui.virtual.inline-hint` and finally `comment`.
+pub fn ghost_text_style(theme: &Theme) -> Style {
+    theme
+        .try_get_exact("ui.virtual.copilot")
+        .or_else(|| theme.try_get_exact("ui.virtual.inline-hint"))
+        .unwrap_or_else(|| theme.get("comment"))
+}
+
+/// Style for the span a partial accept would insert next (e.g. the next
+/// word). `ui.virtual.copilot.accept` is patched on top of the base ghost
+/// text style so themes only need to override what differs; without the key
+/// the span is simply emboldened.
+pub fn ghost_text_accept_style(theme: &Theme) -> Style {
+    let base = ghost_text_style(theme);
+    match theme.try_get_exact("ui.virtual.copilot.accept") {
+        Some(style) => base.patch(style),
+        None => base.add_modifier(Modifier::BOLD),
+    }
+}
+
+/// Lays out a pending suggestion as ghost text fitting a window of `width`
+/// columns by `max_lines` rows, scrolled `scroll` lines into the suggestion.
+///
+/// This defines the overflow behaviour rather than leaving it to the
+/// renderer: a line wider than the window is truncated with `…` (accepting
+/// still inserts the full text), and when more lines remain than fit, the
+/// last row becomes a `+N more lines` marker. `scroll` lets a key page
+/// through the clipped lines without moving the document; it is clamped so
+/// the window never runs past the suggestion.
+pub fn ghost_text_layout(
+    suggestion: &str,
+    width: usize,
+    max_lines: usize,
+    scroll: usize,
+) -> Vec<String> {
+    // Truncates to `width - 1` columns plus `…`, but only when the line
+    // genuinely overflows; a line of exactly `width` columns is kept whole.
+    fn truncated(line: &str, width: usize) -> String {
+        let mut cols = 0;
+        let mut cut = None;
+        for (idx, grapheme) in line.grapheme_indices(true) {
+            let next = cols + grapheme_width(grapheme);
+            if cut.is_none() && next > width.saturating_sub(1) {
+                cut = Some(idx);
+            }
+            if next > width {
+                let mut truncated = line[..cut.unwrap()].to_string();
+                truncated.push('…');
+                return truncated;
+            }
+            cols = next;
+        }
+        line.to_string()
+    }
+
+    if width == 0 || max_lines == 0 {
+        return Vec::new();
+    }
+
+    let lines: Vec<&str> = suggestion.lines().collect();
+    let scroll = scroll.min(lines.len().saturating_sub(max_lines));
+    let visible = &lines[scroll..];
+
+    if visible.len() > max_lines {
+        let shown = max_lines - 1;
+        let mut out: Vec<_> = visible[..shown]
+            .iter()
+            .map(|line| truncated(line, width))
+            .collect();
+        out.push(format!("+{} more lines", visible.len() - shown));
+        out
+    } else {
+        visible.iter().map(|line| truncated(line, width)).collect()
+    }
+}
+
+/// The text a suggestion transaction inserts, used to repeat an accepted
+/// suggestion at the remaining cursors.
+fn inserted_text(transaction: &Transaction) -> String {
+    transaction
+        .changes_iter()
+        .filter_map(|(_, _, text)| text)
+        .fold(String::new(), |mut acc, text| {
+            acc.push_str(&text);
+            acc
+        })
+}
+
+/// The 0-indexed lines a suggestion will occupy once previewed over
+/// `original`, used for the gutter markers and the line count in the
+/// suggestion indicator. `None` when the transaction inserts nothing.
+pub fn suggestion_line_range(
+    original: &Rope,
+    transaction: &Transaction,
+) -> Option<std::ops::Range<usize>> {
+    let pos = transaction
+        .changes_iter()
+        .find(|(_, _, text)| text.is_some())
+        .map(|(from, _, _)| from)?;
+    let inserted = inserted_text(transaction);
+    if inserted.is_empty() {
+        return None;
+    }
+    let start = original.char_to_line(pos);
+    // A trailing newline doesn't start a line of suggestion content.
+    let newlines = inserted.trim_end_matches('\n').matches('\n').count();
+    Some(start..start + newlines + 1)
+}
+
+/// The line content before `pos` with leading whitespace stripped, so
+/// cursors at different indentation levels still count as matching context.
+fn line_prefix(text: RopeSlice, pos: usize) -> String {
+    let line_start = text.line_to_char(text.char_to_line(pos));
+    let prefix: String = text.slice(line_start..pos).into();
+    prefix.trim_start().to_string()
+}
+
+/// Inserts an accepted suggestion at every cursor except the primary, which
+/// already contains it. Cursors whose line prefix differs from the text the
+/// primary's suggestion was completing are skipped, since the suggestion was
+/// produced for that context and would likely be nonsense elsewhere. Each
+/// remaining cursor ends up after its inserted text.
+pub fn apply_accept_at_all_cursors(doc: &mut Document, view_id: ViewId, inserted: &str) {
+    if inserted.is_empty() {
+        return;
+    }
+    let text = doc.text().clone();
+    let slice = text.slice(..);
+    let selection = doc.selection(view_id).clone();
+    if selection.len() <= 1 {
+        return;
+    }
+
+    // The primary cursor sits after the inserted text, so its context is the
+    // line prefix just before the insertion point.
+    let primary_cursor = selection.primary().cursor(slice);
+    let insertion_start = primary_cursor.saturating_sub(inserted.chars().count());
+    let primary_prefix = line_prefix(slice, insertion_start);
+
+    let primary_index = selection.primary_index();
+    let changes = selection.iter().enumerate().filter_map(|(i, range)| {
+        if i == primary_index {
+            return None;
+        }
+        let cursor = range.cursor(slice);
+        if line_prefix(slice, cursor) != primary_prefix {
+            return None;
+        }
+        Some((cursor, cursor, Some(inserted.into())))
+    });
+    let transaction = Transaction::change(&text, changes);
+    doc.apply(&transaction, view_id);
+}
+
+/// Appends a continuation response to the active suggestion: applies the
+/// first returned completion on top of the preview and folds the result into
+/// the picker's stored transaction. Responses for a document state other
+/// than the one previewed (the user typed meanwhile) and empty answers are
+/// dropped, leaving the original suggestion untouched.
+fn append_continuation(
+    editor: &mut helix_view::Editor,
+    compositor: &mut crate::compositor::Compositor,
+) {
+    let Some(state) = helix_view::doc!(editor).copilot_state.lock().take() else {
+        return;
+    };
+    let Some(picker) = compositor.find::<CopilotCompletionPicker>() else {
+        return;
+    };
+    let (view, doc) = current!(editor);
+    if doc.text() != &state.doc_at_req {
+        return;
+    }
+
+    let transactions = helix_lsp::util::generate_transactions_from_copilot_response(
+        doc.text(),
+        state.response,
+        state.offset_encoding,
+    );
+    let Some(continuation) = transactions.into_iter().next() else {
+        return;
+    };
+    if inserted_text(&continuation).is_empty() {
+        return;
+    }
+
+    doc.apply(&continuation, view.id);
+    doc.copilot_preview_lines = picker.extend_current(doc.text());
+}
+
+/// A transaction turning the live document text back into `original`.
+///
+/// Cancelling diffs against the document instead of inverting the stored
+/// suggestion transaction, since partial accepts can leave the document at a
+/// state no single stored transaction produced.
+fn restore_original(current: &Rope, original: &Rope) -> Transaction {
+    helix_core::diff::compare_ropes(current, original)
+}
 
 pub struct CopilotCompletionPicker {
     original: Rope,
@@ -13,6 +220,8 @@ pub struct CopilotCompletionPicker {
 }
 
 impl CopilotCompletionPicker {
+    pub const ID: &'static str = "copilot-picker";
+
     // need to return the state
     pub fn new(original: Rope, transactions: Vec<Transaction>) -> Option<(Self, Transaction)> {
         if transactions.is_empty() {
@@ -25,11 +234,21 @@ impl CopilotCompletionPicker {
                 original,
                 cur: 0,
                 transactions,
-                id: "copilot-picker",
+                id: Self::ID,
             },
             first,
         ))
     }
+
+    /// Tears the session down without going through the event loop: restores
+    /// the document to the text this picker was created with. Used when a
+    /// newer completion response replaces this session.
+    pub fn abort(&self, editor: &mut helix_view::Editor) {
+        let (view, doc) = current!(editor);
+        let restore = restore_original(doc.text(), &self.original);
+        doc.apply(&restore, view.id);
+        doc.copilot_preview_lines = None;
+    }
     // returns (prev_applied_transaction, next_transaction)
     pub fn next(&mut self) -> Option<(Transaction, Transaction)> {
         if self.cur == self.transactions.len() - 1 {
@@ -52,6 +271,32 @@ impl CopilotCompletionPicker {
             self.transactions[self.cur].clone(),
         ))
     }
+
+    /// Replaces the current suggestion with whatever `text` (the document
+    /// after a continuation was appended to the preview) reads relative to
+    /// `original`, so cycling and accepting operate on the combined text.
+    /// Returns the new preview line range.
+    pub fn extend_current(&mut self, text: &Rope) -> Option<std::ops::Range<usize>> {
+        let combined = helix_core::diff::compare_ropes(&self.original, text);
+        let lines = suggestion_line_range(&self.original, &combined);
+        self.transactions[self.cur] = combined;
+        lines
+    }
+
+    /// Jumps directly to the suggestion at `index` (0-based), clamped to the
+    /// available count. Returns `None` when already on that suggestion.
+    fn goto(&mut self, index: usize) -> Option<(Transaction, Transaction)> {
+        let index = index.min(self.transactions.len() - 1);
+        if index == self.cur {
+            return None;
+        }
+        let prev = self.cur;
+        self.cur = index;
+        Some((
+            self.transactions[prev].clone(),
+            self.transactions[self.cur].clone(),
+        ))
+    }
 }
 
 impl Component for CopilotCompletionPicker {
@@ -68,15 +313,22 @@ impl Component for CopilotCompletionPicker {
         Some(self.id)
     }
 
-    fn handle_event(&mut self, event: &Event, _: &mut Context) -> EventResult {
+    fn handle_event(&mut self, event: &Event, cx: &mut Context) -> EventResult {
         let key = match event {
             Event::Key(event) => *event,
             _ => return EventResult::Ignored(None),
         };
 
+        // The picker's bindings come from `[editor.copilot.keys]` rather
+        // than being hardcoded, so that e.g. terminals where a key collides
+        // with Enter can remap.
+        let keys = cx.editor.config().copilot.keys.clone();
+
         fn update_picker(
             transactions: Option<(Transaction, Transaction)>,
             original: &Rope,
+            // 1-based current suggestion and total count, shown as indicator
+            (current, total): (usize, usize),
         ) -> EventResult {
             match transactions {
                 None => EventResult::Consumed(None),
@@ -90,6 +342,15 @@ impl Component for CopilotCompletionPicker {
                         doc.apply(&invert, view.id);
 
                         doc.apply(&next, view.id);
+                        let lines = suggestion_line_range(&original, &next);
+                        let line_count = lines.as_ref().map_or(0, |lines| lines.len());
+                        doc.copilot_preview_lines = lines;
+
+                        let mut status = format!("copilot suggestion {}/{}", current, total);
+                        if line_count > 1 {
+                            write!(status, " (+{} lines)", line_count).unwrap();
+                        }
+                        context.editor.set_status(status);
                     });
 
                     EventResult::Consumed(Some(undo_then_apply))
@@ -98,26 +359,138 @@ impl Component for CopilotCompletionPicker {
         }
 
         match key {
-            ctrl!('g') => update_picker(self.next(), &self.original),
-            ctrl!('m') => update_picker(self.prev(), &self.original),
-            key!(Enter) => {
+            _ if key == keys.next => {
+                let transactions = self.next();
+                update_picker(
+                    transactions,
+                    &self.original,
+                    (self.cur + 1, self.transactions.len()),
+                )
+            }
+            _ if key == keys.prev => {
+                let transactions = self.prev();
+                update_picker(
+                    transactions,
+                    &self.original,
+                    (self.cur + 1, self.transactions.len()),
+                )
+            }
+            KeyEvent {
+                code: KeyCode::Char(ch @ '1'..='9'),
+                modifiers: KeyModifiers::ALT,
+            } => {
+                let transactions = self.goto((ch as u8 - b'1') as usize);
+                update_picker(
+                    transactions,
+                    &self.original,
+                    (self.cur + 1, self.transactions.len()),
+                )
+            }
+            _ if key == keys.accept => {
+                let id = self.id;
+                let inserted = inserted_text(&self.transactions[self.cur]);
+                let accept: Callback = Box::new(move |compositor, context| {
+                    compositor.remove(id);
+                    let multi_cursor = context.editor.config().copilot.multi_cursor;
+                    let (view, doc) = current!(context.editor);
+                    doc.copilot_preview_lines = None;
+                    if multi_cursor {
+                        apply_accept_at_all_cursors(doc, view.id, &inserted);
+                    }
+                    helix_view::copilot_stats::record_accepted_full();
+                });
+
+                EventResult::Consumed(Some(accept))
+            }
+            // Accept-and-next: keeps the previewed text like the accept key,
+            // then immediately requests a fresh completion at the new cursor
+            // position and reopens the session once suggestions come back.
+            _ if key == keys.accept_and_request => {
                 let id = self.id;
-                let remove_picker: Callback = Box::new(move |compositor, _| {
+                let inserted = inserted_text(&self.transactions[self.cur]);
+                let accept_then_request: Callback = Box::new(move |compositor, context| {
                     compositor.remove(id);
+                    let multi_cursor = context.editor.config().copilot.multi_cursor;
+                    let (view, doc) = current!(context.editor);
+                    doc.copilot_preview_lines = None;
+                    if multi_cursor {
+                        apply_accept_at_all_cursors(doc, view.id, &inserted);
+                    }
+                    helix_view::copilot_stats::record_accepted_full();
+
+                    // An empty acceptance leaves the document unchanged, so a
+                    // follow-up request would only hand back the same answer;
+                    // stopping here keeps a server that answers with nothing
+                    // from looping forever.
+                    if inserted.is_empty() {
+                        return;
+                    }
+                    *doc.copilot_state.lock() = None;
+                    doc.request_copilot_completion();
+
+                    // The response lands in `copilot_state` whenever the
+                    // agent answers; poll briefly rather than waiting forever
+                    // on a dead agent.
+                    let copilot_state = doc.copilot_state.clone();
+                    tokio::spawn(async move {
+                        for _ in 0..40 {
+                            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                            if copilot_state.lock().is_none() {
+                                continue;
+                            }
+                            crate::job::dispatch(|editor, compositor| {
+                                let state = helix_view::doc!(editor).copilot_state.lock().clone();
+                                if let Some(state) = state {
+                                    crate::commands::open_copilot_session(
+                                        compositor, editor, state,
+                                    );
+                                }
+                            })
+                            .await;
+                            return;
+                        }
+                    });
+                });
+
+                EventResult::Consumed(Some(accept_then_request))
+            }
+            // Extend: ask the agent to continue the current suggestion.
+            // The preview is already part of the document text, so a fresh
+            // request at the cursor (which sits at the end of the pending
+            // text) speculatively includes it; the answer is appended to the
+            // preview and folded into the stored transaction. If the request
+            // fails or comes back empty, the suggestion stays as it is.
+            _ if key == keys.extend => {
+                let request_continuation: Callback = Box::new(move |_, context| {
+                    let doc = helix_view::doc!(context.editor);
+                    *doc.copilot_state.lock() = None;
+                    doc.request_copilot_completion();
+
+                    let copilot_state = doc.copilot_state.clone();
+                    tokio::spawn(async move {
+                        for _ in 0..40 {
+                            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                            if copilot_state.lock().is_none() {
+                                continue;
+                            }
+                            crate::job::dispatch(append_continuation).await;
+                            return;
+                        }
+                    });
                 });
 
-                EventResult::Consumed(Some(remove_picker))
+                EventResult::Consumed(Some(request_continuation))
             }
-            key!(Esc) => {
-                let cur = self.transactions[self.cur].clone();
+            _ if key == keys.cancel => {
                 let id = self.id;
                 let original = self.original.clone();
 
                 let undo_remove_picker: Callback = Box::new(move |compositor, context| {
-                    // undo cur transaction
                     let (view, doc) = current!(context.editor);
-                    let invert = cur.invert(&original);
-                    doc.apply(&invert, view.id);
+                    let restore = restore_original(doc.text(), &original);
+                    doc.apply(&restore, view.id);
+                    doc.copilot_preview_lines = None;
+                    helix_view::copilot_stats::record_rejected();
 
                     compositor.remove(id);
                 });
@@ -129,3 +502,303 @@ impl Component for CopilotCompletionPicker {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helix_view::theme::Color;
+
+    fn parse_theme(source: &str) -> Theme {
+        Theme::from(toml::from_str::<toml::Value>(source).unwrap())
+    }
+
+    #[test]
+    fn ghost_text_style_prefers_copilot_key() {
+        let theme = parse_theme(
+            r##"
+            "comment" = "#111111"
+            "ui.virtual.inline-hint" = "#222222"
+            "ui.virtual.copilot" = "#333333"
+            "##,
+        );
+        assert_eq!(
+            ghost_text_style(&theme).fg,
+            Some(Color::Rgb(0x33, 0x33, 0x33))
+        );
+    }
+
+    #[test]
+    fn ghost_text_style_falls_back() {
+        let theme = parse_theme(r##""ui.virtual.inline-hint" = "#222222""##);
+        assert_eq!(
+            ghost_text_style(&theme).fg,
+            Some(Color::Rgb(0x22, 0x22, 0x22))
+        );
+
+        let theme = parse_theme(r##""comment" = "#111111""##);
+        assert_eq!(
+            ghost_text_style(&theme).fg,
+            Some(Color::Rgb(0x11, 0x11, 0x11))
+        );
+    }
+
+    #[test]
+    fn accept_style_patches_base() {
+        let theme = parse_theme(
+            r##"
+            "ui.virtual.copilot" = { fg = "#333333", modifiers = ["italic"] }
+            "ui.virtual.copilot.accept" = { fg = "#444444" }
+            "##,
+        );
+        let style = ghost_text_accept_style(&theme);
+        assert_eq!(style.fg, Some(Color::Rgb(0x44, 0x44, 0x44)));
+        assert!(style.add_modifier.contains(Modifier::ITALIC));
+    }
+
+    #[test]
+    fn suggestion_line_range_spans_inserted_lines() {
+        let original = Rope::from("fn main() {\n\n}\n");
+        let insert = |pos: usize, text: &str| {
+            Transaction::change(&original, std::iter::once((pos, pos, Some(text.into()))))
+        };
+
+        // single-line suggestion on line 1
+        assert_eq!(
+            suggestion_line_range(&original, &insert(12, "todo!();")),
+            Some(1..2)
+        );
+        // three lines of content; the trailing newline adds no line
+        assert_eq!(
+            suggestion_line_range(&original, &insert(12, "let a = 1;\nlet b = 2;\na + b\n")),
+            Some(1..4)
+        );
+        // a transaction that inserts nothing has no lines to mark
+        assert_eq!(
+            suggestion_line_range(&original, &Transaction::new(&original)),
+            None
+        );
+    }
+
+    #[test]
+    fn ghost_text_layout_truncates_wide_lines() {
+        // at 10 columns short lines pass through and exact fits stay whole
+        assert_eq!(ghost_text_layout("short", 10, 4, 0), vec!["short"]);
+        assert_eq!(ghost_text_layout("exactly10!", 10, 4, 0), vec!["exactly10!"]);
+        assert_eq!(
+            ghost_text_layout("this overflows", 10, 4, 0),
+            vec!["this over…"]
+        );
+        // width counts terminal columns, not bytes: CJK cells are two wide
+        assert_eq!(ghost_text_layout("日本語のテキスト", 6, 4, 0), vec!["日本…"]);
+        assert!(ghost_text_layout("anything", 0, 4, 0).is_empty());
+    }
+
+    #[test]
+    fn ghost_text_layout_clips_and_scrolls_vertically() {
+        let suggestion = "one\ntwo\nthree\nfour\nfive";
+
+        // the last row turns into a marker when the suggestion is clipped
+        assert_eq!(
+            ghost_text_layout(suggestion, 10, 3, 0),
+            vec!["one", "two", "+3 more lines"]
+        );
+        assert_eq!(
+            ghost_text_layout(suggestion, 10, 3, 1),
+            vec!["two", "three", "+2 more lines"]
+        );
+        // scrolling to the end shows a full window without a marker, and
+        // scrolling past it is clamped
+        assert_eq!(
+            ghost_text_layout(suggestion, 10, 3, 2),
+            vec!["three", "four", "five"]
+        );
+        assert_eq!(
+            ghost_text_layout(suggestion, 10, 3, 99),
+            vec!["three", "four", "five"]
+        );
+        assert!(ghost_text_layout(suggestion, 10, 0, 0).is_empty());
+    }
+
+    #[test]
+    fn direct_jumps_mix_with_next_and_prev() {
+        let original = Rope::from("\n");
+        let transactions: Vec<Transaction> = ["one", "two", "three"]
+            .iter()
+            .map(|text| {
+                Transaction::change(&original, std::iter::once((0, 0, Some((*text).into()))))
+            })
+            .collect();
+        let (mut picker, first) =
+            CopilotCompletionPicker::new(original, transactions.clone()).unwrap();
+        assert_eq!(first, transactions[0]);
+
+        // jump straight to the third suggestion
+        let (prev, next) = picker.goto(2).unwrap();
+        assert_eq!((prev, next), (transactions[0].clone(), transactions[2].clone()));
+        // already at the end, so cycling forward does nothing
+        assert!(picker.next().is_none());
+        let (prev, next) = picker.prev().unwrap();
+        assert_eq!((prev, next), (transactions[2].clone(), transactions[1].clone()));
+        // indices past the end clamp to the last suggestion
+        let (prev, next) = picker.goto(8).unwrap();
+        assert_eq!((prev, next), (transactions[1].clone(), transactions[2].clone()));
+        // jumping to the current suggestion is a no-op
+        assert!(picker.goto(2).is_none());
+    }
+
+    /// Opens a scratch document containing `text` with a cursor at each of
+    /// `cursors` (primary first) in a fresh dummy editor.
+    fn editor_with(text: &str, cursors: &[usize]) -> (helix_view::Editor, ViewId) {
+        use helix_core::{Range, Selection};
+
+        let mut editor = crate::compositor::Context::dummy_editor();
+        editor.new_file(helix_view::editor::Action::VerticalSplit);
+        let (view, doc) = current!(editor);
+        let view_id = view.id;
+        let transaction =
+            Transaction::change(doc.text(), [(0, 0, Some(text.into()))].into_iter());
+        doc.apply(&transaction, view_id);
+        let ranges = cursors.iter().map(|&pos| Range::point(pos)).collect();
+        doc.set_selection(view_id, Selection::new(ranges, 0));
+        (editor, view_id)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn multi_cursor_accept_completes_second_cursor() {
+        // The primary (first) cursor has already accepted "x = 1;".
+        let (mut editor, view_id) = editor_with("let x = 1;\nlet \n", &[10, 15]);
+        let doc = doc_mut!(editor);
+        apply_accept_at_all_cursors(doc, view_id, "x = 1;");
+
+        assert!(doc.text().to_string().starts_with("let x = 1;\nlet x = 1;\n"));
+        let slice = doc.text().slice(..);
+        assert_eq!(doc.selection(view_id).ranges()[1].cursor(slice), 21);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn multi_cursor_accept_completes_three_cursors() {
+        let (mut editor, view_id) = editor_with("let x = 1;\nlet \nlet \n", &[10, 15, 20]);
+        let doc = doc_mut!(editor);
+        apply_accept_at_all_cursors(doc, view_id, "x = 1;");
+
+        assert!(doc
+            .text()
+            .to_string()
+            .starts_with("let x = 1;\nlet x = 1;\nlet x = 1;\n"));
+        let slice = doc.text().slice(..);
+        let selection = doc.selection(view_id);
+        assert_eq!(selection.ranges()[1].cursor(slice), 21);
+        assert_eq!(selection.ranges()[2].cursor(slice), 32);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn multi_cursor_accept_skips_mismatched_context() {
+        let (mut editor, view_id) = editor_with("let x = 1;\nfoo \n", &[10, 15]);
+        let doc = doc_mut!(editor);
+        apply_accept_at_all_cursors(doc, view_id, "x = 1;");
+
+        // The second cursor's line prefix doesn't match the primary's, so
+        // nothing is inserted there.
+        assert!(doc.text().to_string().starts_with("let x = 1;\nfoo \n"));
+        let slice = doc.text().slice(..);
+        assert_eq!(doc.selection(view_id).ranges()[1].cursor(slice), 15);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn cancel_after_partial_accept_restores_original() {
+        let (mut editor, view_id) = editor_with("let \n", &[4]);
+
+        let original = doc!(editor).text().clone();
+        let suggestion =
+            Transaction::change(&original, std::iter::once((4, 4, Some("x = 1;".into()))));
+        let (picker, first) =
+            CopilotCompletionPicker::new(original.clone(), vec![suggestion]).unwrap();
+
+        let doc = doc_mut!(editor);
+        doc.apply(&first, view_id);
+        assert!(doc.text().to_string().starts_with("let x = 1;\n"));
+
+        // Simulate a partial word accept: part of the suggestion is kept as
+        // real text, so the document no longer equals original + suggestion.
+        // The first word "x" stays, the rest of the suggestion is dropped.
+        let mut partially_accepted = original.clone();
+        partially_accepted.insert(4, "x");
+        let partial = helix_core::diff::compare_ropes(doc.text(), &partially_accepted);
+        doc.apply(&partial, view_id);
+
+        // Cancelling still gets back to the exact original text.
+        picker.abort(&mut editor);
+        assert_eq!(doc!(editor).text(), &original);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn second_response_replaces_active_session() {
+        use crate::compositor::{Compositor, Context as CompositorContext};
+        use helix_lsp::copilot_types::CompletionResponse;
+        use helix_view::document::CopilotState;
+        use helix_view::graphics::Rect;
+
+        let mut editor = CompositorContext::dummy_editor();
+        let mut jobs = CompositorContext::dummy_jobs();
+        editor.new_file(helix_view::editor::Action::VerticalSplit);
+        let mut compositor = Compositor::new(Rect::new(0, 0, 80, 24));
+
+        let response = |text: &str| -> CompletionResponse {
+            serde_json::from_value(serde_json::json!({
+                "completions": [{
+                    "uuid": "0",
+                    "range": {
+                        "start": { "line": 0, "character": 0 },
+                        "end": { "line": 0, "character": 0 },
+                    },
+                    "displayText": text,
+                    "position": { "line": 0, "character": 0 },
+                    "text": text,
+                }]
+            }))
+            .unwrap()
+        };
+
+        let original = doc!(editor).text().clone();
+
+        // Fire two responses back-to-back, both requested at `original`.
+        for text in ["hello", "world"] {
+            let doc = doc!(editor);
+            *doc.copilot_state.lock() = Some(CopilotState {
+                response: response(text),
+                doc_at_req: original.clone(),
+                offset_encoding: helix_lsp::OffsetEncoding::Utf8,
+            });
+
+            let mut cx = crate::commands::Context {
+                register: None,
+                count: None,
+                editor: &mut editor,
+                callback: Vec::new(),
+                on_next_key_callback: None,
+                jobs: &mut jobs,
+            };
+            crate::commands::copilot_picker(&mut cx);
+            let callbacks = std::mem::take(&mut cx.callback);
+            drop(cx);
+
+            for callback in callbacks {
+                let mut cx = CompositorContext {
+                    editor: &mut editor,
+                    scroll: None,
+                    jobs: &mut jobs,
+                };
+                callback(&mut compositor, &mut cx);
+            }
+        }
+
+        // Exactly one picker remains and the document previews only the
+        // second response's suggestion.
+        assert!(compositor.remove(CopilotCompletionPicker::ID).is_some());
+        assert!(compositor.remove(CopilotCompletionPicker::ID).is_none());
+
+        let mut expected = original;
+        expected.insert(0, "world");
+        assert_eq!(doc!(editor).text(), &expected);
+    }
+}
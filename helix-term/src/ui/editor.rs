@@ -17,8 +17,10 @@ use helix_core::{
     diagnostic::NumberOrString,
     graphemes::{next_grapheme_boundary, prev_grapheme_boundary},
     movement::Direction,
+    pos_at_coords,
     syntax::{self, HighlightEvent},
     text_annotations::TextAnnotations,
+    textobject,
     unicode::width::UnicodeWidthStr,
     visual_offset_from_block, Change, Position, Range, Selection, Transaction,
 };
@@ -45,6 +47,10 @@ pub struct EditorView {
     /// Tracks if the terminal window is focused by reaction to terminal focus events
     terminal_focused: bool,
     pub(crate) explorer: Option<Explorer>,
+    /// Time and position of the last left click, for double click detection
+    last_left_click: Option<(std::time::Instant, usize)>,
+    /// Cache for the `syntax-breadcrumbs` statusline element
+    breadcrumbs: statusline::BreadcrumbsCache,
 }
 
 #[derive(Debug, Clone)]
@@ -69,6 +75,8 @@ impl EditorView {
             spinners: ProgressSpinners::default(),
             terminal_focused: true,
             explorer: None,
+            last_left_click: None,
+            breadcrumbs: statusline::BreadcrumbsCache::default(),
         }
     }
 
@@ -237,8 +245,14 @@ impl EditorView {
             .clip_top(view.area.height.saturating_sub(1))
             .clip_bottom(1); // -1 from bottom to remove commandline
 
-        let mut context =
-            statusline::RenderContext::new(editor, doc, view, is_focused, &self.spinners);
+        let mut context = statusline::RenderContext::new(
+            editor,
+            doc,
+            view,
+            is_focused,
+            &self.spinners,
+            &self.breadcrumbs,
+        );
 
         statusline::render(&mut context, statusline_area, surface);
     }
@@ -564,6 +578,19 @@ impl EditorView {
             }
         }
 
+        // The virtual cursor is drawn in the same pass as the real cursors:
+        // a preview highlight that doesn't move the document cursor, see
+        // [`View::virtual_cursor`].
+        if let Some(position) = view.virtual_cursor {
+            if let Some(scope) = theme.find_scope_index_exact("ui.cursor.virtual") {
+                let pos = pos_at_coords(text, position, true);
+                let end = next_grapheme_boundary(text, pos);
+                if pos < end {
+                    spans.push((scope, pos..end));
+                }
+            }
+        }
+
         spans
     }
 
@@ -1176,12 +1203,33 @@ impl EditorView {
                 let editor = &mut cxt.editor;
 
                 if let Some((pos, view_id)) = pos_and_view(editor, row, column, true) {
+                    if !config.mouse.click_to_move_cursor {
+                        return EventResult::Ignored(None);
+                    }
+
+                    let now = std::time::Instant::now();
+                    let double_click = config.mouse.double_click_word_select
+                        && modifiers.is_empty()
+                        && self.last_left_click.is_some_and(|(when, at)| {
+                            at == pos && now.duration_since(when).as_millis() < 500
+                        });
+                    self.last_left_click = Some((now, pos));
+
                     let prev_view_id = view!(editor).id;
                     let doc = doc_mut!(editor, &view!(editor, view_id).doc);
 
                     if modifiers == KeyModifiers::ALT {
                         let selection = doc.selection(view_id).clone();
                         doc.set_selection(view_id, selection.push(Range::point(pos)));
+                    } else if double_click {
+                        let word = textobject::textobject_word(
+                            doc.text().slice(..),
+                            Range::point(pos),
+                            textobject::TextObject::Inside,
+                            1,
+                            false,
+                        );
+                        doc.set_selection(view_id, Selection::single(word.anchor, word.head));
                     } else if editor.mode == Mode::Select {
                         // Discards non-primary selections for consistent UX with normal mode
                         let primary = doc.selection(view_id).primary().put_cursor(
@@ -1228,6 +1276,10 @@ impl EditorView {
             }
 
             MouseEventKind::Drag(MouseButton::Left) => {
+                if !config.mouse.drag_to_select {
+                    return EventResult::Ignored(None);
+                }
+
                 let (view, doc) = current!(cxt.editor);
 
                 let pos = match view.pos_at_screen_coords(doc, row, column, true) {
@@ -1245,6 +1297,10 @@ impl EditorView {
             }
 
             MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
+                if !config.mouse.scroll {
+                    return EventResult::Ignored(None);
+                }
+
                 let current_view = cxt.editor.tree.focus;
 
                 let direction = match event.kind {
@@ -1422,6 +1478,9 @@ impl Component for EditorView {
                 // clear status
                 cx.editor.status_msg = None;
 
+                // Virtual cursor previews are only valid until the next key.
+                view_mut!(cx.editor).virtual_cursor = None;
+
                 let mode = cx.editor.mode();
 
                 if !self.on_next_key(OnKeyCallbackKind::PseudoPending, &mut cx, key) {
@@ -1541,6 +1600,7 @@ impl Component for EditorView {
     fn render(&mut self, area: Rect, surface: &mut Surface, cx: &mut Context) {
         // clear with background color
         surface.set_style(area, cx.editor.theme.get("ui.background"));
+        cx.editor.resolve_annotation_highlights();
         let config = cx.editor.config();
 
         let editor_area = area.clip_bottom(1);
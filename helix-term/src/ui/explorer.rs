@@ -1,21 +1,28 @@
+use super::image_preview::{self, BlockImage, ImageFormat, ImageInfo};
+use super::tree::ItemDecoration;
 use super::{Prompt, TreeOp, TreeView, TreeViewItem};
 use crate::{
-    compositor::{Component, Context, EventResult},
+    alt,
+    compositor::{Component, Compositor, Context, EventResult},
     ctrl, key, shift, ui,
 };
 use anyhow::{bail, ensure, Result};
-use helix_core::Position;
+use helix_core::{coords_at_pos, pos_at_coords, Position, Selection};
 use helix_stdx::path;
+use helix_vcs::FileChange;
 use helix_view::{
-    editor::{Action, ExplorerPosition},
-    graphics::{CursorKind, Rect},
+    align_view,
+    editor::{Action, ExplorerPosition, ExplorerRootDisplay},
+    graphics::{CursorKind, Rect, UnderlineStyle},
     info::Info,
     input::{Event, KeyEvent},
-    theme::Modifier,
-    Editor,
+    theme::{Modifier, Style, Theme},
+    Align, DocumentId, Editor,
 };
 use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use std::{borrow::Cow, fs::DirEntry};
 use tui::{
     buffer::Buffer as Surface,
@@ -33,19 +40,29 @@ enum FileType {
 struct FileInfo {
     file_type: FileType,
     path: PathBuf,
+    /// How the path is rendered; only consulted for the root entry.
+    root_display: ExplorerRootDisplay,
 }
 
 impl FileInfo {
-    fn root(path: PathBuf) -> Self {
+    fn root(path: PathBuf, root_display: ExplorerRootDisplay) -> Self {
         Self {
             file_type: FileType::Root,
             path,
+            root_display,
         }
     }
 
     fn get_text(&self) -> Cow<'static, str> {
         let text = match self.file_type {
-            FileType::Root => self.path.display().to_string(),
+            FileType::Root => match self.root_display {
+                ExplorerRootDisplay::Absolute => self.path.display().to_string(),
+                ExplorerRootDisplay::Tilde => path::fold_home_dir(&self.path).display().to_string(),
+                ExplorerRootDisplay::Basename => self.path.file_name().map_or_else(
+                    || self.path.display().to_string(),
+                    |name| name.to_string_lossy().into_owned(),
+                ),
+            },
             FileType::File | FileType::Folder => self
                 .path
                 .file_name()
@@ -59,6 +76,105 @@ impl FileInfo {
     }
 }
 
+/// Git working-tree status of an entry, rendered as a one-character marker
+/// behind the file name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GitStatus {
+    Modified,
+    Added,
+    Deleted,
+    Untracked,
+    /// Not reported by the current diff providers, but themable already so
+    /// themes don't need to change once a provider starts reporting it.
+    Ignored,
+}
+
+impl GitStatus {
+    fn marker(self) -> char {
+        match self {
+            Self::Modified => 'M',
+            Self::Added => 'A',
+            Self::Deleted => 'D',
+            Self::Untracked => '?',
+            Self::Ignored => '!',
+        }
+    }
+
+    /// Style from the dedicated theme key, falling back to the diff scopes
+    /// every theme already defines.
+    fn style(self, theme: &Theme) -> Style {
+        let (key, fallback) = match self {
+            Self::Modified => ("ui.explorer.git.modified", "diff.delta"),
+            Self::Added => ("ui.explorer.git.added", "diff.plus"),
+            Self::Deleted => ("ui.explorer.git.deleted", "diff.minus"),
+            Self::Untracked => ("ui.explorer.git.untracked", "diff.plus"),
+            Self::Ignored => ("ui.explorer.git.ignored", "comment"),
+        };
+        theme.try_get(key).unwrap_or_else(|| theme.get(fallback))
+    }
+}
+
+type GitStatusMap = Arc<Mutex<HashMap<PathBuf, GitStatus>>>;
+
+/// Paths of documents currently open in a buffer and the subset with
+/// unsaved changes, rebuilt once per frame in `render_tree` and shared with
+/// the tree's decoration hook.
+#[derive(Debug, Default)]
+struct BufferPaths {
+    open: HashSet<PathBuf>,
+    modified: HashSet<PathBuf>,
+}
+
+type BufferPathsMap = Arc<Mutex<BufferPaths>>;
+
+/// Fallback for languages without a `sibling-file-template` of their own.
+const DEFAULT_SIBLING_FILE_TEMPLATE: &str = "{stem}.test.{ext}";
+
+/// Files larger than this are skipped by "open all files in folder".
+const OPEN_FOLDER_SIZE_CAP: u64 = 1024 * 1024;
+
+/// "Open all files in folder" asks for confirmation above this many files.
+const OPEN_FOLDER_CONFIRM_THRESHOLD: usize = 32;
+
+/// Maximum number of entries shown in the "Recent" section.
+const RECENT_MAX: usize = 5;
+
+/// The name style for files open in a buffer: the theme key when defined,
+/// otherwise a plain underline so the indicator works with every theme.
+fn buffer_indicator_style(theme: &Theme, key: &str) -> Style {
+    theme
+        .try_get(key)
+        .unwrap_or_else(|| Style::default().underline_style(UnderlineStyle::Line))
+}
+
+/// How the explorer orders siblings, switched at runtime via
+/// `:explorer-sort`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExplorerSort {
+    /// Folders before files, each group alphabetically (the default).
+    #[default]
+    DirsFirst,
+    /// Purely alphabetical, mixing files and folders.
+    Name,
+    /// Folders before files, each group in reverse alphabetical order.
+    Reversed,
+}
+
+impl std::str::FromStr for ExplorerSort {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "dirs-first" => Ok(Self::DirsFirst),
+            "name" => Ok(Self::Name),
+            "reversed" => Ok(Self::Reversed),
+            _ => Err(anyhow::anyhow!(
+                "unknown sort mode '{s}', expected one of 'dirs-first', 'name' or 'reversed'"
+            )),
+        }
+    }
+}
+
 impl PartialOrd for FileInfo {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -120,6 +236,7 @@ fn dir_entry_to_file_info(entry: DirEntry, path: &Path) -> Option<FileInfo> {
         FileInfo {
             file_type,
             path: path.join(entry.file_name()),
+            root_display: ExplorerRootDisplay::default(),
         }
     })
 }
@@ -127,9 +244,13 @@ fn dir_entry_to_file_info(entry: DirEntry, path: &Path) -> Option<FileInfo> {
 #[derive(Clone, Debug)]
 enum PromptAction {
     CreateFileOrFolder,
+    /// Create a file from the sibling template and open it on confirmation.
+    CreateSiblingFile,
     RemoveFolder,
     RemoveFile,
     RenameFile,
+    /// Open the contained files as background buffers on confirmation.
+    OpenFolderFiles(Vec<PathBuf>),
 }
 
 #[derive(Clone, Debug, Default)]
@@ -138,6 +259,17 @@ struct State {
     open: bool,
     current_root: PathBuf,
     area_width: u16,
+    /// The transient buffer opened by `explorer.preview-open` navigation,
+    /// discarded again when the cursor moves on to another file. `None` once
+    /// the preview is made permanent with Enter.
+    preview_doc: Option<DocumentId>,
+    /// Zero-based position the next activated file should land on instead of
+    /// the top, e.g. the match position when jumping from a content search.
+    /// Consumed by the activation.
+    open_target: Option<Position>,
+    /// Open/modified buffer lookup for the decoration hook, see
+    /// [`BufferPaths`].
+    buffer_paths: BufferPathsMap,
 }
 
 impl State {
@@ -147,6 +279,9 @@ impl State {
             current_root,
             open: true,
             area_width: 0,
+            preview_doc: None,
+            open_target: None,
+            buffer_paths: BufferPathsMap::default(),
         }
     }
 }
@@ -165,6 +300,36 @@ pub struct Explorer {
     #[allow(clippy::type_complexity)]
     on_next_key: Option<Box<dyn FnMut(&mut Context, &mut Self, &KeyEvent) -> EventResult>>,
     column_width: u16,
+    supports_kitty_graphics: bool,
+    /// Half-block renderings keyed by path and preview area size, so images
+    /// aren't redecoded on every frame.
+    preview_cache: HashMap<(PathBuf, u16, u16), Option<BlockImage>>,
+    /// Whether a Kitty graphics image is currently on screen. Kitty images
+    /// persist independently of cell redraws, so they have to be deleted
+    /// explicitly once a frame no longer shows the preview.
+    kitty_image_visible: bool,
+    /// Git statuses by path, shared with the tree's decoration hook and
+    /// filled in by a background scan.
+    git_status: GitStatusMap,
+    /// Entries marked with `m`, consumed by the name swap (`S`).
+    marked: Vec<PathBuf>,
+    /// Maximum number of roots kept in `history`, from
+    /// `explorer.root-history-size`.
+    root_history_size: usize,
+    /// How the root path is rendered in the title, initially from
+    /// `explorer.root-display` and cycled with `~`.
+    root_display: ExplorerRootDisplay,
+    /// The focused document and cursor position when the explorer was last
+    /// focused, used by `gd` to run goto-definition from that spot.
+    last_editor_position: Option<(DocumentId, Position)>,
+    /// Paths shown in the synthetic "Recent" section above the tree, most
+    /// recently focused first. Refreshed from the open documents on every
+    /// render while `explorer.show-recent` is on.
+    recent: Vec<PathBuf>,
+    /// Index into `recent` while the Recent section holds the focus instead
+    /// of the tree. The section does not participate in create, rename or
+    /// delete.
+    recent_selection: Option<usize>,
 }
 
 impl Explorer {
@@ -172,49 +337,155 @@ impl Explorer {
         let current_root = std::env::current_dir()
             .unwrap_or_else(|_| "./".into())
             .canonicalize()?;
-        Ok(Self {
-            tree: Self::new_tree_view(current_root.clone())?,
+        let git_status = GitStatusMap::default();
+        let root_display = cx.editor.config().explorer.root_display;
+        let state = State::new(true, current_root.clone());
+        let mut explorer = Self {
+            tree: Self::new_tree_view(current_root, root_display)?.with_decoration_fn(
+                Self::item_decoration_fn(git_status.clone(), state.buffer_paths.clone()),
+            ),
             history: vec![],
             show_help: false,
-            state: State::new(true, current_root),
+            state,
             prompt: None,
             on_next_key: None,
             column_width: cx.editor.config().explorer.column_width as u16,
-        })
+            supports_kitty_graphics: image_preview::terminal_supports_kitty_graphics(),
+            preview_cache: HashMap::new(),
+            kitty_image_visible: false,
+            git_status,
+            marked: Vec::new(),
+            // Every history entry holds a full tree, so cap even very
+            // generous configured values.
+            root_history_size: cx.editor.config().explorer.root_history_size.min(1000),
+            root_display,
+            last_editor_position: None,
+            recent: Vec::new(),
+            recent_selection: None,
+        };
+        explorer.record_editor_position(cx.editor);
+        explorer.refresh_git_status(cx.editor);
+        Ok(explorer)
     }
 
     #[cfg(test)]
     fn from_path(root: PathBuf, column_width: u16) -> Result<Self> {
         Ok(Self {
-            tree: Self::new_tree_view(root.clone())?,
+            tree: Self::new_tree_view(root.clone(), ExplorerRootDisplay::default())?,
             history: vec![],
             show_help: false,
             state: State::new(true, root),
             prompt: None,
             on_next_key: None,
             column_width,
+            supports_kitty_graphics: false,
+            preview_cache: HashMap::new(),
+            kitty_image_visible: false,
+            git_status: GitStatusMap::default(),
+            marked: Vec::new(),
+            root_history_size: 20,
+            root_display: ExplorerRootDisplay::default(),
+            last_editor_position: None,
+            recent: Vec::new(),
+            recent_selection: None,
         })
     }
 
-    fn new_tree_view(root: PathBuf) -> Result<TreeView<FileInfo>> {
-        let root = FileInfo::root(root);
+    fn new_tree_view(
+        root: PathBuf,
+        root_display: ExplorerRootDisplay,
+    ) -> Result<TreeView<FileInfo>> {
+        let root = FileInfo::root(root, root_display);
         Ok(TreeView::build_tree(root)?.with_enter_fn(Self::toggle_current))
     }
 
+    /// The decoration hook translating the shared git status map into
+    /// markers and, with `explorer.git.colorize-names` on, name tints, and
+    /// restyling the names of files that are open in a buffer.
+    fn item_decoration_fn(
+        git_status: GitStatusMap,
+        buffer_paths: BufferPathsMap,
+    ) -> impl Fn(&FileInfo, &Editor) -> Option<ItemDecoration> + 'static {
+        move |item, editor| {
+            let status = git_status.lock().unwrap().get(&item.path).copied();
+            let git_style = status.map(|status| status.style(&editor.theme));
+            let git_name_style = git_style.filter(|_| editor.config().explorer.git.colorize_names);
+
+            let buffer_style = {
+                let buffer_paths = buffer_paths.lock().unwrap();
+                if buffer_paths.modified.contains(&item.path) {
+                    Some(buffer_indicator_style(
+                        &editor.theme,
+                        "ui.explorer.buffer_modified",
+                    ))
+                } else if buffer_paths.open.contains(&item.path) {
+                    Some(buffer_indicator_style(
+                        &editor.theme,
+                        "ui.explorer.buffer_open",
+                    ))
+                } else {
+                    None
+                }
+            };
+            let name_style = match (git_name_style, buffer_style) {
+                (Some(git), Some(buffer)) => Some(git.patch(buffer)),
+                (git, buffer) => git.or(buffer),
+            };
+
+            match status.zip(git_style) {
+                Some((status, marker_style)) => Some(ItemDecoration {
+                    marker: status.marker(),
+                    marker_style,
+                    name_style,
+                }),
+                None => name_style.map(|name_style| ItemDecoration {
+                    marker: ' ',
+                    marker_style: Style::default(),
+                    name_style: Some(name_style),
+                }),
+            }
+        }
+    }
+
+    /// Repopulates the git status map in the background; markers appear as
+    /// results stream in on subsequent redraws.
+    fn refresh_git_status(&self, editor: &Editor) {
+        let statuses = self.git_status.clone();
+        statuses.lock().unwrap().clear();
+        editor.diff_providers.clone().for_each_changed_file(
+            self.state.current_root.clone(),
+            move |change| {
+                let Ok(change) = change else {
+                    return false;
+                };
+                let (path, status) = match change {
+                    FileChange::Untracked { path } => (path, GitStatus::Untracked),
+                    FileChange::Modified { path } => (path, GitStatus::Modified),
+                    FileChange::Conflict { path } => (path, GitStatus::Modified),
+                    FileChange::Deleted { path } => (path, GitStatus::Deleted),
+                    FileChange::Renamed { to_path, .. } => (to_path, GitStatus::Added),
+                };
+                statuses.lock().unwrap().insert(path, status);
+                true
+            },
+        );
+    }
+
     fn push_history(&mut self, tree_view: TreeView<FileInfo>, current_root: PathBuf) {
         self.history.push(ExplorerHistory {
             tree: tree_view,
             current_root,
         });
-        const MAX_HISTORY_SIZE: usize = 20;
-        Vec::truncate(&mut self.history, MAX_HISTORY_SIZE)
+        Vec::truncate(&mut self.history, self.root_history_size)
     }
 
     fn change_root(&mut self, root: PathBuf) -> Result<()> {
         if self.state.current_root.eq(&root) {
             return Ok(());
         }
-        let tree = Self::new_tree_view(root.clone())?;
+        let tree = Self::new_tree_view(root.clone(), self.root_display)?.with_decoration_fn(
+            Self::item_decoration_fn(self.git_status.clone(), self.state.buffer_paths.clone()),
+        );
         let old_tree = std::mem::replace(&mut self.tree, tree);
         self.push_history(old_tree, self.state.current_root.clone());
         self.state.current_root = root;
@@ -254,7 +525,7 @@ impl Explorer {
     }
 
     pub fn reveal_current_file(&mut self, cx: &mut Context) -> Result<()> {
-        self.focus();
+        self.focus(cx.editor);
         let current_document_path = doc!(cx.editor).path().cloned();
         match current_document_path {
             None => Ok(()),
@@ -262,9 +533,19 @@ impl Explorer {
         }
     }
 
-    pub fn focus(&mut self) {
+    pub fn focus(&mut self, editor: &Editor) {
         self.state.focus = true;
         self.state.open = true;
+        self.record_editor_position(editor);
+    }
+
+    /// Remembers where the editor cursor was so `gd` can run goto-definition
+    /// from that spot after opening a file.
+    fn record_editor_position(&mut self, editor: &Editor) {
+        let (view, doc) = current_ref!(editor);
+        let text = doc.text().slice(..);
+        let cursor = doc.selection(view.id).primary().cursor(text);
+        self.last_editor_position = Some((doc.id(), coords_at_pos(text, cursor)));
     }
 
     fn unfocus(&mut self) {
@@ -299,6 +580,115 @@ impl Explorer {
         Ok(())
     }
 
+    /// Like [`Self::new_create_file_or_folder_prompt`], but prefilled with
+    /// the last yanked path instead of the folder under the cursor, so a
+    /// sibling can be created by editing that path.
+    fn new_create_from_yanked_path_prompt(&mut self, cx: &mut Context) -> Result<()> {
+        let path = cx
+            .editor
+            .registers
+            .read('"', cx.editor)
+            .and_then(|mut values| values.next())
+            .map(|value| value.into_owned())
+            .ok_or_else(|| anyhow::anyhow!("No yanked path to paste"))?;
+        // Relative yanks are anchored to the current root, so the prompt
+        // always shows where the new entry would end up.
+        let path = PathBuf::from(path);
+        let path = if path.is_absolute() {
+            path
+        } else {
+            self.state.current_root.join(path)
+        };
+        self.prompt = Some((
+            PromptAction::CreateFileOrFolder,
+            Prompt::new(
+                format!(
+                    " New file or folder (ends with '{}'): ",
+                    std::path::MAIN_SEPARATOR
+                )
+                .into(),
+                None,
+                ui::completers::none,
+                |_, _, _| {},
+            )
+            .with_line(path.to_string_lossy().into_owned(), cx.editor),
+        ));
+        Ok(())
+    }
+
+    /// Like [`Self::new_create_file_or_folder_prompt`], but prefilled with a
+    /// sibling of the file under the cursor named from the language's
+    /// `sibling-file-template` (e.g. `foo.test.rs` next to `foo.rs`). The
+    /// created file is opened right away.
+    fn new_create_sibling_file_prompt(&mut self, cx: &mut Context) -> Result<()> {
+        let item = self.tree.current_item()?;
+        ensure!(
+            item.file_type == FileType::File,
+            "The item under the cursor is not a file"
+        );
+        let path = item.path.clone();
+        let template = cx
+            .editor
+            .syn_loader
+            .load()
+            .language_config_for_file_name(&path)
+            .and_then(|config| config.sibling_file_template.clone())
+            .unwrap_or_else(|| DEFAULT_SIBLING_FILE_TEMPLATE.to_string());
+
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let ext = path.extension().unwrap_or_default().to_string_lossy();
+        let mut name = template.replace("{stem}", &stem).replace("{ext}", &ext);
+        if ext.is_empty() {
+            // e.g. `Makefile` with the default template: drop the dangling dot
+            name = name.trim_end_matches('.').to_string();
+        }
+
+        let sibling = path.with_file_name(name);
+        self.prompt = Some((
+            PromptAction::CreateSiblingFile,
+            Prompt::new(
+                " New sibling file: ".into(),
+                None,
+                ui::completers::none,
+                |_, _, _| {},
+            )
+            .with_line(sibling.to_string_lossy().into_owned(), cx.editor),
+        ));
+        Ok(())
+    }
+
+    /// Yanks the absolute path of the file or folder under the cursor into
+    /// the default yank register.
+    fn yank_current_path(&mut self, cx: &mut Context) -> Result<()> {
+        let path = self.tree.current_item()?.path.to_string_lossy().into_owned();
+        cx.editor.registers.write('"', vec![path.clone()])?;
+        cx.editor.set_status(format!("Yanked {}", path));
+        Ok(())
+    }
+
+    /// Like [`Self::yank_current_path`], but relative to the current root.
+    fn yank_current_relative_path(&mut self, cx: &mut Context) -> Result<()> {
+        let item_path = &self.tree.current_item()?.path;
+        let path = item_path
+            .strip_prefix(&self.state.current_root)
+            .unwrap_or(item_path)
+            .to_string_lossy()
+            .into_owned();
+        cx.editor.registers.write('"', vec![path.clone()])?;
+        cx.editor.set_status(format!("Yanked {}", path));
+        Ok(())
+    }
+
+    /// Opens the OS file manager with the current item highlighted.
+    fn reveal_in_file_manager(&mut self, cx: &mut Context) -> Result<()> {
+        let path = self.tree.current_item()?.path.clone();
+        if let Err(err) = reveal_in_file_manager(&path) {
+            cx.editor
+                .set_error(format!("Failed to reveal {}: {}", path.display(), err));
+        }
+        Ok(())
+    }
+
     fn nearest_folder(&self) -> Result<PathBuf> {
         let current = self.tree.current()?.item();
         if current.is_parent() {
@@ -314,6 +704,51 @@ impl Explorer {
         }
     }
 
+    /// `b`: opens every file directly inside the current folder as a
+    /// background buffer ([`Action::Load`]), skipping binaries and files
+    /// over [`OPEN_FOLDER_SIZE_CAP`]. Above
+    /// [`OPEN_FOLDER_CONFIRM_THRESHOLD`] candidates, a confirmation prompt
+    /// guards against folders with thousands of files.
+    fn open_folder_files(&mut self, cx: &mut Context) -> Result<()> {
+        let folder = self.nearest_folder()?;
+        let files = folder_files_to_open(&folder)?;
+        ensure!(
+            !files.is_empty(),
+            "No files to open in '{}'",
+            folder.display()
+        );
+
+        if files.len() > OPEN_FOLDER_CONFIRM_THRESHOLD {
+            let message = format!(
+                " Open {} files from '{}'? y/N: ",
+                files.len(),
+                folder.display()
+            );
+            self.prompt = Some((
+                PromptAction::OpenFolderFiles(files),
+                Prompt::new(message.into(), None, ui::completers::none, |_, _, _| {}),
+            ));
+            return Ok(());
+        }
+
+        self.open_background_buffers(files, cx);
+        Ok(())
+    }
+
+    /// Opens `files` with [`Action::Load`] so the focused view stays put,
+    /// and reports how many of them made it into a buffer.
+    fn open_background_buffers(&mut self, files: Vec<PathBuf>, cx: &mut Context) {
+        let total = files.len();
+        let mut opened = 0;
+        for file in files {
+            if cx.editor.open(&file, Action::Load).is_ok() {
+                opened += 1;
+            }
+        }
+        cx.editor
+            .set_status(format!("Opened {} of {} files", opened, total));
+    }
+
     fn new_remove_prompt(&mut self) -> Result<()> {
         let item = self.tree.current()?.item();
         match item.file_type {
@@ -338,6 +773,33 @@ impl Explorer {
         Ok(())
     }
 
+    /// Like [`Self::new_rename_prompt`], but with the file stem selected so
+    /// retyping the name keeps the extension.
+    fn new_rename_stem_prompt(&mut self, cx: &mut Context) -> Result<()> {
+        let path = self.tree.current_item()?.path.clone();
+        let line = path.to_string_lossy().to_string();
+        let stem_range = path
+            .file_name()
+            .zip(path.file_stem())
+            .map(|(name, stem)| {
+                let start = line.len() - name.to_string_lossy().len();
+                start..start + stem.to_string_lossy().len()
+            })
+            .unwrap_or(0..0);
+        self.prompt = Some((
+            PromptAction::RenameFile,
+            Prompt::new(
+                " Rename to ".into(),
+                None,
+                ui::completers::none,
+                |_, _, _| {},
+            )
+            .with_line(line, cx.editor)
+            .with_selection(stem_range),
+        ));
+        Ok(())
+    }
+
     fn new_remove_file_prompt(&mut self) -> Result<()> {
         let item = self.tree.current_item()?;
         ensure!(
@@ -384,7 +846,9 @@ impl Explorer {
             }
             let meta = std::fs::metadata(&item.path)?;
             if meta.is_file() {
-                cx.editor.open(&item.path, Action::Replace)?;
+                Self::open_file(cx.editor, &item.path, state.open_target.take())?;
+                // Activating a previewed file makes its buffer permanent.
+                state.preview_doc = None;
                 state.focus = false;
                 return Ok(TreeOp::Noop);
             }
@@ -401,6 +865,263 @@ impl Explorer {
         })
     }
 
+    /// Opens `path` in the main area, landing on `target` (if given) rather
+    /// than wherever the buffer was last positioned.
+    fn open_file(editor: &mut Editor, path: &Path, target: Option<Position>) -> Result<()> {
+        editor.open(path, Action::Replace)?;
+        if let Some(target) = target {
+            let (view, doc) = current!(editor);
+            let text = doc.text().slice(..);
+            let pos = pos_at_coords(text, target, true);
+            doc.set_selection(view.id, Selection::point(pos));
+            align_view(doc, view, Align::Center);
+        }
+        Ok(())
+    }
+
+    /// Makes the next file activation land on the given zero-based position,
+    /// e.g. the match position when jumping in from a content search.
+    pub fn set_open_target(&mut self, position: Position) {
+        self.state.open_target = Some(position);
+    }
+
+    /// `gd`: opens the file under the cursor, then runs goto-definition from
+    /// the position the editor cursor had when the explorer was focused.
+    /// With that cursor on an import or call of a symbol defined in the
+    /// opened file, this lands straight on the definition.
+    fn goto_definition_from_explorer(&mut self, cx: &mut Context) -> Result<()> {
+        let item = self.tree.current_item()?;
+        ensure!(
+            item.path.is_file(),
+            "The item under the cursor is not a file"
+        );
+        let path = item.path.clone();
+        let (doc_id, position) = self
+            .last_editor_position
+            .filter(|(doc_id, _)| cx.editor.documents.contains_key(doc_id))
+            .ok_or_else(|| anyhow::anyhow!("No editor position to go to a definition from"))?;
+
+        cx.editor.open(&path, Action::Replace)?;
+
+        // Issue the request from the stored position; the response jumps
+        // back into the freshly opened buffer (or wherever the definition
+        // actually lives).
+        cx.editor.switch(doc_id, Action::Replace);
+        let (view, doc) = current!(cx.editor);
+        let text = doc.text().slice(..);
+        let pos = pos_at_coords(text, position, true);
+        doc.set_selection(view.id, Selection::point(pos));
+
+        let mut cx = crate::commands::Context {
+            register: None,
+            count: None,
+            editor: cx.editor,
+            callback: Vec::new(),
+            on_next_key_callback: None,
+            jobs: cx.jobs,
+        };
+        crate::commands::goto_definition(&mut cx);
+        self.state.focus = false;
+        Ok(())
+    }
+
+    /// `A-o` / `:explorer-find-file`: builds the fuzzy file picker scoped to
+    /// the explorer's current root instead of the project root. Picked files
+    /// are opened and then revealed in the tree, combining the picker's
+    /// quick navigation with the tree's visual context.
+    pub fn find_file_picker(&self, editor: &Editor, query: Option<&str>) -> Box<dyn Component> {
+        let mut picker = ui::file_picker_with(
+            self.state.current_root.clone(),
+            &editor.config(),
+            |cx, path| {
+                let path = path.to_path_buf();
+                let callback = async move {
+                    let call: crate::job::Callback = crate::job::Callback::EditorCompositor(
+                        Box::new(move |editor: &mut Editor, compositor: &mut Compositor| {
+                            let Some(explorer) = compositor
+                                .find::<ui::EditorView>()
+                                .and_then(|editor_view| editor_view.explorer.as_mut())
+                            else {
+                                return;
+                            };
+                            if let Err(err) = explorer.reveal_file(path) {
+                                editor.set_error(err.to_string());
+                            }
+                        }),
+                    );
+                    Ok(call)
+                };
+                cx.jobs.callback(callback);
+            },
+        );
+        if let Some(query) = query {
+            picker = picker.with_line(query.to_string(), editor);
+        }
+        Box::new(ui::overlay::overlaid(picker))
+    }
+
+    /// When `explorer.preview-open` is set, opens the file under the cursor
+    /// in the main area as a transient buffer. The buffer is discarded once
+    /// the cursor moves on to another file, unless it was activated with
+    /// Enter or modified in the meantime.
+    fn preview_current(&mut self, cx: &mut Context) {
+        if !cx.editor.config().explorer.preview_open || !self.is_focus() {
+            return;
+        }
+        let path = match self.tree.current_item() {
+            Ok(item) if item.path.is_file() => item.path.clone(),
+            _ => return,
+        };
+
+        // Files that are already open keep their regular buffer.
+        let already_open = cx.editor.document_by_path(&path).is_some();
+        let id = match cx.editor.open(&path, Action::Replace) {
+            Ok(id) => id,
+            Err(err) => {
+                cx.editor
+                    .set_error(format!("Unable to preview {}: {}", path.display(), err));
+                return;
+            }
+        };
+
+        let previous = self.state.preview_doc.take();
+        if !already_open {
+            self.state.preview_doc = Some(id);
+        }
+        if let Some(previous) = previous.filter(|&previous| previous != id) {
+            // Best effort: modified previews are kept around.
+            let _ = cx.editor.close_document(previous, false);
+        }
+    }
+
+    /// Rebuilds the "Recent" list from the open documents, most recently
+    /// focused first, skipping the currently focused one.
+    fn refresh_recent(&mut self, editor: &Editor) {
+        let current = view!(editor).doc;
+        let mut docs: Vec<_> = editor
+            .documents()
+            .filter(|doc| doc.id() != current)
+            .filter_map(|doc| doc.path().map(|path| (doc.focused_at, path.clone())))
+            .collect();
+        docs.sort_by(|a, b| b.0.cmp(&a.0));
+        self.recent = docs
+            .into_iter()
+            .take(RECENT_MAX)
+            .map(|(_, path)| path)
+            .collect();
+        match self.recent_selection {
+            Some(_) if self.recent.is_empty() => self.recent_selection = None,
+            Some(selection) => {
+                self.recent_selection = Some(selection.min(self.recent.len() - 1));
+            }
+            None => {}
+        }
+    }
+
+    /// `Ctrl-r`: moves the focus from the tree into the Recent section.
+    fn focus_recent(&mut self, cx: &mut Context) {
+        if !cx.editor.config().explorer.show_recent {
+            cx.editor.set_error("explorer.show-recent is disabled");
+            return;
+        }
+        self.refresh_recent(cx.editor);
+        if self.recent.is_empty() {
+            cx.editor.set_status("No recent files");
+            return;
+        }
+        self.recent_selection = Some(0);
+    }
+
+    /// Key handling while the Recent section holds the focus: plain list
+    /// navigation and activation; everything that acts on tree entries
+    /// (create, rename, delete, ...) is unavailable here.
+    fn handle_recent_event(&mut self, event: &KeyEvent, cx: &mut Context) -> EventResult {
+        let Some(selection) = self.recent_selection else {
+            return EventResult::Ignored(None);
+        };
+        match event {
+            key!('j') | key!(Down) | ctrl!('n') => {
+                self.recent_selection = Some((selection + 1).min(self.recent.len() - 1));
+            }
+            key!('k') | key!(Up) | ctrl!('p') => {
+                self.recent_selection = Some(selection.saturating_sub(1));
+            }
+            key!('o') | key!(Enter) => {
+                if let Some(path) = self.recent.get(selection).cloned() {
+                    match Self::open_file(cx.editor, &path, None) {
+                        Ok(()) => {
+                            // As with activating a tree entry, hand the
+                            // focus back to the editor.
+                            self.state.preview_doc = None;
+                            self.state.focus = false;
+                            self.recent_selection = None;
+                        }
+                        Err(err) => cx.editor.set_error(format!("{err}")),
+                    }
+                }
+            }
+            key!(Esc) | ctrl!('r') => self.recent_selection = None,
+            key!('q') => {
+                self.recent_selection = None;
+                self.close();
+            }
+            key!('?') => self.toggle_help(),
+            _ => {}
+        }
+        EventResult::Consumed(None)
+    }
+
+    /// Renders the Recent section at the top of `area` and returns what is
+    /// left for the tree. Skipped entirely when no other document is open.
+    fn render_recent(&mut self, area: Rect, surface: &mut Surface, cx: &mut Context) -> Rect {
+        self.refresh_recent(cx.editor);
+        // Leave at least half of the panel to the tree.
+        let max_entries = (area.height as usize / 2).saturating_sub(2);
+        let entries = self.recent.len().min(max_entries);
+        if entries == 0 {
+            self.recent_selection = None;
+            return area;
+        }
+        if let Some(selection) = self.recent_selection {
+            self.recent_selection = Some(selection.min(entries - 1));
+        }
+
+        let text_style = cx.editor.theme.get("ui.text");
+        let selected_style = cx
+            .editor
+            .theme
+            .try_get("ui.highlight")
+            .unwrap_or_else(|| cx.editor.theme.get("ui.selection"));
+        surface.set_stringn(area.x, area.y, " RECENT", area.width as usize, text_style);
+        for (i, path) in self.recent.iter().take(entries).enumerate() {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy())
+                .unwrap_or_default();
+            let style = if self.state.focus && self.recent_selection == Some(i) {
+                selected_style
+            } else {
+                text_style
+            };
+            surface.set_stringn(
+                area.x,
+                area.y + 1 + i as u16,
+                format!("  {}", name),
+                area.width as usize,
+                style,
+            );
+        }
+        // A rule keeping the section visually apart from the real root.
+        surface.set_stringn(
+            area.x,
+            area.y + 1 + entries as u16,
+            "─".repeat(area.width as usize),
+            area.width as usize,
+            cx.editor.theme.get("ui.window"),
+        );
+        area.clip_top(entries as u16 + 2)
+    }
+
     fn render_tree(
         &mut self,
         area: Rect,
@@ -408,6 +1129,22 @@ impl Explorer {
         surface: &mut Surface,
         cx: &mut Context,
     ) {
+        // Collect open/modified document paths once per frame so the
+        // decoration hook does set lookups per row instead of scanning all
+        // documents.
+        {
+            let mut buffer_paths = self.state.buffer_paths.lock().unwrap();
+            buffer_paths.open.clear();
+            buffer_paths.modified.clear();
+            for doc in cx.editor.documents() {
+                if let Some(path) = doc.path() {
+                    buffer_paths.open.insert(path.clone());
+                    if doc.is_modified() {
+                        buffer_paths.modified.insert(path.clone());
+                    }
+                }
+            }
+        }
         self.tree.render(area, prompt_area, surface, cx);
     }
 
@@ -419,6 +1156,7 @@ impl Explorer {
         position: &ExplorerPosition,
     ) {
         if !self.state.open {
+            self.clear_kitty_image();
             return;
         }
         let width = area.width.min(self.column_width + 2);
@@ -447,7 +1185,13 @@ impl Explorer {
                 render_block(side_area.clip_right(1), surface, Borders::LEFT).clip_bottom(1)
             }
         };
-        self.render_tree(list_area, prompt_area, surface, cx);
+        let tree_area = if cx.editor.config().explorer.show_recent {
+            self.render_recent(list_area, surface, cx)
+        } else {
+            self.recent_selection = None;
+            list_area
+        };
+        self.render_tree(tree_area, prompt_area, surface, cx);
 
         {
             let statusline = if self.is_focus() {
@@ -468,25 +1212,33 @@ impl Explorer {
             } else {
                 title_style
             };
-            surface.set_stringn(
-                area.x,
-                area.y,
-                if self.is_focus() {
-                    " EXPLORER: press ? for help"
-                } else {
-                    " EXPLORER"
-                },
-                area.width.into(),
-                title_style,
-            );
+            let title = if self.is_focus() {
+                match self.tree.search_match_status() {
+                    Some((current, total)) => format!(" EXPLORER: match {}/{}", current, total),
+                    None => " EXPLORER: press ? for help".to_string(),
+                }
+            } else {
+                " EXPLORER".to_string()
+            };
+            surface.set_stringn(area.x, area.y, title, area.width.into(), title_style);
         }
 
+        let mut kitty_image_drawn = false;
         if self.is_focus() && self.show_help {
             let help_area = match position {
                 ExplorerPosition::Left => area,
                 ExplorerPosition::Right => area.clip_right(list_area.width.saturating_add(2)),
             };
             self.render_help(help_area, surface, cx);
+        } else if self.is_focus() && cx.editor.config().explorer.image_preview {
+            let preview_area = match position {
+                ExplorerPosition::Left => area.clip_left(width),
+                ExplorerPosition::Right => area.clip_right(width),
+            };
+            kitty_image_drawn = self.render_preview(preview_area, surface, cx);
+        }
+        if !kitty_image_drawn {
+            self.clear_kitty_image();
         }
 
         if let Some((_, prompt)) = self.prompt.as_mut() {
@@ -494,17 +1246,135 @@ impl Explorer {
         }
     }
 
+    /// Deletes a previously transmitted Kitty image, if any. The terminal
+    /// keeps it painted across cell redraws, so this has to run whenever a
+    /// frame no longer renders the preview.
+    fn clear_kitty_image(&mut self) {
+        if self.kitty_image_visible {
+            let _ = image_preview::emit_kitty_delete();
+            self.kitty_image_visible = false;
+        }
+    }
+
+    /// Renders a preview for the focused file if it is an image.
+    ///
+    /// PNG files are displayed inline when the terminal supports the Kitty
+    /// graphics protocol; everything else falls back to file metadata.
+    /// Returns whether a Kitty image was transmitted, so the caller knows to
+    /// delete a stale one otherwise.
+    fn render_preview(&mut self, area: Rect, surface: &mut Surface, cx: &mut Context) -> bool {
+        let item = match self.tree.current_item() {
+            Ok(item) => item,
+            Err(_) => return false,
+        };
+        if item.file_type != FileType::File {
+            return false;
+        }
+        let format = match item
+            .path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(ImageFormat::from_extension)
+        {
+            Some(format) => format,
+            None => return false,
+        };
+        let data = match std::fs::read(&item.path) {
+            Ok(data) => data,
+            Err(_) => return false,
+        };
+
+        let width = area.width.min(42);
+        let height = area.height.min(12);
+        if width < 12 || height < 4 {
+            return false;
+        }
+        let area = Rect::new(area.x, area.y, width, height);
+        let background = cx.editor.theme.get("ui.background");
+        surface.clear_with(area, background);
+        let inner = render_block(area, surface, Borders::ALL);
+
+        if self.supports_kitty_graphics && format == ImageFormat::Png {
+            let sequence = image_preview::kitty_image_sequence(&data, inner.width, inner.height);
+            let _ = image_preview::emit_kitty_image_at(inner.x, inner.y, &sequence);
+            self.kitty_image_visible = true;
+            return true;
+        }
+
+        let item_text = item.get_text().to_string();
+        let path = item.path.clone();
+
+        // Tiny half-block rendering for terminals with truecolor support.
+        if cx.editor.config().true_color || crate::true_color() {
+            if self.preview_cache.len() > 64 {
+                self.preview_cache.clear();
+            }
+            let block = self
+                .preview_cache
+                .entry((path, inner.width, inner.height))
+                .or_insert_with(|| image_preview::render_blocks(&data, inner.width, inner.height));
+            if let Some(block) = block {
+                for row in 0..block.height {
+                    for column in 0..block.width {
+                        let (top, bottom) =
+                            block.cells[usize::from(row) * usize::from(block.width) + usize::from(column)];
+                        let style = helix_view::theme::Style::default().fg(top).bg(bottom);
+                        surface.set_stringn(inner.x + column, inner.y + row, "▀", 1, style);
+                    }
+                }
+                return false;
+            }
+        }
+
+        let info = ImageInfo::from_bytes(format, &data);
+        let text_style = cx.editor.theme.get("ui.text");
+        let mut lines = vec![
+            "<binary/image>".to_string(),
+            item_text,
+            format!("{} image, {}", info.format.as_str(), image_preview::format_size(info.size)),
+        ];
+        if let Some((image_width, image_height)) = info.dimensions {
+            lines.push(format!("{image_width} x {image_height} px"));
+        }
+        for (i, line) in lines.iter().enumerate() {
+            if i as u16 >= inner.height {
+                break;
+            }
+            surface.set_stringn(
+                inner.x,
+                inner.y + i as u16,
+                line,
+                inner.width as usize,
+                text_style,
+            );
+        }
+        false
+    }
+
     fn render_help(&mut self, area: Rect, surface: &mut Surface, cx: &mut Context) {
         Info::new(
             "Explorer",
             &[
                 ("?", "Toggle help"),
                 ("a", "Add file/folder"),
+                ("A", "Add sibling file from template and open it"),
                 ("r", "Rename file/folder"),
+                ("R", "Rename file keeping extension"),
                 ("d", "Delete file"),
+                ("y", "Yank path"),
+                ("Y", "Yank path relative to root"),
+                ("p", "Add file/folder from yanked path"),
+                ("O", "Reveal in file manager"),
+                ("gd", "Open file, then goto definition at last cursor"),
+                ("Alt-o", "Fuzzy find file under the explorer root"),
+                ("b", "Open all files in folder as buffers"),
+                ("m", "Mark entry for swap"),
+                ("S", "Swap the two marked entries' names"),
                 ("B", "Change root to parent folder"),
                 ("]", "Change root to current folder"),
                 ("[", "Go to previous root"),
+                ("~", "Cycle root path display"),
+                ("Ctrl-r", "Jump into the Recent section"),
                 ("+, =", "Increase size"),
                 ("-, _", "Decrease size"),
                 ("q", "Close"),
@@ -527,12 +1397,19 @@ impl Explorer {
             let current_item_path = self.tree.current_item()?.path.clone();
             match (&action, event) {
                 (PromptAction::CreateFileOrFolder, key!(Enter)) => {
-                    if line.ends_with(std::path::MAIN_SEPARATOR) {
+                    // On Windows both '/' and '\' are separators
+                    if line.ends_with(std::path::is_separator) {
                         self.new_folder(line)?
                     } else {
                         self.new_file(line)?
                     }
                 }
+                (PromptAction::CreateSiblingFile, key!(Enter)) => {
+                    self.new_file(line)?;
+                    let path = path::normalize(&PathBuf::from(line));
+                    cx.editor.open(&path, Action::Replace)?;
+                    self.state.focus = false;
+                }
                 (PromptAction::RemoveFolder, key) => {
                     if let key!('y') = key {
                         close_documents(current_item_path, cx)?;
@@ -545,6 +1422,11 @@ impl Explorer {
                         self.remove_file()?;
                     }
                 }
+                (PromptAction::OpenFolderFiles(files), key) => {
+                    if let key!('y') = key {
+                        self.open_background_buffers(files.clone(), cx);
+                    }
+                }
                 (PromptAction::RenameFile, key!(Enter)) => {
                     close_documents(current_item_path, cx)?;
                     self.rename_current(line)?;
@@ -612,6 +1494,28 @@ impl Explorer {
         self.state.open
     }
 
+    /// Switches the ordering of the tree, re-sorting the nodes that are
+    /// already loaded without re-reading the filesystem.
+    pub fn set_sort(&mut self, sort: ExplorerSort) {
+        match sort {
+            ExplorerSort::DirsFirst => self.tree.clear_comparator(),
+            ExplorerSort::Name => self.tree.set_comparator(|a: &FileInfo, b: &FileInfo| {
+                a.path.cmp(&b.path)
+            }),
+            ExplorerSort::Reversed => {
+                self.tree.set_comparator(|a: &FileInfo, b: &FileInfo| {
+                    // Siblings always share a parent, so only the kind and
+                    // the name matter here.
+                    match (a.file_type, b.file_type) {
+                        (FileType::Folder, FileType::File) => Ordering::Less,
+                        (FileType::File, FileType::Folder) => Ordering::Greater,
+                        _ => b.path.cmp(&a.path),
+                    }
+                })
+            }
+        }
+    }
+
     pub fn column_width(&self) -> u16 {
         self.column_width
     }
@@ -628,6 +1532,95 @@ impl Explorer {
         self.column_width = self.column_width.saturating_sub(1)
     }
 
+    /// Toggles a swap mark on the entry under the cursor. Marks feed
+    /// [`Self::swap_marked`].
+    fn toggle_mark(&mut self, cx: &mut Context) -> Result<()> {
+        let item = self.tree.current_item()?;
+        ensure!(item.path != Path::new(""), "Cannot mark the root");
+        let path = item.path.clone();
+        match self.marked.iter().position(|marked| marked == &path) {
+            Some(index) => {
+                self.marked.remove(index);
+            }
+            None => self.marked.push(path),
+        }
+        cx.editor
+            .set_status(format!("{} entries marked for swap", self.marked.len()));
+        Ok(())
+    }
+
+    /// Cycles how the root path is shown in the title:
+    /// absolute -> tilde -> basename.
+    fn toggle_root_display(&mut self, cx: &mut Context) {
+        self.root_display = match self.root_display {
+            ExplorerRootDisplay::Absolute => ExplorerRootDisplay::Tilde,
+            ExplorerRootDisplay::Tilde => ExplorerRootDisplay::Basename,
+            ExplorerRootDisplay::Basename => ExplorerRootDisplay::Absolute,
+        };
+        self.tree.root_item_mut().root_display = self.root_display;
+        cx.editor.set_status(format!(
+            "Explorer root shown as {}",
+            match self.root_display {
+                ExplorerRootDisplay::Absolute => "absolute path",
+                ExplorerRootDisplay::Tilde => "path with ~",
+                ExplorerRootDisplay::Basename => "basename",
+            }
+        ));
+    }
+
+    /// Swaps the names of the two marked entries via a unique temporary
+    /// name (A→tmp, B→A, tmp→B), rolling the earlier steps back when a
+    /// later one fails.
+    fn swap_marked(&mut self, cx: &mut Context) -> Result<()> {
+        ensure!(
+            self.marked.len() == 2,
+            "Mark exactly two entries to swap their names ({} marked)",
+            self.marked.len()
+        );
+        let a = self.marked[0].clone();
+        let b = self.marked[1].clone();
+        ensure!(a.exists(), "'{}' no longer exists", a.display());
+        ensure!(b.exists(), "'{}' no longer exists", b.display());
+
+        close_documents(a.clone(), cx)?;
+        close_documents(b.clone(), cx)?;
+
+        // A sibling of `a`, so the temporary name stays on one filesystem.
+        let tmp = (0..)
+            .map(|i| a.with_file_name(format!(".helix-swap-{}-{}", std::process::id(), i)))
+            .find(|candidate| !candidate.exists())
+            .expect("some candidate is unused");
+
+        std::fs::rename(&a, &tmp)?;
+        if let Err(err) = std::fs::rename(&b, &a) {
+            match std::fs::rename(&tmp, &a) {
+                Ok(()) => bail!("Swap failed, rolled back: {err}"),
+                Err(rollback_err) => bail!(
+                    "Swap failed ({err}) and rollback failed ({rollback_err}): '{}' is left at '{}'",
+                    a.display(),
+                    tmp.display()
+                ),
+            }
+        }
+        if let Err(err) = std::fs::rename(&tmp, &b) {
+            // Undo the second step, then the first.
+            match std::fs::rename(&a, &b).and_then(|_| std::fs::rename(&tmp, &a)) {
+                Ok(()) => bail!("Swap failed, rolled back: {err}"),
+                Err(rollback_err) => bail!(
+                    "Swap failed ({err}) and rollback failed ({rollback_err}): '{}' is left at '{}'",
+                    a.display(),
+                    tmp.display()
+                ),
+            }
+        }
+
+        self.marked.clear();
+        self.tree.refresh()?;
+        cx.editor
+            .set_status(format!("Swapped '{}' and '{}'", a.display(), b.display()));
+        Ok(())
+    }
+
     fn rename_current(&mut self, line: &String) -> Result<()> {
         let item = self.tree.current_item()?;
         let path = PathBuf::from(line);
@@ -652,6 +1645,37 @@ impl Explorer {
     }
 }
 
+/// Launches the platform file manager with `path` selected: `open -R` on
+/// macOS, `explorer /select,` on Windows. `xdg-open` has no way to highlight
+/// an entry, so elsewhere the containing directory is opened instead.
+fn reveal_in_file_manager(path: &Path) -> Result<()> {
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        let mut command = std::process::Command::new("open");
+        command.arg("-R").arg(path);
+        command
+    };
+    #[cfg(windows)]
+    let mut command = {
+        let mut command = std::process::Command::new("explorer");
+        command.arg(format!("/select,{}", path.display()));
+        command
+    };
+    #[cfg(not(any(target_os = "macos", windows)))]
+    let mut command = {
+        let directory = if path.is_dir() {
+            path
+        } else {
+            path.parent().unwrap_or(path)
+        };
+        let mut command = std::process::Command::new("xdg-open");
+        command.arg(directory);
+        command
+    };
+    command.spawn()?;
+    Ok(())
+}
+
 fn close_documents(current_item_path: PathBuf, cx: &mut Context) -> Result<()> {
     let ids = cx
         .editor
@@ -672,6 +1696,37 @@ fn close_documents(current_item_path: PathBuf, cx: &mut Context) -> Result<()> {
     Ok(())
 }
 
+/// The files directly inside `folder` that "open all files in folder"
+/// should load: regular files below [`OPEN_FOLDER_SIZE_CAP`] that don't
+/// look binary, in name order.
+fn folder_files_to_open(folder: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(folder)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() || entry.metadata()?.len() > OPEN_FOLDER_SIZE_CAP {
+            continue;
+        }
+        let path = entry.path();
+        if !is_binary(&path) {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Best-effort binary sniff: a NUL byte in the first kilobyte. Unreadable
+/// files count as binary so they are skipped rather than reported as errors.
+fn is_binary(path: &Path) -> bool {
+    use std::io::Read;
+
+    let mut buf = [0u8; 1024];
+    match std::fs::File::open(path).and_then(|mut file| file.read(&mut buf)) {
+        Ok(read) => buf[..read].contains(&0),
+        Err(_) => true,
+    }
+}
+
 impl Component for Explorer {
     /// Process input events, return true if handled.
     fn handle_event(&mut self, event: &Event, cx: &mut Context) -> EventResult {
@@ -694,22 +1749,73 @@ impl Component for Explorer {
             return EventResult::Consumed(c);
         }
 
+        if self.recent_selection.is_some() {
+            return self.handle_recent_event(key_event, cx);
+        }
+
+        // Handled outside the fallible block below: pushing the picker needs
+        // the compositor, which is only reachable through the returned
+        // callback.
+        if matches!(key_event, alt!('o')) {
+            let picker = self.find_file_picker(cx.editor, None);
+            return EventResult::Consumed(Some(Box::new(
+                move |compositor: &mut Compositor, _cx: &mut Context| compositor.push(picker),
+            )));
+        }
+
         (|| -> Result<()> {
             match key_event {
                 key!(Esc) => self.unfocus(),
                 key!('q') => self.close(),
                 key!('?') => self.toggle_help(),
                 key!('a') => self.new_create_file_or_folder_prompt(cx)?,
+                shift!('A') => self.new_create_sibling_file_prompt(cx)?,
                 shift!('B') => self.change_root_parent_folder()?,
                 key!(']') => self.change_root_to_current_folder()?,
                 key!('[') => self.go_to_previous_root(),
                 key!('d') => self.new_remove_prompt()?,
                 key!('r') => self.new_rename_prompt(cx)?,
+                shift!('R') => self.new_rename_stem_prompt(cx)?,
+                key!('y') => self.yank_current_path(cx)?,
+                shift!('Y') => self.yank_current_relative_path(cx)?,
+                key!('p') => self.new_create_from_yanked_path_prompt(cx)?,
+                shift!('O') => self.reveal_in_file_manager(cx)?,
+                key!('b') => self.open_folder_files(cx)?,
+                key!('m') => self.toggle_mark(cx)?,
+                shift!('S') => self.swap_marked(cx)?,
+                key!('~') => self.toggle_root_display(cx),
+                ctrl!('r') => self.focus_recent(cx),
+                key!('g') => {
+                    self.on_next_key = Some(Box::new(|cx, explorer, event| {
+                        match event {
+                            key!('d') => explorer
+                                .goto_definition_from_explorer(cx)
+                                .unwrap_or_else(|err| cx.editor.set_error(err.to_string())),
+                            _ => {
+                                // Replay `g` plus the pressed key so the
+                                // tree's own submap (gg, ge, gh, gl) keeps
+                                // working.
+                                explorer.tree.handle_event(
+                                    &Event::Key(key!('g')),
+                                    cx,
+                                    &mut explorer.state,
+                                );
+                                explorer.tree.handle_event(
+                                    &Event::Key(*event),
+                                    cx,
+                                    &mut explorer.state,
+                                );
+                            }
+                        }
+                        EventResult::Consumed(None)
+                    }));
+                }
                 key!('-') | key!('_') => self.decrease_size(),
                 key!('+') | key!('=') => self.increase_size(),
                 _ => {
                     self.tree
                         .handle_event(&Event::Key(*key_event), cx, &mut self.state);
+                    self.preview_current(cx);
                 }
             };
             Ok(())
@@ -1022,6 +2128,42 @@ mod test_explorer {
         );
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_rename_keeping_extension() {
+        let (path, mut explorer) = new_explorer();
+        let path_str = path.display().to_string();
+
+        // 0. Move cursor to "index.html" and open the stem rename prompt
+        explorer.handle_events("/index.html<ret><S-R>").unwrap();
+
+        // 0.1 Expect the full path prefilled with the stem selected
+        let prompt = &explorer.prompt.as_ref().unwrap().1;
+        assert_eq!(
+            prompt.line().replace(std::path::MAIN_SEPARATOR, "/"),
+            format!("{path_str}/index.html")
+        );
+
+        // 1. Retype the name; the typed text replaces only the stem
+        explorer.handle_events("main<ret>").unwrap();
+
+        // 1a. Expect the extension to be kept
+        assert_eq!(
+            render(&mut explorer),
+            format!(
+                "
+[{path_str}]
+⏵ scripts
+⏵ styles
+  .gitignore
+  (main.html)
+"
+            )
+            .trim()
+        );
+
+        assert!(path.join("main.html").exists());
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_new_folder() {
         let (path, mut explorer) = new_explorer();
@@ -1265,6 +2407,52 @@ mod test_explorer {
         assert!(fs::read_to_string(path.join("styles/foobar")).is_ok());
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_create_from_yanked_path() {
+        let (path, mut explorer) = new_explorer();
+
+        // 1. Yank the absolute path of "index.html", then open the create
+        //    prompt from it and append "2". The keys run in one batch so the
+        //    yank register survives until the paste.
+        explorer.handle_events("jjjjyp2<ret>").unwrap();
+
+        // 1a. Expect a sibling "index.html2" next to the yanked file
+        assert!(fs::read_to_string(path.join("index.html2")).is_ok());
+
+        // 2. The same flow from a relative yank: "Y" yanks "index.html2"
+        //    relative to the root, which the prompt anchors back to the root.
+        explorer.handle_events("<S-Y>p3<ret>").unwrap();
+
+        assert!(fs::read_to_string(path.join("index.html23")).is_ok());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_swap_marked_names() {
+        let (path, mut explorer) = new_explorer();
+
+        fs::write(path.join("index.html"), "index").unwrap();
+        fs::write(path.join(".gitignore"), "ignore").unwrap();
+
+        // 1. Mark ".gitignore" and "index.html", then swap their names.
+        explorer.handle_events("/.gitignore<ret>m").unwrap();
+        explorer.handle_events("/index.html<ret>m<S-S>").unwrap();
+
+        // 1a. Expect the contents changed places and the marks are consumed.
+        assert_eq!(
+            fs::read_to_string(path.join("index.html")).unwrap(),
+            "ignore"
+        );
+        assert_eq!(
+            fs::read_to_string(path.join(".gitignore")).unwrap(),
+            "index"
+        );
+        assert!(explorer.marked.is_empty());
+
+        // 2. Swapping with fewer than two marks is rejected and keeps the mark.
+        explorer.handle_events("m<S-S>").unwrap();
+        assert_eq!(explorer.marked.len(), 1);
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_remove_file() {
         let (path, mut explorer) = new_explorer();
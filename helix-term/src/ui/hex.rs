@@ -0,0 +1,360 @@
+//! An xxd-style hex view for binary files.
+//!
+//! The buffer content is the raw bytes of the file, not a decoded rope, so
+//! files that fail UTF-8 decoding can be inspected and patched byte by byte.
+//! Edits in the hex column change byte values a nibble at a time; edits in
+//! the ASCII column set bytes to the typed character.
+
+use std::path::{Path, PathBuf};
+
+use crate::compositor::{Callback, Component, Context, Event, EventResult};
+use crate::{ctrl, key, shift};
+use anyhow::{bail, Result};
+use helix_view::graphics::{CursorKind, Rect};
+use helix_view::input::KeyCode;
+use helix_view::theme::Modifier;
+use helix_view::Editor;
+use tui::buffer::Buffer as Surface;
+
+const BYTES_PER_LINE: usize = 16;
+// "00000000: " + 16 bytes as "xx" in 8 pairs of 2 + separator + ascii
+const OFFSET_WIDTH: u16 = 10;
+const HEX_WIDTH: u16 = (BYTES_PER_LINE * 2 + BYTES_PER_LINE / 2) as u16;
+const ASCII_START: u16 = OFFSET_WIDTH + HEX_WIDTH + 2;
+
+/// Whether file contents should be treated as binary: a null byte within the
+/// first kilobyte, or contents that are not valid UTF-8.
+pub fn is_binary(data: &[u8]) -> bool {
+    data.iter().take(1024).any(|&byte| byte == 0) || std::str::from_utf8(data).is_err()
+}
+
+/// The raw bytes shown and edited by the [`HexView`].
+pub struct HexDocument {
+    path: Option<PathBuf>,
+    bytes: Vec<u8>,
+    modified: bool,
+}
+
+impl HexDocument {
+    pub fn new(bytes: Vec<u8>, path: Option<PathBuf>) -> Self {
+        Self {
+            path,
+            bytes,
+            modified: false,
+        }
+    }
+
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        let bytes = std::fs::read(path)?;
+        Ok(Self::new(bytes, Some(path.to_path_buf())))
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub fn byte(&self, offset: usize) -> Option<u8> {
+        self.bytes.get(offset).copied()
+    }
+
+    pub fn set_byte(&mut self, offset: usize, value: u8) {
+        if let Some(byte) = self.bytes.get_mut(offset) {
+            if *byte != value {
+                *byte = value;
+                self.modified = true;
+            }
+        }
+    }
+
+    pub fn is_modified(&self) -> bool {
+        self.modified
+    }
+
+    pub fn save(&mut self) -> Result<()> {
+        let path = match &self.path {
+            Some(path) => path,
+            None => bail!("hex document has no associated path"),
+        };
+        std::fs::write(path, &self.bytes)?;
+        self.modified = false;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HexColumn {
+    Hex,
+    Ascii,
+}
+
+pub struct HexView {
+    document: HexDocument,
+    /// Byte offset of the cursor.
+    cursor: usize,
+    /// First visible line (of `BYTES_PER_LINE` bytes each).
+    scroll: usize,
+    column: HexColumn,
+    /// High nibble already typed in the hex column, waiting for the low one.
+    pending_nibble: Option<u8>,
+}
+
+impl HexView {
+    pub fn new(document: HexDocument) -> Self {
+        Self {
+            document,
+            cursor: 0,
+            scroll: 0,
+            column: HexColumn::Hex,
+            pending_nibble: None,
+        }
+    }
+
+    const ID: &'static str = "hex-view";
+
+    fn max_offset(&self) -> usize {
+        self.document.len().saturating_sub(1)
+    }
+
+    fn move_cursor(&mut self, offset: usize) {
+        self.cursor = offset.min(self.max_offset());
+        self.pending_nibble = None;
+    }
+
+    fn scroll_into_view(&mut self, height: usize) {
+        let line = self.cursor / BYTES_PER_LINE;
+        if line < self.scroll {
+            self.scroll = line;
+        } else if line >= self.scroll + height {
+            self.scroll = line + 1 - height;
+        }
+    }
+
+    fn insert_nibble(&mut self, nibble: u8) {
+        match self.pending_nibble.take() {
+            Some(high) => {
+                self.document.set_byte(self.cursor, (high << 4) | nibble);
+                self.move_cursor(self.cursor.saturating_add(1));
+            }
+            None => self.pending_nibble = Some(nibble),
+        }
+    }
+
+    fn insert_ascii(&mut self, c: char) {
+        if c.is_ascii() && !c.is_ascii_control() {
+            self.document.set_byte(self.cursor, c as u8);
+            self.move_cursor(self.cursor.saturating_add(1));
+        }
+    }
+}
+
+impl Component for HexView {
+    fn render(&mut self, area: Rect, surface: &mut Surface, cx: &mut Context) {
+        let theme = &cx.editor.theme;
+        let background = theme.get("ui.background");
+        let text_style = theme.get("ui.text");
+        let offset_style = theme.get("ui.linenr");
+        let cursor_style = theme.get("ui.cursor").add_modifier(Modifier::REVERSED);
+        let status_style = theme.get("ui.statusline");
+
+        surface.clear_with(area, background);
+        let height = area.height.saturating_sub(1) as usize;
+        self.scroll_into_view(height);
+
+        for row in 0..height {
+            let line = self.scroll + row;
+            let line_offset = line * BYTES_PER_LINE;
+            if line_offset >= self.document.len() && line_offset != 0 {
+                break;
+            }
+            let y = area.y + row as u16;
+
+            surface.set_stringn(
+                area.x,
+                y,
+                format!("{line_offset:08x}: "),
+                OFFSET_WIDTH as usize,
+                offset_style,
+            );
+
+            for i in 0..BYTES_PER_LINE {
+                let offset = line_offset + i;
+                let byte = match self.document.byte(offset) {
+                    Some(byte) => byte,
+                    None => break,
+                };
+                // Bytes are grouped in pairs, xxd-style.
+                let hex_x = area.x + OFFSET_WIDTH + (i * 2 + i / 2) as u16;
+                let is_cursor = offset == self.cursor;
+                let hex_style = if is_cursor && self.column == HexColumn::Hex {
+                    cursor_style
+                } else {
+                    text_style
+                };
+                let hex = match self.pending_nibble {
+                    Some(nibble) if is_cursor => format!("{nibble:x}_"),
+                    _ => format!("{byte:02x}"),
+                };
+                surface.set_stringn(hex_x, y, hex, 2, hex_style);
+
+                let ascii_style = if is_cursor && self.column == HexColumn::Ascii {
+                    cursor_style
+                } else {
+                    text_style
+                };
+                let preview = if byte.is_ascii_graphic() || byte == b' ' {
+                    byte as char
+                } else {
+                    '.'
+                };
+                surface.set_stringn(
+                    area.x + ASCII_START + i as u16,
+                    y,
+                    preview.to_string(),
+                    1,
+                    ascii_style,
+                );
+            }
+        }
+
+        let status_area = Rect::new(area.x, area.y + area.height.saturating_sub(1), area.width, 1);
+        surface.clear_with(status_area, status_style);
+        let path = self
+            .document
+            .path
+            .as_ref()
+            .map_or("[scratch]".to_string(), |path| {
+                path.to_string_lossy().to_string()
+            });
+        let status = format!(
+            " HEX {}{} {} bytes, offset {:#x} ({}) C-s: save, q: close, tab: switch column",
+            path,
+            if self.document.is_modified() { " [+]" } else { "" },
+            self.document.len(),
+            self.cursor,
+            match self.column {
+                HexColumn::Hex => "hex",
+                HexColumn::Ascii => "ascii",
+            },
+        );
+        surface.set_stringn(
+            status_area.x,
+            status_area.y,
+            status,
+            status_area.width as usize,
+            status_style,
+        );
+    }
+
+    fn handle_event(&mut self, event: &Event, cx: &mut Context) -> EventResult {
+        let key = match event {
+            Event::Key(key) => *key,
+            Event::Resize(..) => return EventResult::Consumed(None),
+            _ => return EventResult::Ignored(None),
+        };
+
+        let close: Callback = Box::new(|compositor, _| {
+            compositor.remove(Self::ID);
+        });
+
+        match key {
+            key!(Esc) | key!('q') => return EventResult::Consumed(Some(close)),
+            ctrl!('s') => {
+                if let Err(err) = self.document.save() {
+                    cx.editor.set_error(err.to_string());
+                } else {
+                    cx.editor.set_status("written");
+                }
+            }
+            key!(Tab) => {
+                self.column = match self.column {
+                    HexColumn::Hex => HexColumn::Ascii,
+                    HexColumn::Ascii => HexColumn::Hex,
+                };
+                self.pending_nibble = None;
+            }
+            key!(Left) | key!('h') if self.column == HexColumn::Hex => {
+                self.move_cursor(self.cursor.saturating_sub(1))
+            }
+            key!(Right) | key!('l') if self.column == HexColumn::Hex => {
+                self.move_cursor(self.cursor.saturating_add(1))
+            }
+            key!(Down) | key!('j') if self.column == HexColumn::Hex => {
+                self.move_cursor(self.cursor.saturating_add(BYTES_PER_LINE))
+            }
+            key!(Up) | key!('k') if self.column == HexColumn::Hex => {
+                self.move_cursor(self.cursor.saturating_sub(BYTES_PER_LINE))
+            }
+            key!(Left) => self.move_cursor(self.cursor.saturating_sub(1)),
+            key!(Right) => self.move_cursor(self.cursor.saturating_add(1)),
+            key!(Down) => self.move_cursor(self.cursor.saturating_add(BYTES_PER_LINE)),
+            key!(Up) => self.move_cursor(self.cursor.saturating_sub(BYTES_PER_LINE)),
+            key!(Home) => self.move_cursor(self.cursor - self.cursor % BYTES_PER_LINE),
+            key!(End) => {
+                self.move_cursor(self.cursor - self.cursor % BYTES_PER_LINE + BYTES_PER_LINE - 1)
+            }
+            key!('g') => self.move_cursor(0),
+            shift!('G') => self.move_cursor(self.max_offset()),
+            _ => match (self.column, key.code) {
+                (HexColumn::Hex, KeyCode::Char(c)) if c.is_ascii_hexdigit() => {
+                    self.insert_nibble(c.to_digit(16).unwrap() as u8)
+                }
+                (HexColumn::Ascii, KeyCode::Char(c)) => self.insert_ascii(c),
+                _ => return EventResult::Ignored(None),
+            },
+        }
+
+        EventResult::Consumed(None)
+    }
+
+    fn id(&self) -> Option<&'static str> {
+        Some(Self::ID)
+    }
+
+    fn cursor(&self, _area: Rect, _editor: &Editor) -> (Option<helix_core::Position>, CursorKind) {
+        (None, CursorKind::Hidden)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binary_detection() {
+        assert!(is_binary(b"\x00\x01\x02"));
+        assert!(is_binary(b"\xff\xfe invalid utf8"));
+        assert!(!is_binary(b"plain text\n"));
+    }
+
+    #[test]
+    fn nibble_edits_combine_into_bytes() {
+        let document = HexDocument::new(vec![0x00, 0x11], None);
+        let mut view = HexView::new(document);
+
+        view.insert_nibble(0xa);
+        assert!(view.document.byte(0) == Some(0x00) && view.pending_nibble == Some(0xa));
+        view.insert_nibble(0xb);
+        assert_eq!(view.document.byte(0), Some(0xab));
+        // the cursor advanced to the next byte
+        assert_eq!(view.cursor, 1);
+        assert!(view.document.is_modified());
+    }
+
+    #[test]
+    fn ascii_edits_set_bytes() {
+        let document = HexDocument::new(b"abc".to_vec(), None);
+        let mut view = HexView::new(document);
+        view.column = HexColumn::Ascii;
+
+        view.insert_ascii('X');
+        assert_eq!(view.document.byte(0), Some(b'X'));
+        assert_eq!(view.cursor, 1);
+        // control characters are rejected
+        view.insert_ascii('\x07');
+        assert_eq!(view.document.byte(1), Some(b'b'));
+    }
+}
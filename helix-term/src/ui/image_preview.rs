@@ -0,0 +1,249 @@
+//! Lightweight image previews for the Explorer.
+//!
+//! When the terminal implements the Kitty graphics protocol (detected via
+//! `$TERM` at startup), PNG files can be displayed inline by transmitting the
+//! file contents directly (the protocol's `f=100` format), so no image
+//! decoding is required. Terminals without graphics support, and formats the
+//! protocol cannot display directly (JPEG, GIF, SVG), fall back to showing
+//! file metadata. Dimensions are parsed from the image headers by hand to
+//! keep from pulling in an image crate for just that.
+
+use std::fmt::Write as _;
+use std::io::Write;
+
+use helix_view::base64;
+use helix_view::theme::Color;
+
+/// The Kitty graphics protocol caps escape payloads at 4096 bytes.
+const KITTY_CHUNK_SIZE: usize = 4096;
+
+/// The escape that deletes every image the terminal currently displays
+/// (the protocol's `a=d` action).
+const KITTY_DELETE_SEQUENCE: &str = "\x1b_Ga=d\x1b\\";
+
+/// Whether the terminal claims to support the Kitty graphics protocol.
+///
+/// Checked once at Explorer construction; `$TERM` is `xterm-kitty` under
+/// kitty itself and contains `kitty` for compatible terminals.
+pub fn terminal_supports_kitty_graphics() -> bool {
+    std::env::var("TERM").is_ok_and(|term| term.contains("kitty"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    Gif,
+    Svg,
+}
+
+impl ImageFormat {
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_lowercase().as_str() {
+            "png" => Some(Self::Png),
+            "jpg" | "jpeg" => Some(Self::Jpeg),
+            "gif" => Some(Self::Gif),
+            "svg" => Some(Self::Svg),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Png => "PNG",
+            Self::Jpeg => "JPEG",
+            Self::Gif => "GIF",
+            Self::Svg => "SVG",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ImageInfo {
+    pub format: ImageFormat,
+    /// File size in bytes.
+    pub size: u64,
+    /// Pixel dimensions, if they could be parsed from the header.
+    pub dimensions: Option<(u32, u32)>,
+}
+
+impl ImageInfo {
+    pub fn from_bytes(format: ImageFormat, data: &[u8]) -> Self {
+        let dimensions = match format {
+            ImageFormat::Png => png_dimensions(data),
+            ImageFormat::Jpeg => jpeg_dimensions(data),
+            ImageFormat::Gif => gif_dimensions(data),
+            // SVG has no fixed pixel size; rasterizing it would require a
+            // heavyweight dependency.
+            ImageFormat::Svg => None,
+        };
+        Self {
+            format,
+            size: data.len() as u64,
+            dimensions,
+        }
+    }
+}
+
+/// An image downscaled to a grid of terminal cells, two vertical pixels per
+/// cell, for rendering with the upper-half-block character and truecolor.
+pub struct BlockImage {
+    /// `(top, bottom)` pixel colors, row-major, `width` cells per row.
+    pub cells: Vec<(Color, Color)>,
+    pub width: u16,
+    pub height: u16,
+}
+
+/// Decodes and downscales `data` so it fits into `width` x `height` terminal
+/// cells, mapping two vertical pixels onto each cell. Returns `None` when the
+/// data cannot be decoded (e.g. SVG, which the `image` crate cannot read).
+pub fn render_blocks(data: &[u8], width: u16, height: u16) -> Option<BlockImage> {
+    if width == 0 || height == 0 {
+        return None;
+    }
+    let decoded = image::load_from_memory(data).ok()?;
+    // `thumbnail` preserves the aspect ratio within the given bounds.
+    let scaled = decoded
+        .thumbnail(u32::from(width), u32::from(height) * 2)
+        .to_rgba8();
+
+    let cell_width = scaled.width().min(u32::from(width)) as u16;
+    let cell_height = scaled.height().div_ceil(2).min(u32::from(height) * 2) as u16;
+    let mut cells = Vec::with_capacity(usize::from(cell_width) * usize::from(cell_height));
+    for row in 0..cell_height {
+        for column in 0..cell_width {
+            let pixel = |y: u32| -> Color {
+                if y >= scaled.height() {
+                    return Color::Reset;
+                }
+                let image::Rgba([r, g, b, a]) = *scaled.get_pixel(u32::from(column), y);
+                if a < 128 {
+                    Color::Reset
+                } else {
+                    Color::Rgb(r, g, b)
+                }
+            };
+            cells.push((
+                pixel(u32::from(row) * 2),
+                pixel(u32::from(row) * 2 + 1),
+            ));
+        }
+    }
+
+    Some(BlockImage {
+        cells,
+        width: cell_width,
+        height: cell_height,
+    })
+}
+
+/// Renders a byte count in a human readable form for the metadata fallback.
+pub fn format_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{bytes} B")
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
+fn png_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+    if !data.starts_with(SIGNATURE) || data.len() < 24 || &data[12..16] != b"IHDR" {
+        return None;
+    }
+    let width = u32::from_be_bytes(data[16..20].try_into().unwrap());
+    let height = u32::from_be_bytes(data[20..24].try_into().unwrap());
+    Some((width, height))
+}
+
+fn gif_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    if !(data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a")) || data.len() < 10 {
+        return None;
+    }
+    let width = u16::from_le_bytes(data[6..8].try_into().unwrap());
+    let height = u16::from_le_bytes(data[8..10].try_into().unwrap());
+    Some((width.into(), height.into()))
+}
+
+fn jpeg_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    if !data.starts_with(&[0xFF, 0xD8]) {
+        return None;
+    }
+    let mut i = 2;
+    while i + 4 <= data.len() {
+        if data[i] != 0xFF {
+            return None;
+        }
+        let marker = data[i + 1];
+        // Start-of-frame markers carry the dimensions.
+        if matches!(marker, 0xC0..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC)) {
+            if i + 9 > data.len() {
+                return None;
+            }
+            let height = u16::from_be_bytes(data[i + 5..i + 7].try_into().unwrap());
+            let width = u16::from_be_bytes(data[i + 7..i + 9].try_into().unwrap());
+            return Some((width.into(), height.into()));
+        }
+        let length = u16::from_be_bytes(data[i + 2..i + 4].try_into().unwrap());
+        i += 2 + length as usize;
+    }
+    None
+}
+
+/// Encodes a PNG file as a sequence of Kitty graphics protocol escapes that
+/// display it at the cursor position, scaled to fit `columns` x `rows` cells.
+///
+/// The sequence starts by deleting any previously transmitted images, so
+/// re-emitting it on every frame replaces the old placement instead of
+/// accumulating images in the terminal.
+pub fn kitty_image_sequence(png: &[u8], columns: u16, rows: u16) -> String {
+    let encoded = base64::encode(png);
+    let mut sequence = String::with_capacity(encoded.len() + encoded.len() / KITTY_CHUNK_SIZE * 16);
+    sequence.push_str(KITTY_DELETE_SEQUENCE);
+    let mut chunks = encoded.as_bytes().chunks(KITTY_CHUNK_SIZE).peekable();
+    let mut first = true;
+    while let Some(chunk) = chunks.next() {
+        let more = u8::from(chunks.peek().is_some());
+        sequence.push_str("\x1b_G");
+        if first {
+            // a=T: transmit and display, f=100: PNG data
+            let _ = write!(sequence, "a=T,f=100,c={columns},r={rows},");
+            first = false;
+        }
+        let _ = write!(sequence, "m={more};");
+        // base64 output is always valid UTF-8
+        sequence.push_str(std::str::from_utf8(chunk).unwrap());
+        sequence.push_str("\x1b\\");
+    }
+    sequence
+}
+
+/// Writes a Kitty graphics sequence directly to the terminal at the given
+/// cell position. This bypasses the compositor surface, which only holds
+/// text cells.
+///
+/// Because this skips the compositor's buffered writer it reaches the
+/// terminal before the frame currently being composed is flushed; that is
+/// harmless only because the image persists independently of cell redraws.
+/// The flip side is that it also survives frames that no longer render it,
+/// so callers must track the image and [`emit_kitty_delete`] it once it
+/// should disappear.
+pub fn emit_kitty_image_at(x: u16, y: u16, sequence: &str) -> std::io::Result<()> {
+    use crossterm::{cursor, QueueableCommand};
+
+    let mut stdout = std::io::stdout();
+    stdout.queue(cursor::SavePosition)?;
+    stdout.queue(cursor::MoveTo(x, y))?;
+    stdout.write_all(sequence.as_bytes())?;
+    stdout.queue(cursor::RestorePosition)?;
+    stdout.flush()
+}
+
+/// Deletes every image the terminal currently displays.
+pub fn emit_kitty_delete() -> std::io::Result<()> {
+    let mut stdout = std::io::stdout();
+    stdout.write_all(KITTY_DELETE_SEQUENCE.as_bytes())?;
+    stdout.flush()
+}
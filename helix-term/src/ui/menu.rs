@@ -78,13 +78,23 @@ impl<T: Item> Menu<T> {
     }
 
     pub fn score(&mut self, pattern: &str, incremental: bool) {
+        self.score_atom(pattern, incremental, AtomKind::Fuzzy)
+    }
+
+    /// Only keeps options whose filter text starts with `pattern`, for
+    /// completion filtering with `completion-fuzzy-match` disabled.
+    pub fn score_prefix(&mut self, pattern: &str, incremental: bool) {
+        self.score_atom(pattern, incremental, AtomKind::Prefix)
+    }
+
+    fn score_atom(&mut self, pattern: &str, incremental: bool, kind: AtomKind) {
         let mut matcher = MATCHER.lock();
         matcher.config = Config::DEFAULT;
         let pattern = Atom::new(
             pattern,
             CaseMatching::Ignore,
             Normalization::Smart,
-            AtomKind::Fuzzy,
+            kind,
             false,
         );
         let mut buf = Vec::new();
@@ -120,6 +130,40 @@ impl<T: Item> Menu<T> {
         self.recalculate = true;
     }
 
+    /// Scores `pattern` with [`helix_core::fuzzy::fuzzy_score`] so it may
+    /// match across word segments (`vit` matches `visit_mut`). Exact-prefix
+    /// matches rank before fuzzy ones, those by score descending, ties
+    /// alphabetically.
+    pub fn score_fuzzy(&mut self, pattern: &str) {
+        let mut scored: Vec<_> = self
+            .options
+            .iter()
+            .enumerate()
+            .filter_map(|(index, option)| {
+                let text = option.filter_text(&self.editor_data).into_owned();
+                helix_core::fuzzy::fuzzy_score(pattern, &text, false)
+                    .map(|score| (text.starts_with(pattern), score, text, index as u32))
+            })
+            .collect();
+        scored.sort_unstable_by(
+            |(a_prefix, a_score, a_text, _), (b_prefix, b_score, b_text, _)| {
+                b_prefix
+                    .cmp(a_prefix)
+                    .then(b_score.cmp(a_score))
+                    .then_with(|| a_text.cmp(b_text))
+            },
+        );
+        self.matches = scored
+            .into_iter()
+            .map(|(_, score, _, index)| (index, score as u32))
+            .collect();
+
+        // reset cursor position
+        self.cursor = None;
+        self.scroll = 0;
+        self.recalculate = true;
+    }
+
     pub fn clear(&mut self) {
         self.matches.clear();
 
@@ -261,6 +305,7 @@ impl<T: Item + 'static> Component for Menu<T> {
                 Some(SmartTabConfig {
                     enable: true,
                     supersede_menu: true,
+                    ..
                 })
             )
         {
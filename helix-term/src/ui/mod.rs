@@ -3,6 +3,8 @@ pub mod copilot_picker;
 mod document;
 pub(crate) mod editor;
 mod explorer;
+pub mod hex;
+mod image_preview;
 mod info;
 pub mod lsp;
 mod markdown;
@@ -22,7 +24,7 @@ use crate::filter_picker_entry;
 use crate::job::{self, Callback};
 pub use completion::Completion;
 pub use editor::EditorView;
-pub use explorer::Explorer;
+pub use explorer::{Explorer, ExplorerSort};
 use helix_stdx::rope;
 pub use markdown::Markdown;
 pub use menu::Menu;
@@ -35,7 +37,10 @@ pub use tree::{TreeOp, TreeView, TreeViewItem};
 
 use helix_view::Editor;
 
-use std::{error::Error, path::PathBuf};
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+};
 
 struct Utf8PathBuf {
     path: String,
@@ -124,6 +129,11 @@ pub fn raw_regex_prompt(
                     } else {
                         false
                     };
+                    let pattern = if config.search.unicode_normalize {
+                        std::borrow::Cow::Owned(helix_core::search::normalize_nfc(input))
+                    } else {
+                        std::borrow::Cow::Borrowed(input)
+                    };
 
                     match rope::RegexBuilder::new()
                         .syntax(
@@ -131,7 +141,7 @@ pub fn raw_regex_prompt(
                                 .case_insensitive(case_insensitive)
                                 .multi_line(true),
                         )
-                        .build(input)
+                        .build(&pattern)
                     {
                         Ok(regex) => {
                             let (view, doc) = current!(cx.editor);
@@ -190,6 +200,17 @@ pub fn raw_regex_prompt(
 type FilePicker = Picker<PathBuf, PathBuf>;
 
 pub fn file_picker(root: PathBuf, config: &helix_view::editor::Config) -> FilePicker {
+    file_picker_with(root, config, |_, _| ())
+}
+
+/// Like [`file_picker`], but also runs `on_select` with the picked path once
+/// the file has been opened successfully. The explorer uses this to reveal
+/// the picked file in its tree.
+pub fn file_picker_with(
+    root: PathBuf,
+    config: &helix_view::editor::Config,
+    on_select: impl Fn(&mut crate::compositor::Context, &Path) + 'static,
+) -> FilePicker {
     use ignore::{types::TypesBuilder, WalkBuilder};
     use std::time::Instant;
 
@@ -253,7 +274,9 @@ pub fn file_picker(root: PathBuf, config: &helix_view::editor::Config) -> FilePi
                 format!("unable to open \"{}\"", path.display())
             };
             cx.editor.set_error(err);
+            return;
         }
+        on_select(cx, path);
     })
     .with_preview(|_editor, path| Some((path.as_path().into(), None)));
     let injector = picker.injector();
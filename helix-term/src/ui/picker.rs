@@ -410,11 +410,35 @@ impl<T: 'static + Send + Sync, D: 'static + Send + Sync> Picker<T, D> {
         self
     }
 
+    /// Seeds the preview cache with in-memory documents, for previews that are
+    /// not backed by a file on disk. The paths act purely as cache keys and
+    /// must match the locations returned by the [`Self::with_preview`]
+    /// callback.
+    pub fn with_preview_documents(
+        mut self,
+        documents: impl IntoIterator<Item = (Arc<Path>, Document)>,
+    ) -> Self {
+        self.preview_cache.extend(
+            documents
+                .into_iter()
+                .map(|(path, doc)| (path, CachedPreview::Document(Box::new(doc)))),
+        );
+        self
+    }
+
     pub fn with_history_register(mut self, history_register: Option<char>) -> Self {
         self.prompt.with_history_register(history_register);
         self
     }
 
+    /// Seeds the prompt with an initial query, filtering the results as if
+    /// it had been typed.
+    pub fn with_line(mut self, line: String, editor: &Editor) -> Self {
+        self.prompt.set_line(line, editor);
+        self.handle_prompt_change(true);
+        self
+    }
+
     pub fn with_dynamic_query(
         mut self,
         callback: DynQueryCallback<T, D>,
@@ -39,6 +39,10 @@ pub struct Prompt {
     pub doc_fn: DocFn,
     next_char_handler: Option<PromptCharHandler>,
     language: Option<(&'static str, Arc<ArcSwap<syntax::Loader>>)>,
+    /// A selected byte range of the line; typing or deleting replaces it,
+    /// movement deselects. Used to prefill a prompt with a part to retype,
+    /// e.g. the file stem when renaming in the explorer.
+    line_selection: Option<std::ops::Range<usize>>,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -91,6 +95,7 @@ impl Prompt {
             doc_fn: Box::new(|_| None),
             next_char_handler: None,
             language: None,
+            line_selection: None,
         }
     }
 
@@ -109,9 +114,37 @@ impl Prompt {
         let cursor = line.len();
         self.line = line;
         self.cursor = cursor;
+        self.line_selection = None;
         self.recalculate_completion(editor);
     }
 
+    /// Selects `range` of the line so the next typed character replaces it.
+    /// Must be applied after [`Self::with_line`]; out of bounds or non char
+    /// boundary ranges are ignored. The cursor is placed at the range end.
+    pub fn with_selection(mut self, range: std::ops::Range<usize>) -> Self {
+        if range.start < range.end
+            && self.line.is_char_boundary(range.start)
+            && self.line.is_char_boundary(range.end)
+        {
+            self.cursor = range.end;
+            self.line_selection = Some(range);
+        }
+        self
+    }
+
+    /// Removes the selected part of the line, if any. Returns whether
+    /// something was deleted.
+    fn delete_line_selection(&mut self) -> bool {
+        match self.line_selection.take() {
+            Some(range) => {
+                self.cursor = range.start;
+                self.line.replace_range(range, "");
+                true
+            }
+            None => false,
+        }
+    }
+
     pub fn with_language(
         mut self,
         language: &'static str,
@@ -248,6 +281,7 @@ impl Prompt {
             return;
         }
 
+        self.delete_line_selection();
         self.line.insert(self.cursor, c);
         let mut cursor = GraphemeCursor::new(self.cursor, self.line.len(), false);
         if let Ok(Some(pos)) = cursor.next_boundary(&self.line, 0) {
@@ -257,35 +291,43 @@ impl Prompt {
     }
 
     pub fn insert_str(&mut self, s: &str, editor: &Editor) {
+        self.delete_line_selection();
         self.line.insert_str(self.cursor, s);
         self.cursor += s.len();
         self.recalculate_completion(editor);
     }
 
     pub fn move_cursor(&mut self, movement: Movement) {
+        self.line_selection = None;
         let pos = self.eval_movement(movement);
         self.cursor = pos
     }
 
     pub fn move_start(&mut self) {
+        self.line_selection = None;
         self.cursor = 0;
     }
 
     pub fn move_end(&mut self) {
+        self.line_selection = None;
         self.cursor = self.line.len();
     }
 
     pub fn delete_char_backwards(&mut self, editor: &Editor) {
-        let pos = self.eval_movement(Movement::BackwardChar(1));
-        self.line.replace_range(pos..self.cursor, "");
-        self.cursor = pos;
+        if !self.delete_line_selection() {
+            let pos = self.eval_movement(Movement::BackwardChar(1));
+            self.line.replace_range(pos..self.cursor, "");
+            self.cursor = pos;
+        }
 
         self.recalculate_completion(editor);
     }
 
     pub fn delete_char_forwards(&mut self, editor: &Editor) {
-        let pos = self.eval_movement(Movement::ForwardChar(1));
-        self.line.replace_range(self.cursor..pos, "");
+        if !self.delete_line_selection() {
+            let pos = self.eval_movement(Movement::ForwardChar(1));
+            self.line.replace_range(self.cursor..pos, "");
+        }
 
         self.recalculate_completion(editor);
     }
@@ -515,6 +557,24 @@ impl Prompt {
             )
             .into();
             text.render(line_area, surface, cx);
+        } else if let Some(range) = self
+            .line_selection
+            .clone()
+            .filter(|range| range.end <= self.line.len())
+        {
+            // highlight the selected part of the line
+            let selection_color = prompt_color.patch(cx.editor.theme.get("ui.selection"));
+            let before = &self.line[..range.start];
+            let selected = &self.line[range.clone()];
+            surface.set_string(line_area.x, line_area.y, before, prompt_color);
+            let x = line_area.x + before.width() as u16;
+            surface.set_string(x, line_area.y, selected, selection_color);
+            surface.set_string(
+                x + selected.width() as u16,
+                line_area.y,
+                &self.line[range.end..],
+                prompt_color,
+            );
         } else {
             surface.set_string(line_area.x, line_area.y, self.line.clone(), prompt_color);
         }
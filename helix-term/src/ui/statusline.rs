@@ -1,11 +1,13 @@
-use helix_core::{coords_at_pos, encoding, Position};
+use std::cell::RefCell;
+
+use helix_core::{coords_at_pos, encoding, textobject, Position};
 use helix_lsp::lsp::DiagnosticSeverity;
 use helix_view::document::DEFAULT_LANGUAGE_NAME;
 use helix_view::{
     document::{Mode, SCRATCH_BUFFER_NAME},
     graphics::Rect,
     theme::Style,
-    Document, Editor, View,
+    Document, DocumentId, Editor, View, ViewId,
 };
 
 use crate::ui::ProgressSpinners;
@@ -20,7 +22,10 @@ pub struct RenderContext<'a> {
     pub view: &'a View,
     pub focused: bool,
     pub spinners: &'a ProgressSpinners,
+    pub breadcrumbs: &'a BreadcrumbsCache,
     pub parts: RenderBuffer<'a>,
+    /// Width of the statusline, for elements that scale with it.
+    pub width: u16,
 }
 
 impl<'a> RenderContext<'a> {
@@ -30,6 +35,7 @@ impl<'a> RenderContext<'a> {
         view: &'a View,
         focused: bool,
         spinners: &'a ProgressSpinners,
+        breadcrumbs: &'a BreadcrumbsCache,
     ) -> Self {
         RenderContext {
             editor,
@@ -37,8 +43,60 @@ impl<'a> RenderContext<'a> {
             view,
             focused,
             spinners,
+            breadcrumbs,
             parts: RenderBuffer::default(),
+            width: 0,
+        }
+    }
+}
+
+/// Cached result of the `syntax-breadcrumbs` statusline element.
+///
+/// Running the textobject query is too expensive to repeat on every frame, so
+/// the computed breadcrumb string is kept until the primary cursor moves to
+/// another line or the document changes (which also covers reparses).
+#[derive(Default)]
+pub struct BreadcrumbsCache(RefCell<Option<BreadcrumbsEntry>>);
+
+struct BreadcrumbsEntry {
+    doc: DocumentId,
+    view: ViewId,
+    line: usize,
+    version: i32,
+    breadcrumbs: String,
+}
+
+impl BreadcrumbsCache {
+    fn get(&self, doc: &Document, view: &View) -> String {
+        let text = doc.text().slice(..);
+        let line = doc.selection(view.id).primary().cursor_line(text);
+        let mut cached = self.0.borrow_mut();
+        if let Some(entry) = cached.as_ref() {
+            if entry.doc == doc.id()
+                && entry.view == view.id
+                && entry.line == line
+                && entry.version == doc.version()
+            {
+                return entry.breadcrumbs.clone();
+            }
         }
+
+        let breadcrumbs = match (doc.syntax(), doc.language_config()) {
+            (Some(syntax), Some(lang_config)) => {
+                let pos = doc.selection(view.id).primary().cursor(text);
+                textobject::breadcrumbs(text, syntax.tree().root_node(), lang_config, pos)
+                    .join(" ▸ ")
+            }
+            _ => String::new(),
+        };
+        *cached = Some(BreadcrumbsEntry {
+            doc: doc.id(),
+            view: view.id,
+            line,
+            version: doc.version(),
+            breadcrumbs: breadcrumbs.clone(),
+        });
+        breadcrumbs
     }
 }
 
@@ -58,6 +116,8 @@ pub fn render(context: &mut RenderContext, viewport: Rect, surface: &mut Surface
 
     surface.set_style(viewport.with_height(1), base_style);
 
+    context.width = viewport.width;
+
     let write_left = |context: &mut RenderContext, text, style| {
         append(&mut context.parts.left, text, &base_style, style)
     };
@@ -163,6 +223,9 @@ where
         helix_view::editor::StatusLineElement::Spacer => render_spacer,
         helix_view::editor::StatusLineElement::VersionControl => render_version_control,
         helix_view::editor::StatusLineElement::Register => render_register,
+        helix_view::editor::StatusLineElement::Quickfix => render_quickfix,
+        helix_view::editor::StatusLineElement::Copilot => render_copilot,
+        helix_view::editor::StatusLineElement::SyntaxBreadcrumbs => render_syntax_breadcrumbs,
     }
 }
 
@@ -531,3 +594,57 @@ where
         write(context, format!(" reg={} ", reg), None)
     }
 }
+
+fn render_quickfix<F>(context: &mut RenderContext, write: F)
+where
+    F: Fn(&mut RenderContext, String, Option<Style>) + Copy,
+{
+    let text = context
+        .editor
+        .quickfix
+        .lock()
+        .as_ref()
+        .map(|list| match list.current {
+            Some(current) => format!(" qf {}/{} ", current + 1, list.entries.len()),
+            None => format!(" qf {} ", list.entries.len()),
+        });
+    if let Some(text) = text {
+        write(context, text, None)
+    }
+}
+
+fn render_copilot<F>(context: &mut RenderContext, write: F)
+where
+    F: Fn(&mut RenderContext, String, Option<Style>) + Copy,
+{
+    let title = if context.doc.copilot_excluded() {
+        " excluded by .copilotignore "
+    } else if context.doc.copilot_too_large() {
+        " disabled (large file) "
+    } else {
+        ""
+    }
+    .to_string();
+    write(context, title, None);
+}
+
+fn render_syntax_breadcrumbs<F>(context: &mut RenderContext, write: F)
+where
+    F: Fn(&mut RenderContext, String, Option<Style>) + Copy,
+{
+    let mut breadcrumbs = context.breadcrumbs.get(context.doc, context.view);
+    if breadcrumbs.is_empty() {
+        return;
+    }
+
+    // Cut from the left on narrow statuslines so the innermost context stays
+    // visible.
+    let max_width = (context.width as usize / 2).max(16);
+    let width = breadcrumbs.chars().count();
+    if width > max_width {
+        let truncated: String = breadcrumbs.chars().skip(width - max_width + 1).collect();
+        breadcrumbs = format!("…{}", truncated);
+    }
+
+    write(context, format!(" {} ", breadcrumbs), None);
+}
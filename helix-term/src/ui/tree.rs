@@ -1,7 +1,9 @@
 use std::cmp::Ordering;
+use std::rc::Rc;
 
 use anyhow::Result;
-use helix_view::theme::Modifier;
+use helix_view::theme::{Modifier, Style};
+use helix_view::Editor;
 
 use crate::{
     compositor::{Component, Context, EventResult},
@@ -33,8 +35,18 @@ fn tree_item_cmp<T: TreeViewItem>(item1: &T, item2: &T) -> Ordering {
     T::cmp(item1, item2)
 }
 
-fn vec_to_tree<T: TreeViewItem>(mut items: Vec<T>) -> Vec<Tree<T>> {
-    items.sort();
+/// Comparator a [`TreeView`] can use to order sibling nodes instead of the
+/// item's `Ord` implementation.
+pub type TreeViewComparator<T> = Rc<dyn Fn(&T, &T) -> Ordering>;
+
+fn vec_to_tree<T: TreeViewItem>(
+    mut items: Vec<T>,
+    comparator: Option<&TreeViewComparator<T>>,
+) -> Vec<Tree<T>> {
+    match comparator {
+        Some(comparator) => items.sort_by(|a, b| comparator(a, b)),
+        None => items.sort(),
+    }
     index_elems(
         0,
         items
@@ -120,9 +132,9 @@ impl<'a, T> DoubleEndedIterator for TreeIter<'a, T> {
 impl<'a, T> ExactSizeIterator for TreeIter<'a, T> {}
 
 impl<T: TreeViewItem> Tree<T> {
-    fn open(&mut self) -> Result<()> {
+    fn open(&mut self, comparator: Option<&TreeViewComparator<T>>) -> Result<()> {
         if self.item.is_parent() {
-            self.children = self.get_children()?;
+            self.children = self.get_children(comparator)?;
             self.is_opened = true;
         }
         Ok(())
@@ -133,11 +145,11 @@ impl<T: TreeViewItem> Tree<T> {
         self.children = vec![];
     }
 
-    fn refresh(&mut self) -> Result<()> {
+    fn refresh(&mut self, comparator: Option<&TreeViewComparator<T>>) -> Result<()> {
         if !self.is_opened {
             return Ok(());
         }
-        let latest_children = self.get_children()?;
+        let latest_children = self.get_children(comparator)?;
         let filtered = std::mem::take(&mut self.children)
             .into_iter()
             // Remove children that does not exists in latest_children
@@ -147,7 +159,7 @@ impl<T: TreeViewItem> Tree<T> {
                     .any(|child| tree.item.name().eq(&child.item.name()))
             })
             .map(|mut tree| {
-                tree.refresh()?;
+                tree.refresh(comparator)?;
                 Ok(tree)
             })
             .collect::<Result<Vec<_>>>()?;
@@ -164,20 +176,33 @@ impl<T: TreeViewItem> Tree<T> {
 
         self.children = filtered.into_iter().chain(new_nodes).collect();
 
-        self.sort();
+        self.sort(comparator);
 
         self.regenerate_index();
 
         Ok(())
     }
 
-    fn get_children(&self) -> Result<Vec<Tree<T>>> {
-        Ok(vec_to_tree(self.item.get_children()?))
+    fn get_children(&self, comparator: Option<&TreeViewComparator<T>>) -> Result<Vec<Tree<T>>> {
+        Ok(vec_to_tree(self.item.get_children()?, comparator))
     }
 
-    fn sort(&mut self) {
-        self.children
-            .sort_by(|a, b| tree_item_cmp(&a.item, &b.item))
+    fn sort(&mut self, comparator: Option<&TreeViewComparator<T>>) {
+        match comparator {
+            Some(comparator) => self.children.sort_by(|a, b| comparator(&a.item, &b.item)),
+            None => self
+                .children
+                .sort_by(|a, b| tree_item_cmp(&a.item, &b.item)),
+        }
+    }
+
+    /// Re-orders the already loaded nodes, recursively, without calling
+    /// `get_children` again.
+    fn sort_recursive(&mut self, comparator: Option<&TreeViewComparator<T>>) {
+        self.sort(comparator);
+        for child in self.children.iter_mut() {
+            child.sort_recursive(comparator);
+        }
     }
 }
 
@@ -272,6 +297,12 @@ pub struct TreeView<T: TreeViewItem> {
 
     search_str: String,
 
+    /// Indices of all items matching `search_str`, in tree order
+    search_matches: Vec<usize>,
+
+    /// Position of the selected item within `search_matches`, if it is a match
+    current_match: Option<usize>,
+
     /// Selected item idex
     selected: usize,
 
@@ -291,6 +322,10 @@ pub struct TreeView<T: TreeViewItem> {
     count: usize,
     tree_symbol_style: String,
 
+    /// Overrides the item's `Ord` implementation for ordering siblings,
+    /// so the ordering can be changed at runtime via [`Self::resort`].
+    comparator: Option<TreeViewComparator<T>>,
+
     #[allow(clippy::type_complexity)]
     pre_render: Option<Box<dyn Fn(&mut Self, Rect) + 'static>>,
 
@@ -302,12 +337,25 @@ pub struct TreeView<T: TreeViewItem> {
 
     #[allow(clippy::type_complexity)]
     on_next_key: Option<Box<dyn FnMut(&mut Context, &mut Self, &KeyEvent) -> Result<()>>>,
+
+    #[allow(clippy::type_complexity)]
+    decoration_fn: Option<Box<dyn Fn(&T, &Editor) -> Option<ItemDecoration> + 'static>>,
+}
+
+/// Extra per-item decoration the owner of the view can attach to rows: a
+/// one-character marker rendered after the name and an optional style for
+/// the name itself. The explorer uses this for git status markers.
+#[derive(Debug, Clone, Copy)]
+pub struct ItemDecoration {
+    pub marker: char,
+    pub marker_style: Style,
+    pub name_style: Option<Style>,
 }
 
 impl<T: TreeViewItem> TreeView<T> {
     pub fn build_tree(root: T) -> Result<Self> {
         let children = root.get_children()?;
-        let items = vec_to_tree(children);
+        let items = vec_to_tree(children, None);
         Ok(Self {
             tree: Tree::new(root, items),
             selected: 0,
@@ -319,12 +367,16 @@ impl<T: TreeViewItem> TreeView<T> {
             max_len: 0,
             count: 0,
             tree_symbol_style: "ui.text".into(),
+            comparator: None,
             pre_render: None,
             on_opened_fn: None,
             on_folded_fn: None,
             on_next_key: None,
             search_prompt: None,
             search_str: "".into(),
+            search_matches: vec![],
+            current_match: None,
+            decoration_fn: None,
         })
     }
 
@@ -349,6 +401,14 @@ impl<T: TreeViewItem> TreeView<T> {
         self
     }
 
+    pub fn with_decoration_fn<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&T, &Editor) -> Option<ItemDecoration> + 'static,
+    {
+        self.decoration_fn = Some(Box::new(f));
+        self
+    }
+
     /// Reveal item in the tree based on the given `segments`.
     ///
     /// The name of the root should be excluded.
@@ -359,6 +419,7 @@ impl<T: TreeViewItem> TreeView<T> {
     ///
     pub fn reveal_item(&mut self, segments: Vec<String>) -> Result<()> {
         // Expand the tree
+        let comparator = self.comparator.clone();
         let root = self.tree.item.name();
         segments.iter().fold(
             Ok(&mut self.tree),
@@ -372,7 +433,7 @@ impl<T: TreeViewItem> TreeView<T> {
                     {
                         Some(tree) => {
                             if !tree.is_opened {
-                                tree.open()?;
+                                tree.open(comparator.as_ref())?;
                             }
                             Ok(tree)
                         }
@@ -419,6 +480,7 @@ impl<T: TreeViewItem> TreeView<T> {
 
     fn regenerate_index(&mut self) {
         self.tree.regenerate_index();
+        self.recalculate_search_matches();
     }
 
     fn move_to_parent(&mut self) -> Result<()> {
@@ -430,12 +492,13 @@ impl<T: TreeViewItem> TreeView<T> {
     }
 
     fn move_to_children(&mut self) -> Result<()> {
+        let comparator = self.comparator.clone();
         let current = self.current_mut()?;
         if current.is_opened {
             self.set_selected(self.selected + 1);
             Ok(())
         } else {
-            current.open()?;
+            current.open(comparator.as_ref())?;
             if !current.children.is_empty() {
                 self.set_selected(self.selected + 1);
                 self.regenerate_index();
@@ -445,11 +508,63 @@ impl<T: TreeViewItem> TreeView<T> {
     }
 
     pub fn refresh(&mut self) -> Result<()> {
-        self.tree.refresh()?;
+        self.tree.refresh(self.comparator.as_ref())?;
         self.set_selected(self.selected);
+        self.recalculate_search_matches();
         Ok(())
     }
 
+    /// Orders sibling nodes with `comparator` instead of the item's `Ord`
+    /// implementation, re-sorting the already loaded nodes in place.
+    pub fn set_comparator(&mut self, comparator: impl Fn(&T, &T) -> Ordering + 'static) {
+        self.comparator = Some(Rc::new(comparator));
+        self.resort();
+    }
+
+    /// Restores ordering by the item's `Ord` implementation.
+    pub fn clear_comparator(&mut self) {
+        self.comparator = None;
+        self.resort();
+    }
+
+    /// Re-orders the already loaded nodes with the current ordering,
+    /// recursively, without touching the filesystem. The selection stays on
+    /// the same item.
+    pub fn resort(&mut self) {
+        let selected = self.selected_segments();
+        self.tree.sort_recursive(self.comparator.as_ref());
+        self.regenerate_index();
+        if let Some(index) = self.find_by_segments(&selected) {
+            self.set_selected(index);
+        }
+    }
+
+    /// The names leading from the root (exclusive) to the selected item.
+    fn selected_segments(&self) -> Vec<String> {
+        let mut segments = Vec::new();
+        let mut current = self.tree.get(self.selected);
+        while let Some(node) = current {
+            let Some(parent_index) = node.parent_index else {
+                break;
+            };
+            segments.push(node.item.name());
+            current = self.tree.get(parent_index);
+        }
+        segments.reverse();
+        segments
+    }
+
+    fn find_by_segments(&self, segments: &[String]) -> Option<usize> {
+        let mut tree = &self.tree;
+        for segment in segments {
+            tree = tree
+                .children
+                .iter()
+                .find(|tree| tree.item.name().eq(segment))?;
+        }
+        Some(tree.index)
+    }
+
     fn move_to_first_line(&mut self) {
         self.move_up(usize::MAX / 2)
     }
@@ -531,11 +646,12 @@ impl<T: TreeViewItem> TreeView<T> {
         }
 
         if let Some(mut on_open_fn) = self.on_opened_fn.take() {
+            let comparator = self.comparator.clone();
             let mut f = || -> Result<()> {
                 let current = self.current_mut()?;
                 match on_open_fn(&mut current.item, cx, params) {
                     TreeOp::GetChildsAndInsert => {
-                        if let Err(err) = current.open() {
+                        if let Err(err) = current.open(comparator.as_ref()) {
                             cx.editor.set_error(format!("{err}"))
                         }
                     }
@@ -553,6 +669,37 @@ impl<T: TreeViewItem> TreeView<T> {
     fn set_search_str(&mut self, s: String) {
         self.search_str = s;
         self.saved_view = None;
+        self.recalculate_search_matches();
+    }
+
+    /// Recompute the set of items matching `search_str`.
+    ///
+    /// The root is excluded since it is always visible anyway.
+    fn recalculate_search_matches(&mut self) {
+        if self.search_str.is_empty() {
+            self.search_matches.clear();
+        } else {
+            self.search_matches = (1..self.tree.len())
+                .filter(|&index| {
+                    self.tree
+                        .get(index)
+                        .is_some_and(|tree| tree.item.filter(&self.search_str))
+                })
+                .collect();
+        }
+        self.current_match = self.search_matches.binary_search(&self.selected).ok();
+    }
+
+    /// Returns `(current, total)` of the search matches, where `current` is
+    /// 1-based and 0 when the cursor is not on a match.
+    pub fn search_match_status(&self) -> Option<(usize, usize)> {
+        if self.search_matches.is_empty() {
+            return None;
+        }
+        Some((
+            self.current_match.map_or(0, |index| index + 1),
+            self.search_matches.len(),
+        ))
     }
 
     fn saved_view(&self) -> SavedView {
@@ -583,11 +730,13 @@ impl<T: TreeViewItem> TreeView<T> {
     }
 
     fn move_to_next_search_match(&mut self) {
-        self.search_next(&self.search_str.clone())
+        self.search_next(&self.search_str.clone());
+        self.current_match = self.search_matches.binary_search(&self.selected).ok();
     }
 
     fn move_to_previous_next_match(&mut self) {
-        self.search_previous(&self.search_str.clone())
+        self.search_previous(&self.search_str.clone());
+        self.current_match = self.search_matches.binary_search(&self.selected).ok();
     }
 
     pub fn move_down(&mut self, rows: usize) {
@@ -762,6 +911,12 @@ impl<T: TreeViewItem> TreeView<T> {
         Ok(&self.current()?.item)
     }
 
+    /// The root item, mutably; lets callers restyle the root label without
+    /// rebuilding the whole tree.
+    pub fn root_item_mut(&mut self) -> &mut T {
+        &mut self.tree.item
+    }
+
     pub fn winline(&self) -> usize {
         self.winline
     }
@@ -773,12 +928,18 @@ struct RenderedLine {
     content: String,
     selected: bool,
     is_ancestor_of_current_item: bool,
+    is_search_match: bool,
+    decoration: Option<ItemDecoration>,
 }
 struct RenderTreeParams<'a, T> {
     tree: &'a Tree<T>,
     prefix: &'a String,
     level: usize,
     selected: usize,
+    search_matches: &'a [usize],
+    #[allow(clippy::type_complexity)]
+    decoration_fn: Option<&'a (dyn Fn(&T, &Editor) -> Option<ItemDecoration> + 'static)>,
+    editor: Option<&'a Editor>,
 }
 
 fn render_tree<T: TreeViewItem>(
@@ -787,6 +948,9 @@ fn render_tree<T: TreeViewItem>(
         prefix,
         level,
         selected,
+        search_matches,
+        decoration_fn,
+        editor,
     }: RenderTreeParams<T>,
 ) -> Vec<RenderedLine> {
     let indent = if level > 0 {
@@ -808,7 +972,12 @@ fn render_tree<T: TreeViewItem>(
         indent,
         selected: selected == tree.index,
         is_ancestor_of_current_item: selected != tree.index && tree.get(selected).is_some(),
+        is_search_match: search_matches.binary_search(&tree.index).is_ok(),
         content: name,
+        decoration: match (decoration_fn, editor) {
+            (Some(decoration_fn), Some(editor)) => decoration_fn(tree.item(), editor),
+            _ => None,
+        },
     };
     let prefix = format!("{}{}", prefix, if level == 0 { "" } else { "  " });
     vec![head]
@@ -819,6 +988,9 @@ fn render_tree<T: TreeViewItem>(
                 prefix: &prefix,
                 level: level + 1,
                 selected,
+                search_matches,
+                decoration_fn,
+                editor,
             })
         }))
         .collect()
@@ -846,7 +1018,16 @@ impl<T: TreeViewItem + Clone> TreeView<T> {
             }
         };
 
-        let iter = self.render_lines(area).into_iter().enumerate();
+        let match_style = cx
+            .editor
+            .theme
+            .try_get("ui.highlight")
+            .unwrap_or_else(|| cx.editor.theme.get("ui.selection"));
+
+        let iter = self
+            .render_lines(area, Some(cx.editor))
+            .into_iter()
+            .enumerate();
 
         for (index, line) in iter {
             let area = Rect::new(area.x, area.y.saturating_add(index as u16), area.width, 1);
@@ -861,30 +1042,49 @@ impl<T: TreeViewItem + Clone> TreeView<T> {
 
             let style = if line.selected {
                 style.add_modifier(Modifier::REVERSED)
+            } else if line.is_search_match {
+                style.patch(match_style)
+            } else {
+                style
+            };
+            let style = if line.is_ancestor_of_current_item {
+                ancestor_style
             } else {
                 style
             };
+            // A decoration may tint the name, but never the selected row:
+            // the reversed selection has to stay readable.
+            let style = match line.decoration.and_then(|decoration| decoration.name_style) {
+                Some(name_style) if !line.selected => style.patch(name_style),
+                _ => style,
+            };
             let x = area.x.saturating_add(indent_len);
-            surface.set_stringn(
-                x,
-                area.y,
-                line.content.clone(),
-                area.width
-                    .saturating_sub(indent_len)
-                    .saturating_sub(1)
-                    .into(),
-                if line.is_ancestor_of_current_item {
-                    ancestor_style
-                } else {
-                    style
-                },
-            );
+            let width: usize = area
+                .width
+                .saturating_sub(indent_len)
+                .saturating_sub(1)
+                .into();
+            surface.set_stringn(x, area.y, line.content.clone(), width, style);
+
+            if let Some(decoration) = line.decoration {
+                let content_len = line.content.chars().count().min(width) as u16;
+                let marker_x = x.saturating_add(content_len).saturating_add(1);
+                if marker_x < area.right() {
+                    surface.set_stringn(
+                        marker_x,
+                        area.y,
+                        decoration.marker.to_string(),
+                        1,
+                        decoration.marker_style,
+                    );
+                }
+            }
         }
     }
 
     #[cfg(test)]
     pub fn render_to_string(&mut self, area: Rect) -> String {
-        let lines = self.render_lines(area);
+        let lines = self.render_lines(area, None);
         lines
             .into_iter()
             .map(|line| {
@@ -901,7 +1101,7 @@ impl<T: TreeViewItem + Clone> TreeView<T> {
             .join("\n")
     }
 
-    fn render_lines(&mut self, area: Rect) -> Vec<RenderedLine> {
+    fn render_lines(&mut self, area: Rect, editor: Option<&Editor>) -> Vec<RenderedLine> {
         if let Some(pre_render) = self.pre_render.take() {
             pre_render(self, area);
         }
@@ -913,6 +1113,9 @@ impl<T: TreeViewItem + Clone> TreeView<T> {
             prefix: &"".to_string(),
             level: 0,
             selected: self.selected,
+            search_matches: &self.search_matches,
+            decoration_fn: self.decoration_fn.as_deref(),
+            editor,
         };
 
         let lines = render_tree(params);
@@ -1292,6 +1495,45 @@ mod test_tree_view {
         );
     }
 
+    #[test]
+    fn test_resort_with_comparator() {
+        let mut view = dummy_tree_view();
+
+        // A comparator re-orders the already loaded nodes in place
+        view.set_comparator(|a: &DivisibleItem, b: &DivisibleItem| b.name.cmp(a.name));
+        assert_eq!(
+            render(&mut view),
+            "
+(who_lives_in_a_pineapple_under_the_sea)
+⏵ spongebob_squarepants
+⏵ sandy_cheeks
+⏵ plankton
+⏵ patrick_star
+"
+            .trim()
+        );
+
+        // The selection follows the item it was on, not its row
+        view.move_down(1);
+        assert_eq!(view.current_item().unwrap().name, "spongebob_squarepants");
+        view.clear_comparator();
+        assert_eq!(view.current_item().unwrap().name, "spongebob_squarepants");
+
+        // Dropping the comparator restores the `Ord` ordering
+        view.move_up(10);
+        assert_eq!(
+            render(&mut view),
+            "
+(who_lives_in_a_pineapple_under_the_sea)
+⏵ gary_the_snail
+⏵ karen
+⏵ king_neptune
+⏵ krabby_patty
+"
+            .trim()
+        );
+    }
+
     #[test]
     fn test_move_up_down() {
         let mut view = dummy_tree_view();
@@ -2122,6 +2364,36 @@ krabby_patty
         );
     }
 
+    #[test]
+    fn test_search_match_set_and_status() {
+        let mut view = dummy_tree_view();
+        assert_eq!(view.search_match_status(), None);
+
+        view.set_search_str("pat".to_string());
+        let names = view
+            .search_matches
+            .iter()
+            .map(|&index| view.get(index).unwrap().item.name())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["krabby_patty", "patrick_star"]);
+
+        // The cursor is still on the root, which is not a match
+        assert_eq!(view.search_match_status(), Some((0, 2)));
+
+        view.move_to_next_search_match();
+        assert_eq!(view.search_match_status(), Some((1, 2)));
+
+        view.move_to_next_search_match();
+        assert_eq!(view.search_match_status(), Some((2, 2)));
+
+        // Cycling wraps around to the first match
+        view.move_to_next_search_match();
+        assert_eq!(view.search_match_status(), Some((1, 2)));
+
+        view.set_search_str("".to_string());
+        assert_eq!(view.search_match_status(), None);
+    }
+
     #[test]
     fn test_jump_backward_forward() {
         let mut view = dummy_tree_view();
@@ -0,0 +1,334 @@
+use std::time::Duration;
+
+use helix_term::application::Application;
+use helix_view::{doc, editor::LspConfig, input::parse_macro};
+use tempfile::NamedTempFile;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+use super::*;
+
+/// Builds an application editing a `.mock` file whose only language server
+/// is the `copilot-mock-agent` test binary. The returned log file collects
+/// every method the mock receives. `always_allow` skips the per-workspace
+/// consent prompt, which every test but the consent one wants.
+fn copilot_app(
+    always_allow: bool,
+) -> anyhow::Result<(Application, NamedTempFile, NamedTempFile)> {
+    copilot_app_with_file_status(always_allow, None)
+}
+
+/// Like [`copilot_app`], but additionally tells the mock agent which
+/// `checkFileStatus` verdict to hand out (`"blocked"` simulates a
+/// server-side content-exclusion policy).
+fn copilot_app_with_file_status(
+    always_allow: bool,
+    file_status: Option<&str>,
+) -> anyhow::Result<(Application, NamedTempFile, NamedTempFile)> {
+    let file = tempfile::Builder::new().suffix(".mock").tempfile()?;
+    let log = NamedTempFile::new()?;
+
+    let file_status = file_status
+        .map(|status| format!(r#", COPILOT_MOCK_FILE_STATUS = "{}""#, status))
+        .unwrap_or_default();
+    let lang_conf = format!(
+        r#"
+        [language-server.copilot]
+        command = "{agent}"
+        environment = {{ COPILOT_MOCK_LOG = "{log}"{file_status} }}
+
+        [[language]]
+        name = "mock"
+        scope = "source.mock"
+        file-types = ["mock"]
+        roots = []
+        language-servers = ["copilot"]
+        "#,
+        agent = env!("CARGO_BIN_EXE_copilot-mock-agent"),
+        log = log.path().display(),
+    );
+
+    let mut config = test_config();
+    config.editor.lsp = LspConfig {
+        enable: true,
+        ..Default::default()
+    };
+    config.editor.copilot.always_allow = always_allow;
+
+    let app = AppBuilder::new()
+        .with_file(file.path(), None)
+        .with_config(config)
+        .with_lang_loader(test_syntax_loader(Some(lang_conf)))
+        .build()?;
+
+    Ok((app, file, log))
+}
+
+/// Feeds `keys` to the application and runs the event loop until idle.
+async fn send_keys(app: &mut Application, keys: &str) -> anyhow::Result<bool> {
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut rx_stream = UnboundedReceiverStream::new(rx);
+    for key_event in parse_macro(keys)? {
+        tx.send(Ok(crossterm::event::Event::Key(
+            crossterm::event::KeyEvent::from(key_event),
+        )))?;
+    }
+    Ok(app.event_loop_until_idle(&mut rx_stream).await)
+}
+
+/// Keeps the event loop turning until `condition` holds, so asynchronous
+/// agent traffic (initialization, completion responses) gets processed.
+async fn run_until(
+    app: &mut Application,
+    condition: impl Fn(&Application) -> bool,
+    what: &str,
+) -> anyhow::Result<()> {
+    for _ in 0..200 {
+        if condition(app) {
+            return Ok(());
+        }
+        let (_tx, rx) = tokio::sync::mpsc::unbounded_channel::<std::io::Result<crossterm::event::Event>>();
+        let mut rx_stream = UnboundedReceiverStream::new(rx);
+        let _ = tokio::time::timeout(
+            Duration::from_millis(50),
+            app.event_loop_until_idle(&mut rx_stream),
+        )
+        .await;
+    }
+    anyhow::bail!("timed out waiting for {} (doc: {:?})", what, doc_text(app))
+}
+
+/// Turns the event loop a few more times so in-flight agent traffic, if
+/// any, gets processed.
+async fn settle(app: &mut Application) {
+    for _ in 0..10 {
+        let (_tx, rx) =
+            tokio::sync::mpsc::unbounded_channel::<std::io::Result<crossterm::event::Event>>();
+        let mut rx_stream = UnboundedReceiverStream::new(rx);
+        let _ = tokio::time::timeout(
+            Duration::from_millis(50),
+            app.event_loop_until_idle(&mut rx_stream),
+        )
+        .await;
+    }
+}
+
+fn agent_attached(app: &Application) -> bool {
+    doc!(app.editor)
+        .language_servers()
+        .any(|ls| ls.name() == "copilot")
+}
+
+/// A response for an outdated revision is ignored by the picker, so wait
+/// until one for the document as it currently reads has arrived.
+fn has_copilot_response(app: &Application) -> bool {
+    let doc = doc!(app.editor);
+    doc.copilot_state
+        .lock()
+        .as_ref()
+        .is_some_and(|state| &state.doc_at_req == doc.text())
+}
+
+fn doc_text(app: &Application) -> String {
+    doc!(app.editor).text().to_string()
+}
+
+async fn quit(mut app: Application) -> anyhow::Result<()> {
+    send_keys(&mut app, "<esc>:q!<ret>").await?;
+    let errs = app.close().await;
+    if !errs.is_empty() {
+        anyhow::bail!("errors closing app: {:?}", errs);
+    }
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn copilot_cycle_and_accept() -> anyhow::Result<()> {
+    let (mut app, _file, log) = copilot_app(true)?;
+
+    run_until(&mut app, agent_attached, "the mock agent to initialize").await?;
+    send_keys(&mut app, "ihello").await?;
+    run_until(&mut app, has_copilot_response, "a completion response").await?;
+
+    // Opening the picker previews the first suggestion in the document
+    send_keys(&mut app, "<C-n>").await?;
+    assert_eq!(
+        doc_text(&app),
+        "hello and the first mock suggestion".to_string()
+    );
+
+    // Cycling replaces the preview with the next suggestion
+    send_keys(&mut app, "<C-g>").await?;
+    assert_eq!(
+        doc_text(&app),
+        "hello and the second mock suggestion".to_string()
+    );
+
+    // And cycling backward returns to the first one
+    send_keys(&mut app, "<C-m>").await?;
+    assert_eq!(
+        doc_text(&app),
+        "hello and the first mock suggestion".to_string()
+    );
+
+    // Accepting keeps the previewed text
+    send_keys(&mut app, "<ret>").await?;
+    assert_eq!(
+        doc_text(&app),
+        "hello and the first mock suggestion".to_string()
+    );
+
+    quit(app).await?;
+
+    // The mock records everything the editor sent over the wire
+    let log = std::fs::read_to_string(log.path())?;
+    for method in [
+        "initialize",
+        "textDocument/didOpen",
+        "textDocument/didChange",
+        "getCompletionsCycling",
+    ] {
+        assert!(
+            log.lines().any(|line| line == method),
+            "expected {} in the agent log, got:\n{}",
+            method,
+            log
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn copilot_cache_serves_repeated_request() -> anyhow::Result<()> {
+    let (mut app, _file, log) = copilot_app(true)?;
+
+    let completion_requests = |log: &NamedTempFile| -> usize {
+        std::fs::read_to_string(log.path())
+            .unwrap_or_default()
+            .lines()
+            .filter(|line| *line == "getCompletionsCycling")
+            .count()
+    };
+
+    run_until(&mut app, agent_attached, "the mock agent to initialize").await?;
+    send_keys(&mut app, "ihello").await?;
+    run_until(&mut app, has_copilot_response, "a completion response").await?;
+    let baseline = completion_requests(&log);
+
+    // Previewing applies the suggestion (one more request, for the previewed
+    // text); dismissing reverts to already-requested content, which must be
+    // answered from the cache without reaching the agent.
+    send_keys(&mut app, "<C-n>").await?;
+    send_keys(&mut app, "<esc>").await?;
+    assert_eq!(doc_text(&app), "hello".to_string());
+    run_until(&mut app, has_copilot_response, "the cached response").await?;
+
+    settle(&mut app).await;
+    assert_eq!(completion_requests(&log), baseline + 1);
+
+    quit(app).await?;
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn copilot_denied_workspace_sends_no_requests() -> anyhow::Result<()> {
+    // Point the consent store at a throwaway state directory. This test is
+    // the only one touching the store (the others run with `always-allow`),
+    // so it gets to initialize it before first use.
+    let state_home = tempfile::tempdir()?;
+    std::env::set_var("XDG_STATE_HOME", state_home.path());
+
+    let (mut app, _file, log) = copilot_app(false)?;
+
+    let completion_requests = |log: &NamedTempFile| -> usize {
+        std::fs::read_to_string(log.path())
+            .unwrap_or_default()
+            .lines()
+            .filter(|line| *line == "getCompletionsCycling")
+            .count()
+    };
+
+    run_until(&mut app, agent_attached, "the mock agent to initialize").await?;
+
+    // Without recorded consent, typing must not fire automatic requests.
+    send_keys(&mut app, "ihello").await?;
+    settle(&mut app).await;
+    assert_eq!(completion_requests(&log), 0);
+
+    // The manual trigger prompts instead of requesting; answer no.
+    send_keys(&mut app, "<C-n>n").await?;
+    settle(&mut app).await;
+    assert_eq!(completion_requests(&log), 0);
+
+    // The denial is persisted keyed by the workspace root...
+    let consent_file = state_home.path().join("helix").join("copilot-consent");
+    let contents = std::fs::read_to_string(&consent_file)?;
+    assert!(
+        contents.lines().any(|line| line.starts_with("deny ")),
+        "expected a deny entry, got:\n{}",
+        contents
+    );
+
+    // ...and keeps blocking both request paths from now on.
+    send_keys(&mut app, "world").await?;
+    send_keys(&mut app, "<C-n>").await?;
+    settle(&mut app).await;
+    assert_eq!(completion_requests(&log), 0);
+
+    quit(app).await?;
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn copilot_abort_restores_document() -> anyhow::Result<()> {
+    let (mut app, _file, _log) = copilot_app(true)?;
+
+    run_until(&mut app, agent_attached, "the mock agent to initialize").await?;
+    send_keys(&mut app, "ihello").await?;
+    run_until(&mut app, has_copilot_response, "a completion response").await?;
+
+    send_keys(&mut app, "<C-n>").await?;
+    assert_eq!(
+        doc_text(&app),
+        "hello and the first mock suggestion".to_string()
+    );
+
+    // Aborting rolls the preview back to the text as typed
+    send_keys(&mut app, "<esc>").await?;
+    assert_eq!(doc_text(&app), "hello".to_string());
+
+    quit(app).await?;
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn copilot_content_exclusion_blocks_requests() -> anyhow::Result<()> {
+    let (mut app, _file, log) = copilot_app_with_file_status(true, Some("blocked"))?;
+
+    run_until(&mut app, agent_attached, "the mock agent to initialize").await?;
+    run_until(
+        &mut app,
+        |app| doc!(app.editor).copilot_excluded(),
+        "the content-exclusion answer",
+    )
+    .await?;
+
+    // With the document blocked, typing fires neither completion requests
+    // nor document sync towards the agent.
+    send_keys(&mut app, "ihello").await?;
+    send_keys(&mut app, "<esc><C-n>").await?;
+    settle(&mut app).await;
+
+    let log = std::fs::read_to_string(log.path())?;
+    for method in ["getCompletionsCycling", "textDocument/didChange"] {
+        assert!(
+            !log.lines().any(|line| line == method),
+            "expected no {} in the agent log, got:\n{}",
+            method,
+            log
+        );
+    }
+
+    quit(app).await?;
+    Ok(())
+}
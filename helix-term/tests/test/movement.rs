@@ -665,6 +665,12 @@ async fn test_surround_delete() -> anyhow::Result<()> {
         "mdm",
         "\n\n#(\n|)##[\n|]#",
     ))
+    .await?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
 async fn tree_sitter_motions_work_across_injections() -> anyhow::Result<()> {
     test_with_config(
         AppBuilder::new().with_file("foo.html", None),
@@ -703,7 +709,7 @@ async fn tree_sitter_motions_work_across_injections() -> anyhow::Result<()> {
         (
             "<script>let #[|x]# = 1;</script>",
             "<A-n>",
-            "<script>let x #[|=]# 1;</script>",
+            "<script>let x #[=|]# 1;</script>",
         ),
     )
     .await?;
@@ -722,55 +728,66 @@ async fn tree_sitter_motions_work_across_injections() -> anyhow::Result<()> {
 }
 
 #[tokio::test(flavor = "multi_thread")]
-async fn tree_sitter_motions_work_across_injections() -> anyhow::Result<()> {
+async fn expand_shrink_selection_retraces_history() -> anyhow::Result<()> {
+    // Two expands followed by two shrinks restore the original range
+    // exactly, even though a child-based shrink of `(1, 2)` would land on
+    // `(` rather than back on `x`.
     test_with_config(
-        AppBuilder::new().with_file("foo.html", None),
+        AppBuilder::new().with_file("foo.rs", None),
         (
-            "<script>let #[|x]# = 1;</script>",
-            "<A-o>",
-            "<script>let #[|x = 1]#;</script>",
+            "fn main() { let #[|x]# = (1, 2); }",
+            "<A-o><A-o><A-i><A-i>",
+            "fn main() { let #[|x]# = (1, 2); }",
         ),
     )
     .await?;
 
-    // When the full injected layer is selected, expand_selection jumps to
-    // a more shallow layer.
+    // Sibling moves are part of the history as well.
     test_with_config(
-        AppBuilder::new().with_file("foo.html", None),
+        AppBuilder::new().with_file("foo.rs", None),
         (
-            "<script>#[|let x = 1;]#</script>",
-            "<A-o>",
-            "#[|<script>let x = 1;</script>]#",
+            "fn main() { foo(#[|alpha]#, beta); }",
+            "<A-n><A-i>",
+            "fn main() { foo(#[|alpha]#, beta); }",
         ),
     )
     .await?;
 
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn swap_node_siblings() -> anyhow::Result<()> {
+    // Arguments trade places while the comma stays put, and the selection
+    // follows the moved node.
     test_with_config(
-        AppBuilder::new().with_file("foo.html", None),
+        AppBuilder::new().with_file("foo.rs", None),
         (
-            "<script>let #[|x = 1]#;</script>",
-            "<A-i>",
-            "<script>let #[|x]# = 1;</script>",
+            "fn main() { foo(#[|alpha]#, beta); }",
+            "<A-N>",
+            "fn main() { foo(beta, #[|alpha]#); }",
         ),
     )
     .await?;
 
+    // Works on use-declaration lists too, in both directions.
     test_with_config(
-        AppBuilder::new().with_file("foo.html", None),
+        AppBuilder::new().with_file("foo.rs", None),
         (
-            "<script>let #[|x]# = 1;</script>",
-            "<A-n>",
-            "<script>let x #[=|]# 1;</script>",
+            "use foo::{alpha, #[|beta]#};",
+            "<A-P>",
+            "use foo::{#[|beta]#, alpha};",
         ),
     )
     .await?;
 
+    // The first sibling has nothing to swap backward with: no-op.
     test_with_config(
-        AppBuilder::new().with_file("foo.html", None),
+        AppBuilder::new().with_file("foo.rs", None),
         (
-            "<script>let #[|x]# = 1;</script>",
-            "<A-p>",
-            "<script>#[|let]# x = 1;</script>",
+            "fn main() { foo(#[|alpha]#, beta); }",
+            "<A-P>",
+            "fn main() { foo(#[|alpha]#, beta); }",
         ),
     )
     .await?;
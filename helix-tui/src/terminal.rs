@@ -25,7 +25,7 @@ pub struct Config {
 impl From<EditorConfig> for Config {
     fn from(config: EditorConfig) -> Self {
         Self {
-            enable_mouse_capture: config.mouse,
+            enable_mouse_capture: config.mouse.enable,
         }
     }
 }
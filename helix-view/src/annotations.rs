@@ -1 +1,47 @@
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use helix_core::text_annotations::InlineAnnotation;
+
+use crate::graphics::Style;
+
 pub mod diagnostics;
+
+/// Identifies the owner of a batch of [`Annotation`]s so that unrelated
+/// sources (e.g. two different plugins) can annotate the same document
+/// without clobbering or clearing each other's virtual text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AnnotationSourceId(usize);
+
+impl AnnotationSourceId {
+    /// Create a fresh id, distinct from every id handed out before.
+    pub fn new() -> Self {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        Self(COUNTER.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl Default for AnnotationSourceId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A piece of virtual text a third-party source attached to a character
+/// range, drawn right after the range during rendering. Created through
+/// [`Document::annotate_range`](crate::Document::annotate_range) and
+/// independent of LSP diagnostics and inlay hints.
+#[derive(Debug, Clone)]
+pub struct Annotation {
+    /// The source that added this annotation, used for batch removal.
+    pub source: AnnotationSourceId,
+    /// Character range the annotation is attached to.
+    pub range: Range<usize>,
+    /// The rendered text, anchored after the last character of `range`.
+    pub annotation: InlineAnnotation,
+    /// Style the text is drawn with.
+    pub style: Style,
+    /// Index of `style` in the theme's highlight table. Interned lazily
+    /// before rendering and invalidated when the theme changes.
+    pub(crate) highlight: Option<usize>,
+}
@@ -0,0 +1,130 @@
+//! Per-workspace opt-in for sending buffer contents to the copilot agent.
+//!
+//! Answers to the "Enable Copilot for this workspace?" prompt are persisted
+//! in the state directory keyed by workspace root, so a workspace only has
+//! to be approved (or denied) once. `copilot.always-allow` in the config
+//! skips the prompt flow entirely.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+/// The recorded answer for a workspace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Consent {
+    Allowed,
+    Denied,
+    /// No answer recorded yet: the user has to be prompted before any
+    /// request is sent.
+    Unasked,
+}
+
+/// Consent answers keyed by workspace root, backed by a file of
+/// `allow <path>` / `deny <path>` lines.
+#[derive(Debug)]
+pub struct ConsentStore {
+    path: PathBuf,
+    entries: HashMap<PathBuf, bool>,
+}
+
+impl ConsentStore {
+    pub fn load(path: PathBuf) -> Self {
+        let entries = std::fs::read_to_string(&path)
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| {
+                let (verdict, root) = line.split_once(' ')?;
+                let allow = match verdict {
+                    "allow" => true,
+                    "deny" => false,
+                    _ => return None,
+                };
+                Some((PathBuf::from(root), allow))
+            })
+            .collect();
+        Self { path, entries }
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let contents: String = self
+            .entries
+            .iter()
+            .map(|(root, allow)| {
+                let verdict = if *allow { "allow" } else { "deny" };
+                format!("{} {}\n", verdict, root.display())
+            })
+            .collect();
+        std::fs::write(&self.path, contents)
+    }
+
+    pub fn get(&self, root: &Path) -> Consent {
+        match self.entries.get(root) {
+            Some(true) => Consent::Allowed,
+            Some(false) => Consent::Denied,
+            None => Consent::Unasked,
+        }
+    }
+
+    pub fn set(&mut self, root: PathBuf, allow: bool) {
+        self.entries.insert(root, allow);
+    }
+}
+
+fn consent_file() -> PathBuf {
+    helix_loader::state_dir().join("copilot-consent")
+}
+
+static STORE: Lazy<Mutex<ConsentStore>> =
+    Lazy::new(|| Mutex::new(ConsentStore::load(consent_file())));
+
+/// The recorded consent for `root`.
+pub fn consent(root: &Path) -> Consent {
+    STORE.lock().get(root)
+}
+
+/// Records the answer for `root` and persists it. Persistence failures are
+/// logged rather than surfaced: the answer still applies for this session.
+pub fn record(root: &Path, allow: bool) {
+    let mut store = STORE.lock();
+    store.set(root.to_path_buf(), allow);
+    if let Err(err) = store.save() {
+        log::error!(
+            "failed to persist copilot consent to {}: {}",
+            store.path.display(),
+            err
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn consent_round_trips_through_the_store_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("copilot-consent");
+
+        let mut store = ConsentStore::load(path.clone());
+        store.set(PathBuf::from("/projects/allowed"), true);
+        store.set(PathBuf::from("/projects/denied"), false);
+        store.save().unwrap();
+
+        let store = ConsentStore::load(path);
+        assert_eq!(store.get(Path::new("/projects/allowed")), Consent::Allowed);
+        assert_eq!(store.get(Path::new("/projects/denied")), Consent::Denied);
+        assert_eq!(store.get(Path::new("/projects/other")), Consent::Unasked);
+    }
+
+    #[test]
+    fn missing_file_leaves_every_workspace_unasked() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ConsentStore::load(dir.path().join("does-not-exist"));
+        assert_eq!(store.get(Path::new("/anywhere")), Consent::Unasked);
+    }
+}
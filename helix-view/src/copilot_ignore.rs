@@ -0,0 +1,109 @@
+//! Content exclusion for the copilot agent.
+//!
+//! A `.copilotignore` file at the workspace root is parsed with gitignore
+//! semantics; matching documents are never sent to the agent, neither as
+//! completion requests nor as document sync. Agents that implement the
+//! `checkFileStatus` content-exclusion request can block further documents
+//! server-side; their answers are cached here per uri.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+const IGNORE_FILE: &str = ".copilotignore";
+
+/// Compiled `.copilotignore` matchers keyed by workspace root. Roots without
+/// an ignore file are cached as `None` so repeated lookups stay a hashmap
+/// probe instead of a stat per keystroke.
+static MATCHERS: Lazy<Mutex<HashMap<PathBuf, Option<Gitignore>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// `checkFileStatus` answers from the agent, keyed by document uri. `true`
+/// means the agent blocked the document.
+static POLICIES: Lazy<Mutex<HashMap<String, bool>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Whether `path` is kept away from the agent by the `.copilotignore` file
+/// of the workspace it belongs to.
+pub fn excluded(path: &Path) -> bool {
+    let (root, _) = helix_loader::find_workspace();
+    excluded_in(&root, path)
+}
+
+/// Whether `root`'s `.copilotignore` matches `path`. Split out from
+/// [`excluded`] so the matcher can be exercised against a scratch workspace.
+pub fn excluded_in(root: &Path, path: &Path) -> bool {
+    let mut matchers = MATCHERS.lock();
+    let matcher = matchers
+        .entry(root.to_path_buf())
+        .or_insert_with(|| load_matcher(root));
+    matcher.as_ref().is_some_and(|gitignore| {
+        gitignore
+            .matched_path_or_any_parents(path, false)
+            .is_ignore()
+    })
+}
+
+fn load_matcher(root: &Path) -> Option<Gitignore> {
+    let file = root.join(IGNORE_FILE);
+    if !file.exists() {
+        return None;
+    }
+    let mut builder = GitignoreBuilder::new(root);
+    if let Some(err) = builder.add(file) {
+        log::error!("failed to read {}: {}", IGNORE_FILE, err);
+        return None;
+    }
+    match builder.build() {
+        Ok(gitignore) => Some(gitignore),
+        Err(err) => {
+            log::error!("failed to parse {}: {}", IGNORE_FILE, err);
+            None
+        }
+    }
+}
+
+/// Records the agent's `checkFileStatus` answer for `uri`.
+pub fn record_policy(uri: &str, blocked: bool) {
+    POLICIES.lock().insert(uri.to_owned(), blocked);
+}
+
+/// Whether the agent has blocked `uri` via content exclusion. Documents the
+/// agent hasn't been asked about (or that predate agent startup) pass.
+pub fn policy_blocked(uri: &str) -> bool {
+    POLICIES.lock().get(uri).copied().unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copilotignore_matches_with_gitignore_semantics() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join(IGNORE_FILE), "secrets/\n*.pem\n!allowed.pem\n").unwrap();
+
+        assert!(excluded_in(root, &root.join("secrets/vault.txt")));
+        assert!(excluded_in(root, &root.join("deploy/key.pem")));
+        assert!(!excluded_in(root, &root.join("allowed.pem")));
+        assert!(!excluded_in(root, &root.join("src/main.rs")));
+    }
+
+    #[test]
+    fn workspaces_without_an_ignore_file_exclude_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!excluded_in(dir.path(), &dir.path().join("anything.rs")));
+    }
+
+    #[test]
+    fn content_exclusion_answers_are_cached_per_uri() {
+        assert!(!policy_blocked("file:///tmp/copilot-ignore-unasked.rs"));
+        record_policy("file:///tmp/copilot-ignore-blocked.rs", true);
+        record_policy("file:///tmp/copilot-ignore-included.rs", false);
+        assert!(policy_blocked("file:///tmp/copilot-ignore-blocked.rs"));
+        assert!(!policy_blocked("file:///tmp/copilot-ignore-included.rs"));
+    }
+}
@@ -0,0 +1,96 @@
+//! Session counters for the copilot integration.
+//!
+//! Tracks how suggestions fare once they reach the user: previewed, accepted
+//! in full or in part, rejected, plus agent errors. The counters live for
+//! the session only and are viewable through `:copilot-status`; "shown = 0"
+//! and "accepted = 0" point at very different problems, which is hard to
+//! tell apart from logs alone.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static SHOWN: AtomicUsize = AtomicUsize::new(0);
+static ACCEPTED_FULL: AtomicUsize = AtomicUsize::new(0);
+static ACCEPTED_PARTIAL: AtomicUsize = AtomicUsize::new(0);
+static REJECTED: AtomicUsize = AtomicUsize::new(0);
+static ERRORS: AtomicUsize = AtomicUsize::new(0);
+
+/// A point-in-time copy of the counters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub shown: usize,
+    pub accepted_full: usize,
+    pub accepted_partial: usize,
+    pub rejected: usize,
+    pub errors: usize,
+}
+
+/// A suggestion session was previewed in the document.
+pub fn record_shown() {
+    SHOWN.fetch_add(1, Ordering::Relaxed);
+}
+
+/// A previewed suggestion was accepted as-is.
+pub fn record_accepted_full() {
+    ACCEPTED_FULL.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Part of a previewed suggestion was kept (e.g. a word-wise accept).
+pub fn record_accepted_partial() {
+    ACCEPTED_PARTIAL.fetch_add(1, Ordering::Relaxed);
+}
+
+/// A previewed suggestion was dismissed.
+pub fn record_rejected() {
+    REJECTED.fetch_add(1, Ordering::Relaxed);
+}
+
+/// A completion request failed at the agent.
+pub fn record_error() {
+    ERRORS.fetch_add(1, Ordering::Relaxed);
+}
+
+pub fn snapshot() -> Stats {
+    Stats {
+        shown: SHOWN.load(Ordering::Relaxed),
+        accepted_full: ACCEPTED_FULL.load(Ordering::Relaxed),
+        accepted_partial: ACCEPTED_PARTIAL.load(Ordering::Relaxed),
+        rejected: REJECTED.load(Ordering::Relaxed),
+        errors: ERRORS.load(Ordering::Relaxed),
+    }
+}
+
+pub fn reset() {
+    SHOWN.store(0, Ordering::Relaxed);
+    ACCEPTED_FULL.store(0, Ordering::Relaxed);
+    ACCEPTED_PARTIAL.store(0, Ordering::Relaxed);
+    REJECTED.store(0, Ordering::Relaxed);
+    ERRORS.store(0, Ordering::Relaxed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counters_accumulate_and_reset() {
+        reset();
+        record_shown();
+        record_shown();
+        record_accepted_full();
+        record_accepted_partial();
+        record_rejected();
+        record_error();
+
+        let stats = snapshot();
+        assert_eq!(stats.shown, 2);
+        assert_eq!(stats.accepted_full, 1);
+        assert_eq!(stats.accepted_partial, 1);
+        assert_eq!(stats.rejected, 1);
+        assert_eq!(stats.errors, 1);
+
+        reset();
+        let stats = snapshot();
+        assert_eq!(stats.shown, 0);
+        assert_eq!(stats.errors, 0);
+    }
+}
@@ -21,7 +21,7 @@ use serde::de::{self, Deserialize, Deserializer};
 use serde::Serialize;
 use std::borrow::Cow;
 use std::cell::Cell;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::fmt::Display;
 use std::future::Future;
 use std::io;
@@ -40,8 +40,10 @@ use helix_core::{
 };
 
 use crate::{
+    annotations::{Annotation, AnnotationSourceId},
     editor::Config,
     events::{DocumentDidChange, SelectionDidChange},
+    graphics::Style,
     view::ViewPosition,
     DocumentId, Editor, Theme, View, ViewId,
 };
@@ -144,6 +146,9 @@ pub struct Document {
     /// To know if they're up-to-date, check the `id` field in `DocumentInlayHints`.
     pub(crate) inlay_hints: HashMap<ViewId, DocumentInlayHints>,
     pub(crate) jump_labels: HashMap<ViewId, Vec<Overlay>>,
+    /// Virtual text attached by third-party sources through [`Self::annotate_range`],
+    /// kept separate from diagnostics and inlay hints.
+    pub(crate) annotations: Vec<Annotation>,
     /// Set to `true` when the document is updated, reset to `false` on the next inlay hints
     /// update from the LSP
     pub inlay_hints_oudated: bool,
@@ -196,6 +201,11 @@ pub struct Document {
     pub focused_at: std::time::Instant,
     pub readonly: bool,
     pub copilot_state: Arc<Mutex<Option<CopilotState>>>,
+    copilot_cache: Arc<Mutex<CopilotCache>>,
+    /// The 0-indexed lines occupied by the copilot suggestion currently
+    /// previewed in this document, marked in the gutter while the preview is
+    /// up. Maintained by the suggestion picker.
+    pub copilot_preview_lines: Option<std::ops::Range<usize>>,
 }
 
 #[derive(Clone)]
@@ -205,6 +215,42 @@ pub struct CopilotState {
     pub offset_encoding: helix_lsp::OffsetEncoding,
 }
 
+/// Recently received copilot responses, keyed by the exact document content
+/// and cursor position they were requested at.
+///
+/// Re-requesting at a content/cursor the agent already answered (e.g.
+/// dismissing a suggestion, whose revert restores the earlier content) is
+/// served from here without a round trip. Content keying doubles as
+/// invalidation: once the document changes, lookups no longer match.
+#[derive(Default)]
+struct CopilotCache {
+    // LRU order, most recently used last
+    entries: VecDeque<(Rope, usize, CopilotState)>,
+}
+
+impl CopilotCache {
+    fn get(&mut self, text: &Rope, cursor: usize) -> Option<CopilotState> {
+        let index = self
+            .entries
+            .iter()
+            .position(|(key_text, key_cursor, _)| key_text == text && *key_cursor == cursor)?;
+        let entry = self.entries.remove(index).unwrap();
+        let state = entry.2.clone();
+        self.entries.push_back(entry);
+        Some(state)
+    }
+
+    fn insert(&mut self, cursor: usize, state: CopilotState, capacity: usize) {
+        self.entries
+            .retain(|(text, key_cursor, _)| !(text == &state.doc_at_req && *key_cursor == cursor));
+        self.entries
+            .push_back((state.doc_at_req.clone(), cursor, state));
+        while self.entries.len() > capacity {
+            self.entries.pop_front();
+        }
+    }
+}
+
 /// Inlay hints for a single `(Document, View)` combo.
 ///
 /// There are `*_inlay_hints` field for each kind of hints an LSP can send since we offer the
@@ -704,8 +750,11 @@ impl Document {
             version_control_head: None,
             focused_at: std::time::Instant::now(),
             copilot_state: Arc::new(Mutex::new(None)),
+            copilot_cache: Arc::new(Mutex::new(CopilotCache::default())),
+            copilot_preview_lines: None,
             readonly: false,
             jump_labels: HashMap::new(),
+            annotations: Vec::new(),
         }
     }
 
@@ -1469,6 +1518,11 @@ impl Document {
             // TODO: move to hook
             // emit lsp notification
             for language_server in self.language_servers() {
+                // Excluded documents are never synced to the copilot agent,
+                // so it has no stale content to leak into suggestions.
+                if language_server.name() == "copilot" && self.copilot_excluded() {
+                    continue;
+                }
                 let notify = language_server.text_document_did_change(
                     self.versioned_identifier(),
                     &old_doc,
@@ -1480,31 +1534,11 @@ impl Document {
                     tokio::spawn(notify);
                 }
 
-                if language_server.name() == "copilot" {
-                    if let Some(document) = self.copilot_document(language_server) {
-                        let ls = self.language_servers.get("copilot").unwrap().clone();
-                        let copilot_state = self.copilot_state.clone();
-                        let doc_at_req = self.text().clone();
-
-                        tokio::spawn(async move {
-                            let future = match ls.copilot_completion(document) {
-                                Some(f) => f,
-                                None => return,
-                            };
-
-                            let response = match future.await {
-                                Ok(Some(r)) => r,
-                                _ => return,
-                            };
-
-                            let mut state = copilot_state.lock();
-                            *state = Some(CopilotState {
-                                response,
-                                doc_at_req,
-                                offset_encoding: ls.offset_encoding(),
-                            });
-                        });
-                    }
+                if language_server.name() == "copilot"
+                    && self.copilot_workspace_allowed()
+                    && self.copilot_context_allowed()
+                {
+                    self.request_copilot_completion();
                 }
             }
         }
@@ -1512,6 +1546,171 @@ impl Document {
         true
     }
 
+    /// Whether an automatic copilot request may be made for the current
+    /// cursor context:
+    ///
+    /// * with `copilot.trigger-chars` set, the character before the cursor
+    ///   must be one of them,
+    /// * with `copilot.suppress-in-strings` set, string contexts are skipped,
+    /// * with `copilot.only-in-comments` set, only comment and string
+    ///   contexts qualify.
+    ///
+    /// The syntax-based checks pass for documents without a syntax tree.
+    fn copilot_context_allowed(&self) -> bool {
+        let config = self.config.load();
+        let copilot = &config.copilot;
+        if !copilot.only_in_comments
+            && !copilot.suppress_in_strings
+            && copilot.trigger_chars.is_empty()
+        {
+            return true;
+        }
+        let view_id = match self.selections().len() {
+            1 => *self.selections().iter().next().unwrap().0,
+            _ => return true,
+        };
+        let text = self.text().slice(..);
+        let cursor = self.selection(view_id).primary().cursor(text);
+
+        if !copilot.trigger_chars.is_empty() {
+            let triggered =
+                cursor > 0 && copilot.trigger_chars.contains(text.char(cursor - 1));
+            if !triggered {
+                return false;
+            }
+        }
+
+        if let Some(syntax) = self.syntax() {
+            if copilot.suppress_in_strings
+                && helix_core::comment::cursor_in_string(syntax, text, cursor)
+            {
+                return false;
+            }
+            if copilot.only_in_comments
+                && !helix_core::comment::cursor_in_comment_or_string(syntax, text, cursor)
+            {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Whether the workspace has been approved for copilot at all, either
+    /// through `copilot.always-allow` or recorded consent. Without approval
+    /// no buffer contents leave the editor.
+    pub fn copilot_workspace_allowed(&self) -> bool {
+        self.config.load().copilot.always_allow
+            || crate::copilot_consent::consent(&helix_loader::find_workspace().0)
+                == crate::copilot_consent::Consent::Allowed
+    }
+
+    /// Whether this document is kept away from the copilot agent, either by
+    /// a `.copilotignore` match or a content-exclusion answer from the agent
+    /// itself.
+    pub fn copilot_excluded(&self) -> bool {
+        if self
+            .path()
+            .is_some_and(|path| crate::copilot_ignore::excluded(path))
+        {
+            return true;
+        }
+        self.url()
+            .is_some_and(|url| crate::copilot_ignore::policy_blocked(url.as_str()))
+    }
+
+    /// Whether the document exceeds `copilot.max-file-size` or
+    /// `copilot.max-line-count`. Rope length queries are constant time, so
+    /// this is evaluated on demand and picks up the file shrinking below the
+    /// thresholds again without any bookkeeping.
+    pub fn copilot_too_large(&self) -> bool {
+        let config = self.config.load();
+        let copilot = &config.copilot;
+        self.text().len_bytes() > copilot.max_file_size
+            || self.text().len_lines() > copilot.max_line_count
+    }
+
+    /// Sends a completion request to the copilot agent for the document as it
+    /// currently reads, storing the response in `copilot_state`. Requests the
+    /// agent already answered at this content and cursor are served from the
+    /// cache instead.
+    pub fn request_copilot_completion(&self) {
+        if !self.copilot_workspace_allowed() || self.copilot_excluded() || self.copilot_too_large()
+        {
+            return;
+        }
+        let Some(ls) = self.language_servers.get("copilot") else {
+            return;
+        };
+        if !ls.is_initialized() {
+            return;
+        }
+        let Some(document) = self.copilot_document(ls) else {
+            return;
+        };
+        let view_id = match self.selections().len() {
+            1 => *self.selections().iter().next().unwrap().0,
+            _ => return,
+        };
+        let cursor = self
+            .selection(view_id)
+            .primary()
+            .cursor(self.text().slice(..));
+
+        let cache_size = self.config.load().copilot.cache_size;
+        if cache_size > 0 {
+            if let Some(state) = self.copilot_cache.lock().get(self.text(), cursor) {
+                *self.copilot_state.lock() = Some(state);
+                return;
+            }
+        }
+
+        let ls = ls.clone();
+        let copilot_state = self.copilot_state.clone();
+        let copilot_cache = self.copilot_cache.clone();
+        let doc_at_req = self.text().clone();
+
+        tokio::spawn(async move {
+            let future = match ls.copilot_completion(document) {
+                Some(f) => f,
+                None => return,
+            };
+
+            let response = match future.await {
+                Ok(Some(r)) => r,
+                Ok(None) => return,
+                Err(err) => {
+                    crate::copilot_stats::record_error();
+                    log::error!("copilot completion request failed: {}", err);
+                    return;
+                }
+            };
+
+            let state = CopilotState {
+                response,
+                doc_at_req,
+                offset_encoding: ls.offset_encoding(),
+            };
+            if cache_size > 0 {
+                copilot_cache.lock().insert(cursor, state.clone(), cache_size);
+            }
+            *copilot_state.lock() = Some(state);
+        });
+    }
+
+    /// The language id sent to the copilot agent. The agent expects
+    /// vscode-style identifiers which don't always match helix's language
+    /// names; wrong ids noticeably degrade suggestion quality.
+    fn copilot_language_id(&self) -> Option<String> {
+        let config = self.config.load();
+        let mapped = self
+            .language_name()
+            .and_then(|name| map_copilot_language_id(name, &config.copilot.language_overrides));
+        match mapped {
+            Some(id) => Some(id.to_owned()),
+            None => self.language_id().map(str::to_owned),
+        }
+    }
+
     fn copilot_document(&self, copilot: &Client) -> Option<copilot_types::Document> {
         if self.selections.len() != 1 {
             return None;
@@ -1532,7 +1731,7 @@ impl Document {
             indent_size: self.indent_width(),
             version: self.version as usize,
             relative_path: self.relative_path()?.to_str()?.to_owned(),
-            language_id: self.language_id()?.to_owned(),
+            language_id: self.copilot_language_id()?,
             position,
             source: self.text().to_string(),
             uri: self.url()?.to_string(),
@@ -2164,15 +2363,13 @@ impl Document {
             .language_config()
             .and_then(|config| config.text_width)
             .unwrap_or(config.text_width);
-        let mut soft_wrap_at_text_width = self
+        let editor_soft_wrap = &config.soft_wrap;
+        let language_soft_wrap = self
             .language_config()
-            .and_then(|config| {
-                config
-                    .soft_wrap
-                    .as_ref()
-                    .and_then(|soft_wrap| soft_wrap.wrap_at_text_width)
-            })
-            .or(config.soft_wrap.wrap_at_text_width)
+            .and_then(|config| config.soft_wrap.as_ref());
+        let mut soft_wrap_at_text_width = language_soft_wrap
+            .and_then(|soft_wrap| soft_wrap.wrap_at_text_width)
+            .or(editor_soft_wrap.wrap_at_text_width)
             .unwrap_or(false);
         if soft_wrap_at_text_width {
             // if the viewport is smaller than the specified
@@ -2183,12 +2380,6 @@ impl Document {
                 viewport_width = text_width as u16;
             }
         }
-        let config = self.config.load();
-        let editor_soft_wrap = &config.soft_wrap;
-        let language_soft_wrap = self
-            .language
-            .as_ref()
-            .and_then(|config| config.soft_wrap.as_ref());
         let enable_soft_wrap = language_soft_wrap
             .and_then(|soft_wrap| soft_wrap.enable)
             .or(editor_soft_wrap.enable)
@@ -2235,6 +2426,47 @@ impl Document {
         self.jump_labels.remove(&view_id);
     }
 
+    /// Attach `text` as virtual text drawn right after `range`, styled with
+    /// `style`. The annotation stays until the owning source removes it with
+    /// [`Self::clear_annotations`]; positions are not adjusted when the text
+    /// changes, so sources are expected to re-annotate after edits, like
+    /// inlay hints are recomputed.
+    pub fn annotate_range(
+        &mut self,
+        id: AnnotationSourceId,
+        range: std::ops::Range<usize>,
+        text: Cow<'static, str>,
+        style: Style,
+    ) {
+        let annotation = InlineAnnotation::new(range.end, text.as_ref());
+        // Keep the list in document order so the accessor below stays cheap
+        // to consume.
+        let idx = self
+            .annotations
+            .partition_point(|other| other.annotation.char_idx <= range.end);
+        self.annotations.insert(
+            idx,
+            Annotation {
+                source: id,
+                range,
+                annotation,
+                style,
+                highlight: None,
+            },
+        );
+    }
+
+    /// Remove every annotation `id` has attached to this document.
+    pub fn clear_annotations(&mut self, id: AnnotationSourceId) {
+        self.annotations
+            .retain(|annotation| annotation.source != id);
+    }
+
+    /// All annotations currently attached to this document, in document order.
+    pub fn annotations(&self) -> &[Annotation] {
+        &self.annotations
+    }
+
     /// Get the inlay hints for this document and `view_id`.
     pub fn inlay_hints(&self, view_id: ViewId) -> Option<&DocumentInlayHints> {
         self.inlay_hints.get(&view_id)
@@ -2252,6 +2484,29 @@ pub struct ViewData {
     view_position: ViewPosition,
 }
 
+/// Helix language names whose copilot (vscode-style) identifier differs.
+const COPILOT_LANGUAGE_IDS: &[(&str, &str)] = &[
+    ("bash", "shellscript"),
+    ("c-sharp", "csharp"),
+    ("jsx", "javascriptreact"),
+    ("make", "makefile"),
+    ("tsx", "typescriptreact"),
+];
+
+/// Looks up the copilot language id for a helix language `name`, user
+/// `overrides` first. Returns `None` when neither maps the name.
+fn map_copilot_language_id<'a>(
+    name: &str,
+    overrides: &'a HashMap<String, String>,
+) -> Option<&'a str> {
+    if let Some(mapped) = overrides.get(name) {
+        return Some(mapped);
+    }
+    COPILOT_LANGUAGE_IDS
+        .iter()
+        .find_map(|(helix_name, copilot_id)| (*helix_name == name).then_some(*copilot_id))
+}
+
 #[derive(Clone, Debug)]
 pub enum FormatterError {
     SpawningFailed {
@@ -2517,4 +2772,53 @@ mod test {
     decode!(jis0212_decode, "jis0212", "EUC-JP");
     decode!(shift_jis_decode, "shift_jis");
     encode!(shift_jis_encode, "shift_jis");
+
+    #[test]
+    fn copilot_language_id_mapping() {
+        let overrides = HashMap::new();
+        assert_eq!(
+            map_copilot_language_id("tsx", &overrides),
+            Some("typescriptreact")
+        );
+        assert_eq!(
+            map_copilot_language_id("c-sharp", &overrides),
+            Some("csharp")
+        );
+        // Unmapped names fall back to the helix language id
+        assert_eq!(map_copilot_language_id("rust", &overrides), None);
+
+        let overrides: HashMap<_, _> = [
+            ("tsx".to_string(), "tsx".to_string()),
+            ("vue".to_string(), "html".to_string()),
+        ]
+        .into();
+        // User overrides win over the built-in table
+        assert_eq!(map_copilot_language_id("tsx", &overrides), Some("tsx"));
+        assert_eq!(map_copilot_language_id("vue", &overrides), Some("html"));
+    }
+
+    #[test]
+    fn copilot_cache_lru_eviction() {
+        let state = |text: &str| CopilotState {
+            response: copilot_types::CompletionResponse {
+                completions: Vec::new(),
+            },
+            doc_at_req: Rope::from(text),
+            offset_encoding: helix_lsp::OffsetEncoding::Utf8,
+        };
+
+        let mut cache = CopilotCache::default();
+        cache.insert(1, state("a"), 2);
+        cache.insert(2, state("b"), 2);
+        assert!(cache.get(&Rope::from("a"), 1).is_some());
+
+        // "b" is now the least recently used entry and gets evicted
+        cache.insert(3, state("c"), 2);
+        assert!(cache.get(&Rope::from("b"), 2).is_none());
+        assert!(cache.get(&Rope::from("a"), 1).is_some());
+        assert!(cache.get(&Rope::from("c"), 3).is_some());
+
+        // same content at a different cursor is a distinct entry
+        assert!(cache.get(&Rope::from("c"), 4).is_none());
+    }
 }
@@ -18,7 +18,7 @@ use dap::StackFrame;
 use helix_event::dispatch;
 use helix_vcs::DiffProviderRegistry;
 
-use futures_util::stream::select_all::SelectAll;
+use futures_util::stream::{select_all::SelectAll, BoxStream};
 use futures_util::{future, StreamExt};
 use helix_lsp::{Call, LanguageServerId};
 use tokio_stream::wrappers::UnboundedReceiverStream;
@@ -45,6 +45,7 @@ use anyhow::{anyhow, bail, Error};
 pub use helix_core::diagnostic::Severity;
 use helix_core::{
     auto_pairs::AutoPairs,
+    case::Locale,
     syntax::{self, AutoPairConfig, IndentationHeuristic, LanguageServerFeature, SoftWrap},
     Change, LineEnding, Position, Range, Selection, Uri, NATIVE_LINE_ENDING,
 };
@@ -114,6 +115,41 @@ impl From<Vec<GutterType>> for GutterConfig {
     }
 }
 
+fn deserialize_mouse_bool_or_struct<'de, D>(deserializer: D) -> Result<MouseConfig, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct MouseVisitor;
+
+    impl<'de> serde::de::Visitor<'de> for MouseVisitor {
+        type Value = MouseConfig;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(formatter, "a boolean or a detailed mouse configuration")
+        }
+
+        fn visit_bool<E>(self, enable: bool) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            Ok(MouseConfig {
+                enable,
+                ..Default::default()
+            })
+        }
+
+        fn visit_map<M>(self, map: M) -> Result<Self::Value, M::Error>
+        where
+            M: serde::de::MapAccess<'de>,
+        {
+            let deserializer = serde::de::value::MapAccessDeserializer::new(map);
+            Deserialize::deserialize(deserializer)
+        }
+    }
+
+    deserializer.deserialize_any(MouseVisitor)
+}
+
 fn deserialize_gutter_seq_or_struct<'de, D>(deserializer: D) -> Result<GutterConfig, D::Error>
 where
     D: Deserializer<'de>,
@@ -249,6 +285,149 @@ pub struct ExplorerConfig {
     pub position: ExplorerPosition,
     /// explorer column width
     pub column_width: usize,
+    /// render image previews for the focused file, using the terminal
+    /// graphics protocol when available
+    pub image_preview: bool,
+    /// open the file under the cursor in the main area as a transient
+    /// preview buffer while navigating; Enter makes it permanent
+    pub preview_open: bool,
+    /// number of roots kept in the back-navigation history; 0 disables it
+    pub root_history_size: usize,
+    /// how the root path is rendered in the explorer title:
+    /// `absolute`, `tilde` or `basename`
+    pub root_display: ExplorerRootDisplay,
+    /// show a synthetic "Recent" section with recently focused documents
+    /// above the tree
+    pub show_recent: bool,
+    /// git marker behaviour
+    pub git: ExplorerGitConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub struct ExplorerGitConfig {
+    /// Tint entry names by their git status in addition to the markers.
+    /// Defaults to `false`.
+    pub colorize_names: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub struct MouseConfig {
+    /// Master switch; when false the terminal never captures mouse events.
+    pub enable: bool,
+    /// Left click moves the cursor to the clicked position. Disable in
+    /// environments where terminal paste via mouse click should not move
+    /// the cursor.
+    pub click_to_move_cursor: bool,
+    /// The mouse wheel scrolls the view under the pointer.
+    pub scroll: bool,
+    /// Dragging with the left button extends the selection.
+    pub drag_to_select: bool,
+    /// Double-clicking selects the word under the pointer.
+    pub double_click_word_select: bool,
+}
+
+impl Default for MouseConfig {
+    fn default() -> Self {
+        Self {
+            enable: true,
+            click_to_move_cursor: true,
+            scroll: true,
+            drag_to_select: true,
+            double_click_word_select: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub struct CopilotConfig {
+    /// apply accepted suggestions at every cursor rather than only the
+    /// primary one. Defaults to `false`.
+    pub multi_cursor: bool,
+    /// Overrides for the language id sent to the copilot agent, keyed by
+    /// helix language name. Takes precedence over the built-in mapping.
+    pub language_overrides: HashMap<String, String>,
+    /// Only request suggestions automatically when the cursor is inside a
+    /// comment or string. The manual trigger is unaffected. Defaults to
+    /// `false`.
+    pub only_in_comments: bool,
+    /// Characters after which a suggestion may be requested automatically.
+    /// When empty (the default), any edit triggers a request.
+    pub trigger_chars: String,
+    /// Skip automatic requests while the cursor is inside a string node.
+    /// The manual trigger is unaffected. Defaults to `false`.
+    pub suppress_in_strings: bool,
+    /// How many responses to keep for serving identical re-requests without
+    /// a round trip. `0` disables the cache. Defaults to `4`.
+    pub cache_size: usize,
+    /// Mark the lines a previewed suggestion would add with a `+` in the
+    /// gutter. Defaults to `true`.
+    pub gutter_markers: bool,
+    /// Send requests in every workspace without asking. When `false` (the
+    /// default), each workspace must be approved once via the consent
+    /// prompt before any buffer contents are sent to the agent.
+    pub always_allow: bool,
+    /// Documents larger than this many bytes are excluded from copilot.
+    /// Defaults to 1 MiB.
+    pub max_file_size: usize,
+    /// Documents with more lines than this are excluded from copilot.
+    /// Defaults to `10000`.
+    pub max_line_count: usize,
+    /// Bindings used by the suggestion picker, under `[editor.copilot.keys]`.
+    pub keys: CopilotKeysConfig,
+}
+
+impl Default for CopilotConfig {
+    fn default() -> Self {
+        Self {
+            multi_cursor: false,
+            language_overrides: HashMap::new(),
+            only_in_comments: false,
+            trigger_chars: String::new(),
+            suppress_in_strings: false,
+            cache_size: 4,
+            gutter_markers: true,
+            always_allow: false,
+            max_file_size: 1024 * 1024,
+            max_line_count: 10_000,
+            keys: CopilotKeysConfig::default(),
+        }
+    }
+}
+
+/// Bindings active while the copilot suggestion picker is open. Keys are
+/// given in the same notation as the `[keys]` sections, e.g. `C-n` or `ret`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub struct CopilotKeysConfig {
+    /// Cycle to the next suggestion. Defaults to `C-n`.
+    pub next: KeyEvent,
+    /// Cycle to the previous suggestion. Defaults to `C-p`.
+    pub prev: KeyEvent,
+    /// Keep the previewed suggestion. Defaults to `ret`.
+    pub accept: KeyEvent,
+    /// Keep the previewed suggestion and immediately request a fresh one at
+    /// the new cursor position. Defaults to `C-g`.
+    pub accept_and_request: KeyEvent,
+    /// Ask the agent to continue the current suggestion. Defaults to `C-e`.
+    pub extend: KeyEvent,
+    /// Reject the suggestion and close the picker. Defaults to `esc`.
+    pub cancel: KeyEvent,
+}
+
+impl Default for CopilotKeysConfig {
+    fn default() -> Self {
+        Self {
+            next: "C-n".parse().unwrap(),
+            prev: "C-p".parse().unwrap(),
+            accept: "ret".parse().unwrap(),
+            accept_and_request: "C-g".parse().unwrap(),
+            extend: "C-e".parse().unwrap(),
+            cancel: "esc".parse().unwrap(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -258,11 +437,29 @@ pub enum ExplorerPosition {
     Right,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExplorerRootDisplay {
+    /// The full path, as today.
+    #[default]
+    Absolute,
+    /// The home directory prefix folded into `~`.
+    Tilde,
+    /// Just the final path component.
+    Basename,
+}
+
 impl Default for ExplorerConfig {
     fn default() -> Self {
         Self {
             position: ExplorerPosition::Left,
             column_width: 36,
+            image_preview: true,
+            preview_open: false,
+            root_history_size: 20,
+            root_display: ExplorerRootDisplay::default(),
+            show_recent: false,
+            git: ExplorerGitConfig::default(),
         }
     }
 }
@@ -274,8 +471,10 @@ pub struct Config {
     pub scrolloff: usize,
     /// Number of lines to scroll at once. Defaults to 3
     pub scroll_lines: isize,
-    /// Mouse support. Defaults to true.
-    pub mouse: bool,
+    /// Mouse support, either a plain `true`/`false` or a per-feature
+    /// configuration. Defaults to everything enabled.
+    #[serde(deserialize_with = "deserialize_mouse_bool_or_struct")]
+    pub mouse: MouseConfig,
     /// Shell to use for shell commands. Defaults to ["cmd", "/C"] on Windows and ["sh", "-c"] otherwise.
     pub shell: Vec<String>,
     /// Line number mode.
@@ -330,6 +529,10 @@ pub struct Config {
     /// Whether to instruct the LSP to replace the entire word when applying a completion
     /// or to only insert new text
     pub completion_replace: bool,
+    /// Fuzzy-match completion filtering, so the typed text may match across
+    /// word segments (`vit` matches `visit_mut`). When disabled only items
+    /// whose filter text starts with the typed text are kept. Defaults to true.
+    pub completion_fuzzy_match: bool,
     /// `true` if helix should automatically add a line comment token if you're currently in a comment
     /// and press `enter`.
     pub continue_comments: bool,
@@ -361,6 +564,11 @@ pub struct Config {
     pub color_modes: bool,
     /// explore config
     pub explorer: ExplorerConfig,
+    /// copilot config
+    pub copilot: CopilotConfig,
+    /// Custom digraphs for `insert_digraph`, e.g. `"sh" = "щ"`. Looked up
+    /// before the built-in Vim-compatible table.
+    pub digraphs: HashMap<String, String>,
     pub soft_wrap: SoftWrap,
     /// Workspace specific lsp ceiling dirs
     pub workspace_lsp_roots: Vec<PathBuf>,
@@ -368,6 +576,9 @@ pub struct Config {
     pub default_line_ending: LineEndingConfig,
     /// Whether to automatically insert a trailing line-ending on write if missing. Defaults to `true`.
     pub insert_final_newline: bool,
+    /// Locale used for locale-sensitive case mapping, e.g. the Turkish
+    /// dotted/dotless i. Defaults to `auto`, detecting it from the environment.
+    pub locale: Locale,
     /// Enables smart tab
     pub smart_tab: Option<SmartTabConfig>,
     /// Draw border around popups.
@@ -386,6 +597,11 @@ pub struct Config {
     pub end_of_line_diagnostics: DiagnosticFilter,
     // Set to override the default clipboard provider
     pub clipboard_provider: ClipboardProvider,
+    /// Whether syntax tree sibling navigation (`select_next_sibling` /
+    /// `select_prev_sibling`) wraps around to the first/last sibling of the
+    /// same parent instead of escaping to the parent node. Defaults to
+    /// `false`.
+    pub tree_sibling_wrap: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Eq, PartialOrd, Ord)]
@@ -393,6 +609,9 @@ pub struct Config {
 pub struct SmartTabConfig {
     pub enable: bool,
     pub supersede_menu: bool,
+    /// When the cursor is in the middle of a word, trigger completion
+    /// instead of `move_parent_node_end`. Defaults to `false`.
+    pub complete: bool,
 }
 
 impl Default for SmartTabConfig {
@@ -400,6 +619,7 @@ impl Default for SmartTabConfig {
         SmartTabConfig {
             enable: true,
             supersede_menu: false,
+            complete: false,
         }
     }
 }
@@ -476,6 +696,9 @@ pub struct LspConfig {
     pub snippets: bool,
     /// Whether to include declaration in the goto reference query
     pub goto_reference_include_declaration: bool,
+    /// Whether to re-pull workspace diagnostics every 30 seconds from servers
+    /// that support them
+    pub workspace_diagnostic_poll: bool,
 }
 
 impl Default for LspConfig {
@@ -489,6 +712,7 @@ impl Default for LspConfig {
             display_inlay_hints: false,
             snippets: true,
             goto_reference_include_declaration: true,
+            workspace_diagnostic_poll: false,
         }
     }
 }
@@ -500,6 +724,9 @@ pub struct SearchConfig {
     pub smart_case: bool,
     /// Whether the search should wrap after depleting the matches. Default to true.
     pub wrap_around: bool,
+    /// Normalize the search pattern to Unicode NFC before matching, so composed
+    /// and decomposed forms of the same character match each other. Defaults to false.
+    pub unicode_normalize: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -621,6 +848,18 @@ pub enum StatusLineElement {
 
     /// Indicator for selected register
     Register,
+
+    /// Position within the quickfix list and its entry count
+    Quickfix,
+
+    /// Copilot status: shows `"excluded by .copilotignore"` when the current
+    /// document is kept away from the agent
+    Copilot,
+
+    /// The syntactic context of the primary cursor, e.g.
+    /// `impl Explorer ▸ fn handle_event`. Empty for languages without a
+    /// textobject query.
+    SyntaxBreadcrumbs,
 }
 
 // Cursor shape is read and used on every rendered frame and so needs
@@ -975,7 +1214,7 @@ impl Default for Config {
         Self {
             scrolloff: 5,
             scroll_lines: 3,
-            mouse: true,
+            mouse: MouseConfig::default(),
             shell: if cfg!(windows) {
                 vec!["cmd".to_owned(), "/C".to_owned()]
             } else {
@@ -1011,16 +1250,20 @@ impl Default for Config {
             indent_guides: IndentGuidesConfig::default(),
             color_modes: false,
             explorer: ExplorerConfig::default(),
+            copilot: CopilotConfig::default(),
+            digraphs: HashMap::new(),
             soft_wrap: SoftWrap {
                 enable: Some(false),
                 ..SoftWrap::default()
             },
             text_width: 80,
             completion_replace: false,
+            completion_fuzzy_match: true,
             continue_comments: true,
             workspace_lsp_roots: Vec::new(),
             default_line_ending: LineEndingConfig::default(),
             insert_final_newline: true,
+            locale: Locale::default(),
             smart_tab: Some(SmartTabConfig::default()),
             popup_border: PopupBorderConfig::None,
             indent_heuristic: IndentationHeuristic::default(),
@@ -1028,6 +1271,7 @@ impl Default for Config {
             inline_diagnostics: InlineDiagnosticsConfig::default(),
             end_of_line_diagnostics: DiagnosticFilter::Disable,
             clipboard_provider: ClipboardProvider::default(),
+            tree_sibling_wrap: false,
         }
     }
 }
@@ -1037,6 +1281,7 @@ impl Default for SearchConfig {
         Self {
             wrap_around: true,
             smart_case: true,
+            unicode_normalize: false,
         }
     }
 }
@@ -1056,6 +1301,37 @@ pub struct Breakpoint {
 
 use futures_util::stream::{Flatten, Once};
 
+/// A single position a quickfix navigation can jump to.
+#[derive(Debug, Clone)]
+pub struct QuickfixEntry {
+    pub path: PathBuf,
+    /// 0-indexed line
+    pub line: usize,
+    /// The matched line's content, shown when jumping to the entry
+    pub text: String,
+}
+
+/// Positions persisted from the last global search, navigable with the
+/// quickfix commands after the search picker is dismissed.
+#[derive(Debug, Default)]
+pub struct QuickfixList {
+    pub entries: Vec<QuickfixEntry>,
+    /// Index of the entry navigated to last, if any.
+    pub current: Option<usize>,
+}
+
+/// How the `f`/`t` family of commands matches characters, toggled with
+/// `:set-find-mode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FindMode {
+    /// Jump to every occurrence of the typed character.
+    #[default]
+    Literal,
+    /// Skip occurrences inside strings and comments when a syntax tree is
+    /// available.
+    Smart,
+}
+
 pub struct Editor {
     /// Current editing mode.
     pub mode: Mode,
@@ -1074,14 +1350,20 @@ pub struct Editor {
     pub registers: Registers,
     pub macro_recording: Option<(char, Vec<KeyEvent>)>,
     pub macro_replaying: Vec<char>,
+    /// How `find_char` style motions match, see [`FindMode`].
+    pub find_mode: FindMode,
     pub language_servers: helix_lsp::Registry,
     pub diagnostics: BTreeMap<Uri, Vec<(lsp::Diagnostic, LanguageServerId)>>,
     pub diff_providers: DiffProviderRegistry,
 
-    pub debugger: Option<dap::Client>,
-    pub debugger_events: SelectAll<UnboundedReceiverStream<dap::Payload>>,
+    pub debugger: dap::Registry,
+    pub debugger_events: SelectAll<BoxStream<'static, (dap::SessionId, dap::Payload)>>,
     pub breakpoints: HashMap<PathBuf, Vec<Breakpoint>>,
 
+    /// Shared with the global search workers, which replace the list on
+    /// every query run.
+    pub quickfix: Arc<parking_lot::Mutex<Option<QuickfixList>>>,
+
     pub syn_loader: Arc<ArcSwap<syntax::Loader>>,
     pub theme_loader: Arc<theme::Loader>,
     /// last_theme is used for theme previews. We store the current theme here,
@@ -1137,7 +1419,7 @@ pub enum EditorEvent {
     DocumentSaved(DocumentSavedEventResult),
     ConfigEvent(ConfigEvent),
     LanguageServerMessage((LanguageServerId, Call)),
-    DebuggerEvent(dap::Payload),
+    DebuggerEvent((dap::SessionId, dap::Payload)),
     IdleTimer,
     Redraw,
 }
@@ -1232,13 +1514,15 @@ impl Editor {
             selected_register: None,
             macro_recording: None,
             macro_replaying: Vec::new(),
+            find_mode: FindMode::default(),
             theme: theme_loader.default(),
             language_servers,
             diagnostics: BTreeMap::new(),
             diff_providers: DiffProviderRegistry::default(),
-            debugger: None,
+            debugger: dap::Registry::default(),
             debugger_events: SelectAll::new(),
             breakpoints: HashMap::new(),
+            quickfix: Arc::new(parking_lot::Mutex::new(None)),
             syn_loader,
             theme_loader,
             last_theme: None,
@@ -1396,9 +1680,30 @@ impl Editor {
             }
         }
 
+        // Cached annotation highlights index into the old theme's highlight
+        // table; drop them so the styles are re-interned into the new one.
+        for doc in self.documents.values_mut() {
+            for annotation in &mut doc.annotations {
+                annotation.highlight = None;
+            }
+        }
+
         self._refresh();
     }
 
+    /// Intern the styles of third-party document annotations into the theme's
+    /// highlight table so [`View::text_annotations`] can reference them.
+    /// Called once per frame before rendering.
+    pub fn resolve_annotation_highlights(&mut self) {
+        for doc in self.documents.values_mut() {
+            for annotation in &mut doc.annotations {
+                if annotation.highlight.is_none() {
+                    annotation.highlight = Some(self.theme.intern_highlight(annotation.style));
+                }
+            }
+        }
+    }
+
     #[inline]
     pub fn language_server_by_id(
         &self,
@@ -1571,6 +1876,26 @@ impl Editor {
         });
 
         for (_, language_server) in language_servers_not_in_doc {
+            if language_server.name() == "copilot" {
+                // Documents matched by `.copilotignore` are never announced
+                // to the agent at all; for the rest, ask the agent for its
+                // content-exclusion verdict once and cache the answer.
+                if path
+                    .as_deref()
+                    .is_some_and(|path| crate::copilot_ignore::excluded(path))
+                {
+                    continue;
+                }
+                let language_server = language_server.clone();
+                let uri = doc_url.to_string();
+                tokio::spawn(async move {
+                    if let Ok(response) =
+                        language_server.copilot_check_file_status(uri.clone()).await
+                    {
+                        crate::copilot_ignore::record_policy(&uri, response.blocked());
+                    }
+                });
+            }
             // TODO: this now races with on_init code if the init happens too quickly
             tokio::spawn(language_server.text_document_did_open(
                 doc_url.clone(),
@@ -2221,7 +2546,7 @@ impl Editor {
 
     pub fn current_stack_frame(&self) -> Option<&StackFrame> {
         self.debugger
-            .as_ref()
+            .active_client()
             .and_then(|debugger| debugger.current_stack_frame())
     }
 
@@ -314,6 +314,31 @@ fn execution_pause_indicator<'doc>(
     )
 }
 
+/// Marks the lines a previewed copilot suggestion would add with a `+`,
+/// styled like diff additions. The markers follow the preview: they move as
+/// the view scrolls and vanish when the suggestion is accepted or dismissed.
+fn copilot_preview<'doc>(
+    editor: &'doc Editor,
+    doc: &'doc Document,
+    theme: &Theme,
+) -> GutterFn<'doc> {
+    let style = theme.get("diff.plus.gutter");
+    let lines = doc
+        .copilot_preview_lines
+        .clone()
+        .filter(|_| editor.config().copilot.gutter_markers);
+
+    Box::new(
+        move |line: usize, _selected: bool, _first_visual_line: bool, out: &mut String| {
+            if !lines.as_ref()?.contains(&line) {
+                return None;
+            }
+            write!(out, "+").unwrap();
+            Some(style)
+        },
+    )
+}
+
 pub fn diagnostics_or_breakpoints<'doc>(
     editor: &'doc Editor,
     doc: &'doc Document,
@@ -324,10 +349,12 @@ pub fn diagnostics_or_breakpoints<'doc>(
     let mut diagnostics = diagnostic(editor, doc, view, theme, is_focused);
     let mut breakpoints = breakpoints(editor, doc, view, theme, is_focused);
     let mut execution_pause_indicator = execution_pause_indicator(editor, doc, theme, is_focused);
+    let mut copilot_preview = copilot_preview(editor, doc, theme);
 
     Box::new(move |line, selected, first_visual_line: bool, out| {
         execution_pause_indicator(line, selected, first_visual_line, out)
             .or_else(|| breakpoints(line, selected, first_visual_line, out))
+            .or_else(|| copilot_preview(line, selected, first_visual_line, out))
             .or_else(|| diagnostics(line, selected, first_visual_line, out))
     })
 }
@@ -2,7 +2,7 @@ use crate::editor::{Action, Breakpoint};
 use crate::{align_view, Align, Editor};
 use dap::requests::DisconnectArguments;
 use helix_core::Selection;
-use helix_dap::{self as dap, Client, ConnectionType, Payload, Request, ThreadId};
+use helix_dap::{self as dap, Client, ConnectionType, Payload, Request, SessionId, ThreadId};
 use helix_lsp::block_on;
 use log::warn;
 use std::fmt::Write;
@@ -11,13 +11,23 @@ use std::path::PathBuf;
 #[macro_export]
 macro_rules! debugger {
     ($editor:expr) => {{
-        match &mut $editor.debugger {
+        match $editor.debugger.active_client_mut() {
             Some(debugger) => debugger,
             None => return,
         }
     }};
 }
 
+#[macro_export]
+macro_rules! debug_session {
+    ($editor:expr) => {{
+        match $editor.debugger.active_session_mut() {
+            Some(session) => session,
+            None => return,
+        }
+    }};
+}
+
 // general utils:
 pub fn dap_pos_to_pos(doc: &helix_core::Rope, line: usize, column: usize) -> Option<usize> {
     // 1-indexing to 0 indexing
@@ -63,6 +73,13 @@ pub fn jump_to_stack_frame(editor: &mut Editor, frame: &helix_dap::StackFrame) {
         return;
     };
 
+    // Adapters report their own view of the sources; translate back to the
+    // local file (e.g. TypeScript for generated JavaScript).
+    let path = match editor.debugger.active_session() {
+        Some(session) => session.path_from_adapter(&path),
+        None => path,
+    };
+
     if let Err(e) = editor.open(&path, Action::Replace) {
         editor.set_error(format!("Unable to jump to stack frame: {}", e));
         return;
@@ -83,7 +100,7 @@ pub fn jump_to_stack_frame(editor: &mut Editor, frame: &helix_dap::StackFrame) {
 }
 
 pub fn breakpoints_changed(
-    debugger: &mut dap::Client,
+    session: &mut dap::DebugSession,
     path: PathBuf,
     breakpoints: &mut [Breakpoint],
 ) -> Result<(), anyhow::Error> {
@@ -118,10 +135,27 @@ pub fn breakpoints_changed(
         })
         .collect::<Vec<_>>();
 
-    let request = debugger.set_breakpoints(path, source_breakpoints);
+    // The adapter may know the source under a different path (e.g. compiled
+    // output); translate before sending.
+    let adapter_path = session.path_to_adapter(&path);
+    let request = session
+        .client
+        .set_breakpoints(adapter_path, source_breakpoints);
     match block_on(request) {
         Ok(Some(dap_breakpoints)) => {
             for (breakpoint, dap_breakpoint) in breakpoints.iter_mut().zip(dap_breakpoints) {
+                // Adapters that resolve source maps bind the breakpoint to a
+                // different file than the one we asked for; remember the
+                // mapping so later requests and stack frames line up.
+                if let Some(source_path) = dap_breakpoint
+                    .source
+                    .as_ref()
+                    .and_then(|source| source.path.clone())
+                {
+                    if source_path != path {
+                        session.source_map.insert(path.clone(), source_path);
+                    }
+                }
                 breakpoint.id = dap_breakpoint.id;
                 breakpoint.verified = dap_breakpoint.verified;
                 breakpoint.message = dap_breakpoint.message;
@@ -140,11 +174,18 @@ pub fn breakpoints_changed(
 }
 
 impl Editor {
-    pub async fn handle_debugger_message(&mut self, payload: helix_dap::Payload) -> bool {
+    pub async fn handle_debugger_message(
+        &mut self,
+        id: SessionId,
+        payload: helix_dap::Payload,
+    ) -> bool {
         use dap::requests::RunInTerminal;
         use helix_dap::{events, Event};
 
-        let debugger = match self.debugger.as_mut() {
+        // Events are routed to the session that produced them; only the
+        // active one may touch the view or steal the selected thread.
+        let is_active = self.debugger.active_id() == Some(id);
+        let debugger = match self.debugger.client_mut(id) {
             Some(debugger) => debugger,
             None => return false,
         };
@@ -165,13 +206,24 @@ impl Editor {
                             for thread in response.threads {
                                 fetch_stack_trace(debugger, thread.id).await;
                             }
-                            select_thread_id(self, thread_id.unwrap_or_default(), false).await;
+                            if is_active {
+                                select_thread_id(self, thread_id.unwrap_or_default(), false).await;
+                            } else if debugger.thread_id.is_none() {
+                                // Record the stop on the background session
+                                // without jumping the view; switching to it
+                                // later starts from this thread.
+                                debugger.thread_id = thread_id;
+                            }
                         }
                     } else if let Some(thread_id) = thread_id {
                         debugger.thread_states.insert(thread_id, reason.clone()); // TODO: dap uses "type" || "reason" here
 
-                        // whichever thread stops is made "current" (if no previously selected thread).
-                        select_thread_id(self, thread_id, false).await;
+                        if is_active {
+                            // whichever thread stops is made "current" (if no previously selected thread).
+                            select_thread_id(self, thread_id, false).await;
+                        } else if debugger.thread_id.is_none() {
+                            debugger.thread_id = Some(thread_id);
+                        }
                     }
 
                     let scope = match thread_id {
@@ -269,14 +321,19 @@ impl Editor {
                     self.set_status(format!("{} {}", prefix, output));
                 }
                 Event::Initialized(_) => {
+                    let session = match self.debugger.session_mut(id) {
+                        Some(session) => session,
+                        None => return false,
+                    };
+
                     // send existing breakpoints
                     for (path, breakpoints) in &mut self.breakpoints {
                         // TODO: call futures in parallel, await all
-                        let _ = breakpoints_changed(debugger, path.clone(), breakpoints);
+                        let _ = breakpoints_changed(session, path.clone(), breakpoints);
                     }
                     // TODO: fetch breakpoints (in case we're attaching)
 
-                    if debugger.configuration_done().await.is_ok() {
+                    if session.client.configuration_done().await.is_ok() {
                         self.set_status("Debugged application started");
                     }; // TODO: do we need to handle error?
                 }
@@ -326,10 +383,28 @@ impl Editor {
                             }
                         }
                         None => {
-                            self.debugger = None;
-                            self.set_status(
-                                "Terminated debugging session and disconnected debugger.",
-                            );
+                            let removed = self.debugger.remove(id);
+                            let status = if is_active {
+                                match self.debugger.active_name() {
+                                    Some(name) => format!(
+                                        "Terminated debugging session and disconnected debugger. Switched to session '{}'.",
+                                        name
+                                    ),
+                                    None => {
+                                        "Terminated debugging session and disconnected debugger."
+                                            .to_string()
+                                    }
+                                }
+                            } else {
+                                match removed {
+                                    Some(session) => format!(
+                                        "Terminated background debug session '{}'.",
+                                        session.name
+                                    ),
+                                    None => "Terminated background debug session.".to_string(),
+                                }
+                            };
+                            self.set_status(status);
                         }
                     }
                 }
@@ -362,7 +437,7 @@ impl Editor {
                     };
 
                     // Re-borrowing debugger to avoid issues when loading config
-                    let debugger = match self.debugger.as_mut() {
+                    let debugger = match self.debugger.client_mut(id) {
                         Some(debugger) => debugger,
                         None => return false,
                     };
@@ -2,6 +2,7 @@
 use anyhow::{anyhow, Error};
 use helix_core::unicode::{segmentation::UnicodeSegmentation, width::UnicodeWidthStr};
 use serde::de::{self, Deserialize, Deserializer};
+use serde::{Serialize, Serializer};
 use std::fmt;
 
 pub use crate::keyboard::{KeyCode, KeyModifiers, MediaKeyCode, ModifierKeyCode};
@@ -458,6 +459,15 @@ impl<'de> Deserialize<'de> for KeyEvent {
     }
 }
 
+impl Serialize for KeyEvent {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
 #[cfg(feature = "term")]
 impl From<crossterm::event::Event> for Event {
     fn from(event: crossterm::event::Event) -> Self {
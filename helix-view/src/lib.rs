@@ -4,6 +4,9 @@ pub mod macros;
 pub mod annotations;
 pub mod base64;
 pub mod clipboard;
+pub mod copilot_consent;
+pub mod copilot_ignore;
+pub mod copilot_stats;
 pub mod document;
 pub mod editor;
 pub mod events;
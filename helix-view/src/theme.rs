@@ -298,6 +298,20 @@ impl Theme {
         self.highlights[index]
     }
 
+    /// Intern an arbitrary [`Style`] into the highlight table so it can be
+    /// referenced through a highlight index, e.g. by virtual text that is not
+    /// tied to a theme scope. Interning the same style twice yields the same
+    /// index.
+    pub fn intern_highlight(&mut self, style: Style) -> usize {
+        self.highlights
+            .iter()
+            .position(|existing| *existing == style)
+            .unwrap_or_else(|| {
+                self.highlights.push(style);
+                self.highlights.len() - 1
+            })
+    }
+
     #[inline]
     pub fn scope(&self, index: usize) -> &str {
         &self.scopes[index]
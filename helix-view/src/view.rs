@@ -138,8 +138,9 @@ pub struct View {
     // uses two docs because we want to be able to swap between the
     // two last modified docs which we need to manually keep track of
     pub last_modified_docs: [Option<DocumentId>; 2],
-    /// used to store previous selections of tree-sitter objects
-    pub object_selections: Vec<Selection>,
+    /// incremental-selection history: `(before, after)` pairs pushed by the
+    /// tree-sitter expand/sibling commands so shrink can retrace them exactly
+    pub object_selections: Vec<(Selection, Selection)>,
     /// all gutter-related configuration settings, used primarily for gutter rendering
     pub gutters: GutterConfig,
     /// A mapping between documents and the last history revision the view was updated at.
@@ -155,6 +156,12 @@ pub struct View {
     // left to future work. For now we treat all views as focused and give them
     // each their own handler.
     pub diagnostics_handler: DiagnosticsHandler,
+    /// A secondary cursor highlight rendered at this position without moving
+    /// the real cursor, used to preview where an action will take effect
+    /// (e.g. the next search match or the current DAP execution point).
+    /// Styled with the `ui.cursor.virtual` theme key and cleared again on
+    /// the next key event.
+    pub virtual_cursor: Option<Position>,
 }
 
 impl fmt::Debug for View {
@@ -180,6 +187,7 @@ impl View {
             gutters,
             doc_revisions: HashMap::new(),
             diagnostics_handler: DiagnosticsHandler::new(),
+            virtual_cursor: None,
         }
     }
 
@@ -452,6 +460,16 @@ impl View {
             text_annotations.add_overlay(labels, style);
         }
 
+        // Third-party annotations carry their own style, so each one forms its
+        // own single-element layer. The styles are interned into the theme's
+        // highlight table before rendering, see
+        // `Editor::resolve_annotation_highlights`.
+        for annotation in doc.annotations() {
+            let highlight = annotation.highlight.map(Highlight);
+            text_annotations
+                .add_inline_annotations(std::slice::from_ref(&annotation.annotation), highlight);
+        }
+
         if let Some(DocumentInlayHints {
             id: _,
             type_inlay_hints,
@@ -648,6 +666,9 @@ impl View {
     /// Applies a [`Transaction`] to the view.
     pub fn apply(&mut self, transaction: &Transaction, doc: &mut Document) {
         self.jumps.apply(transaction, doc);
+        // The saved selections point into the pre-edit text; retracing them
+        // after a change would restore bogus ranges.
+        self.object_selections.clear();
         self.doc_revisions
             .insert(doc.id(), doc.get_current_revision());
     }